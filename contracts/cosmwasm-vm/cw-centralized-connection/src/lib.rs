@@ -50,8 +50,15 @@ pub fn execute(
             msg,
         } => centralized_connection.recv_message(deps, info, src_network, conn_sn, msg),
         ExecuteMsg::ClaimFees {} => centralized_connection.claim_fees(deps, env, info),
+        ExecuteMsg::AddFee { conn_sn } => centralized_connection.add_fee(deps, info, conn_sn),
         ExecuteMsg::RevertMessage { sn } => centralized_connection.revert_message(deps, info, sn),
         ExecuteMsg::SetAdmin { address } => centralized_connection.set_admin(deps, info, address),
+        ExecuteMsg::SetFeeDenom { denom, rate } => {
+            centralized_connection.set_fee_denom(deps, info, denom, rate)
+        }
+        ExecuteMsg::RemoveFeeDenom { denom } => {
+            centralized_connection.remove_fee_denom(deps, info, denom)
+        }
         ExecuteMsg::SetFee {
             network_id,
             message_fee,
@@ -77,6 +84,14 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             to_json_binary(&centralized_connection.get_receipt(deps.storage, src_network, conn_sn))
         }
 
+        QueryMsg::GetConnSn { sn } => {
+            to_json_binary(&centralized_connection.get_message_conn_sn(deps.storage, sn)?)
+        }
+
+        QueryMsg::GetAddedFee { conn_sn } => {
+            to_json_binary(&centralized_connection.get_added_fee(deps.storage, conn_sn))
+        }
+
         QueryMsg::Admin {} => {
             to_json_binary(&centralized_connection.admin().load(deps.storage).unwrap())
         }
@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Decimal};
 use cw_xcall_lib::network_address::NetId;
 
 #[cw_serde]
@@ -22,12 +22,22 @@ pub enum ExecuteMsg {
     },
 
     ClaimFees {},
+    AddFee {
+        conn_sn: u128,
+    },
     RevertMessage {
         sn: u128,
     },
     SetAdmin {
         address: Addr,
     },
+    SetFeeDenom {
+        denom: String,
+        rate: Decimal,
+    },
+    RemoveFeeDenom {
+        denom: String,
+    },
 }
 
 #[cw_serde]
@@ -40,6 +50,10 @@ pub enum QueryMsg {
     GetFee { nid: NetId, response: bool },
     #[returns(bool)]
     GetReceipt { src_network: NetId, conn_sn: u128 },
+    #[returns(u128)]
+    GetConnSn { sn: u128 },
+    #[returns(u128)]
+    GetAddedFee { conn_sn: u128 },
     //return address of admin
     #[returns(Addr)]
     Admin {},
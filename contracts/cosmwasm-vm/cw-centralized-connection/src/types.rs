@@ -16,6 +16,9 @@ pub enum StorageKey {
     Admin,
     ConnSn,
     Denom,
+    MessageConnSn,
+    AddedFees,
+    FeeDenoms,
 }
 
 impl StorageKey {
@@ -28,6 +31,9 @@ impl StorageKey {
             StorageKey::Admin => "admin",
             StorageKey::ConnSn => "conn_sn",
             StorageKey::Denom => "denom",
+            StorageKey::MessageConnSn => "message_conn_sn",
+            StorageKey::AddedFees => "added_fees",
+            StorageKey::FeeDenoms => "fee_denoms",
         }
     }
 }
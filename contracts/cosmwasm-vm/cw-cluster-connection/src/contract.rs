@@ -1,4 +1,4 @@
-use cosmwasm_std::{coins, Addr, BankMsg, Event, Uint128};
+use cosmwasm_std::{Addr, BankMsg, Coin, Decimal, Event, Uint128};
 use cw_xcall_lib::network_address::NetId;
 
 use super::*;
@@ -116,11 +116,14 @@ impl<'a> ClusterConnection<'a> {
             fee = self.get_fee(deps.storage, to.clone(), sn > 0)?.into();
         }
 
-        let value = self.get_amount_for_denom(&info.funds, self.get_denom(deps.storage));
+        let value = self.get_paid_amount(deps.storage, &info.funds);
 
         if fee > value {
             return Err(ContractError::InsufficientFunds);
         }
+        if sn > 0 {
+            self.store_message_conn_sn(deps.storage, sn as u128, next_conn_sn)?;
+        }
 
         Ok(Response::new()
             .add_attribute("action", "send_message")
@@ -145,6 +148,36 @@ impl<'a> ClusterConnection<'a> {
         Ok(Response::new().add_attribute("action", "set_signature_threshold"))
     }
 
+    pub fn add_fee(
+        &mut self,
+        deps: DepsMut,
+        info: MessageInfo,
+        conn_sn: u128,
+    ) -> Result<Response, ContractError> {
+        if conn_sn == 0 || conn_sn > self.get_conn_sn(deps.storage) {
+            return Err(ContractError::UnknownMessage { conn_sn });
+        }
+
+        let amount = self.get_paid_amount(deps.storage, &info.funds);
+        if amount == 0 {
+            return Err(ContractError::InsufficientFunds);
+        }
+        let total = Uint128::new(self.get_added_fee(deps.storage, conn_sn))
+            .checked_add(Uint128::new(amount))
+            .map_err(StdError::from)?
+            .u128();
+        self.store_added_fee(deps.storage, conn_sn, total)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "add_fee")
+            .add_event(
+                Event::new("FeeAdded")
+                    .add_attribute("connSn", conn_sn.to_string())
+                    .add_attribute("amount", amount.to_string())
+                    .add_attribute("total", total.to_string()),
+            ))
+    }
+
     pub fn recv_message(
         &mut self,
         deps: DepsMut,
@@ -184,6 +217,7 @@ impl<'a> ClusterConnection<'a> {
         Ok(Response::new().add_submessage(xcall_submessage))
     }
 
+    /// Sends the fees collected in the base denom and in every fee denom to the relayer.
     pub fn claim_fees(
         &self,
         deps: DepsMut,
@@ -191,14 +225,55 @@ impl<'a> ClusterConnection<'a> {
         info: MessageInfo,
     ) -> Result<Response, ContractError> {
         self.ensure_relayer(deps.storage, info.sender)?;
-        let contract_balance = self.get_balance(&deps, env, self.get_denom(deps.storage));
-        let msg = BankMsg::Send {
-            to_address: self.get_relayer(deps.storage)?.to_string(),
-            amount: coins(contract_balance, self.get_denom(deps.storage)),
-        };
+        let mut denoms = vec![self.get_denom(deps.storage)];
+        denoms.extend(self.get_fee_denoms(deps.storage)?);
+        let amount: Vec<Coin> = denoms
+            .into_iter()
+            .map(|denom| Coin::new(self.get_balance(&deps, env.clone(), denom.clone()), denom))
+            .filter(|coin| !coin.amount.is_zero())
+            .collect();
+
+        let mut res = Response::new().add_attribute("action", "claim fees");
+        if !amount.is_empty() {
+            res = res.add_message(BankMsg::Send {
+                to_address: self.get_relayer(deps.storage)?.to_string(),
+                amount,
+            });
+        }
+        Ok(res)
+    }
+
+    /// Accepts `denom` for fees, valued at one unit of fee in the base denom for every `rate`
+    /// units. The rate should match the one xcall converts the fees of this connection with.
+    pub fn set_fee_denom(
+        &mut self,
+        deps: DepsMut,
+        info: MessageInfo,
+        denom: String,
+        rate: Decimal,
+    ) -> Result<Response, ContractError> {
+        self.ensure_relayer(deps.storage, info.sender)?;
+        if denom == self.get_denom(deps.storage) || rate.is_zero() {
+            return Err(ContractError::InvalidFeeDenom { denom });
+        }
+        self.store_fee_denom(deps.storage, denom.clone(), rate)?;
+        Ok(Response::new()
+            .add_attribute("action", "set_fee_denom")
+            .add_attribute("denom", denom)
+            .add_attribute("rate", rate.to_string()))
+    }
+
+    pub fn remove_fee_denom(
+        &mut self,
+        deps: DepsMut,
+        info: MessageInfo,
+        denom: String,
+    ) -> Result<Response, ContractError> {
+        self.ensure_relayer(deps.storage, info.sender)?;
+        self.remove_fee_denom_rate(deps.storage, denom.clone());
         Ok(Response::new()
-            .add_attribute("action", "claim fees")
-            .add_message(msg))
+            .add_attribute("action", "remove_fee_denom")
+            .add_attribute("denom", denom))
     }
 
     pub fn set_fee(
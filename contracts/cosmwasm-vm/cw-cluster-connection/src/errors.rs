@@ -18,6 +18,10 @@ pub enum ContractError {
     DuplicateMessage,
     #[error("InsufficientFunds")]
     InsufficientFunds,
+    #[error("Invalid Fee Denom {denom}")]
+    InvalidFeeDenom { denom: String },
+    #[error("Unknown Message {conn_sn}")]
+    UnknownMessage { conn_sn: u128 },
     #[error("ERR_REPLY_ERROR|{code:?}|{msg:?}")]
     ReplyError { code: u64, msg: String },
     #[error("Insufficient Signatures")]
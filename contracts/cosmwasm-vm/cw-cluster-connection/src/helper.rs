@@ -1,7 +1,7 @@
 use std::{collections::HashMap, str::FromStr};
 
 use cosmwasm_schema::QueryResponses;
-use cosmwasm_std::{ensure_eq, Addr, BalanceResponse, BankQuery, Coin, QueryRequest};
+use cosmwasm_std::{ensure_eq, Addr, BalanceResponse, BankQuery, Coin, Decimal, QueryRequest};
 use cw_xcall_lib::network_address::{NetId, NetworkAddress};
 use sha2::Digest;
 use sha3::Keccak256;
@@ -44,13 +44,25 @@ impl<'a> ClusterConnection<'a> {
         Ok(())
     }
 
-    pub fn get_amount_for_denom(&self, funds: &Vec<Coin>, target_denom: String) -> u128 {
-        for coin in funds.iter() {
-            if coin.denom == target_denom {
-                return coin.amount.into();
-            }
-        }
-        0
+    /// Returns the value of `funds` in the base denom. Funds in a fee denom are converted at its
+    /// rate, rounding down, and funds in any other denom are ignored. The value saturates at
+    /// `u128::MAX` instead of overflowing.
+    pub fn get_paid_amount(&self, store: &dyn Storage, funds: &[Coin]) -> u128 {
+        let base_denom = self.get_denom(store);
+        funds
+            .iter()
+            .map(|coin| {
+                if coin.denom == base_denom {
+                    return coin.amount.u128();
+                }
+                self.get_fee_denom_rate(store, &coin.denom)
+                    .map_or(0, |rate| {
+                        coin.amount
+                            .checked_multiply_ratio(Decimal::one().atomics(), rate.atomics())
+                            .map_or(u128::MAX, |amount| amount.u128())
+                    })
+            })
+            .fold(0, u128::saturating_add)
     }
 
     pub fn get_balance(&self, deps: &DepsMut, env: Env, denom: String) -> u128 {
@@ -61,6 +61,12 @@ pub fn execute(
 
         ExecuteMsg::ClaimFees {} => conn.claim_fees(deps, env, info),
 
+        ExecuteMsg::SetFeeDenom { denom, rate } => conn.set_fee_denom(deps, info, denom, rate),
+
+        ExecuteMsg::RemoveFeeDenom { denom } => conn.remove_fee_denom(deps, info, denom),
+
+        ExecuteMsg::AddFee { conn_sn } => conn.add_fee(deps, info, conn_sn),
+
         ExecuteMsg::SendMessage { to, sn, msg } => conn.send_message(deps, info, to, sn, msg),
 
         ExecuteMsg::RecvMessage {
@@ -85,6 +91,12 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             conn_sn,
         } => to_json_binary(&conn.get_receipt(deps.storage, src_network, conn_sn)),
 
+        QueryMsg::GetConnSn { sn } => to_json_binary(&conn.get_message_conn_sn(deps.storage, sn)?),
+
+        QueryMsg::GetAddedFee { conn_sn } => {
+            to_json_binary(&conn.get_added_fee(deps.storage, conn_sn))
+        }
+
         QueryMsg::GetAdmin {} => {
             let admin = conn.get_admin(deps.storage).unwrap();
             to_json_binary(&admin)
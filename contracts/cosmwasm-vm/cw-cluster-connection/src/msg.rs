@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Decimal};
 use cw_xcall_lib::network_address::NetId;
 
 #[cw_serde]
@@ -29,6 +29,19 @@ pub enum ExecuteMsg {
 
     ClaimFees {},
 
+    SetFeeDenom {
+        denom: String,
+        rate: Decimal,
+    },
+
+    RemoveFeeDenom {
+        denom: String,
+    },
+
+    AddFee {
+        conn_sn: u128,
+    },
+
     SendMessage {
         to: NetId,
         sn: i64,
@@ -52,6 +65,12 @@ pub enum QueryMsg {
     #[returns(bool)]
     GetReceipt { src_network: NetId, conn_sn: u128 },
 
+    #[returns(u128)]
+    GetConnSn { sn: u128 },
+
+    #[returns(u128)]
+    GetAddedFee { conn_sn: u128 },
+
     #[returns(Addr)]
     GetAdmin {},
 
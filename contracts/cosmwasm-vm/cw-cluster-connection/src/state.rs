@@ -1,4 +1,4 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Decimal};
 use cw_xcall_lib::network_address::NetId;
 
 use crate::types::StorageKey;
@@ -16,9 +16,12 @@ pub struct ClusterConnection<'a> {
     response_fee: Map<'a, NetId, u128>,
 
     conn_sn: Item<'a, u128>,
+    message_conn_sn: Map<'a, u128, u128>,
+    added_fees: Map<'a, u128, u128>,
     receipts: Map<'a, (String, u128), bool>,
 
     denom: Item<'a, String>,
+    fee_denoms: Map<'a, String, Decimal>,
 }
 
 impl<'a> Default for ClusterConnection<'a> {
@@ -40,9 +43,12 @@ impl<'a> ClusterConnection<'a> {
             response_fee: Map::new(StorageKey::ResponseFee.as_str()),
 
             conn_sn: Item::new(StorageKey::ConnSn.as_str()),
+            message_conn_sn: Map::new(StorageKey::MessageConnSn.as_str()),
+            added_fees: Map::new(StorageKey::AddedFees.as_str()),
             receipts: Map::new(StorageKey::Receipts.as_str()),
 
             denom: Item::new(StorageKey::Denom.as_str()),
+            fee_denoms: Map::new(StorageKey::FeeDenoms.as_str()),
         }
     }
 
@@ -85,6 +91,38 @@ impl<'a> ClusterConnection<'a> {
         Ok(connsn)
     }
 
+    pub fn get_conn_sn(&self, store: &dyn Storage) -> u128 {
+        self.conn_sn.load(store).unwrap_or(0)
+    }
+
+    pub fn store_message_conn_sn(
+        &mut self,
+        store: &mut dyn Storage,
+        sn: u128,
+        conn_sn: u128,
+    ) -> StdResult<()> {
+        self.message_conn_sn.save(store, sn, &conn_sn)?;
+        Ok(())
+    }
+
+    pub fn get_message_conn_sn(&self, store: &dyn Storage, sn: u128) -> StdResult<u128> {
+        self.message_conn_sn.load(store, sn)
+    }
+
+    pub fn get_added_fee(&self, store: &dyn Storage, conn_sn: u128) -> u128 {
+        self.added_fees.load(store, conn_sn).unwrap_or(0)
+    }
+
+    pub fn store_added_fee(
+        &mut self,
+        store: &mut dyn Storage,
+        conn_sn: u128,
+        amount: u128,
+    ) -> StdResult<()> {
+        self.added_fees.save(store, conn_sn, &amount)?;
+        Ok(())
+    }
+
     pub fn store_fee(
         &mut self,
         store: &mut dyn Storage,
@@ -130,6 +168,30 @@ impl<'a> ClusterConnection<'a> {
         self.denom.load(store).unwrap()
     }
 
+    pub fn store_fee_denom(
+        &mut self,
+        store: &mut dyn Storage,
+        denom: String,
+        rate: Decimal,
+    ) -> StdResult<()> {
+        self.fee_denoms.save(store, denom, &rate)?;
+        Ok(())
+    }
+
+    pub fn remove_fee_denom_rate(&mut self, store: &mut dyn Storage, denom: String) {
+        self.fee_denoms.remove(store, denom)
+    }
+
+    pub fn get_fee_denom_rate(&self, store: &dyn Storage, denom: &str) -> Option<Decimal> {
+        self.fee_denoms.load(store, denom.to_string()).ok()
+    }
+
+    pub fn get_fee_denoms(&self, store: &dyn Storage) -> StdResult<Vec<String>> {
+        self.fee_denoms
+            .keys(store, None, None, cosmwasm_std::Order::Ascending)
+            .collect()
+    }
+
     pub fn store_validator(
         &mut self,
         store: &mut dyn Storage,
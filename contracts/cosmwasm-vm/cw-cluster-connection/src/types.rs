@@ -22,6 +22,11 @@ pub enum StorageKey {
     Receipts,
 
     Denom,
+
+    MessageConnSn,
+    AddedFees,
+
+    FeeDenoms,
 }
 
 impl StorageKey {
@@ -40,6 +45,11 @@ impl StorageKey {
             StorageKey::Receipts => "receipts",
 
             StorageKey::Denom => "denom",
+
+            StorageKey::MessageConnSn => "message_conn_sn",
+            StorageKey::AddedFees => "added_fees",
+
+            StorageKey::FeeDenoms => "fee_denoms",
         }
     }
 }
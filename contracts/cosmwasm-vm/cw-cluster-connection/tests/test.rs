@@ -8,7 +8,10 @@ use cosmwasm_std::{
     testing::{mock_dependencies, mock_info, MockApi, MockQuerier},
     Addr, MemoryStorage, OwnedDeps, Uint128,
 };
-use cosmwasm_std::{to_json_binary, Coin, ContractInfoResponse, Event, SystemResult, WasmQuery};
+use cosmwasm_std::{
+    to_json_binary, BankMsg, Coin, ContractInfoResponse, CosmosMsg, Decimal, Event, SystemResult,
+    WasmQuery,
+};
 use cw_xcall_lib::network_address::NetId;
 use k256::{ecdsa::SigningKey, ecdsa::VerifyingKey, elliptic_curve::rand_core::OsRng};
 use std::str::FromStr;
@@ -567,3 +570,107 @@ fn string_to_hex(input: &str) -> String {
         .map(|b| format!("{:02x}", b))
         .collect()
 }
+
+#[test]
+pub fn test_add_fee() {
+    let (mut deps, env, ctx) = instantiate(ADMIN);
+    let msg = ExecuteMsg::SendMessage {
+        to: NetId::from_str("nid").unwrap(),
+        sn: 5,
+        msg: vec![],
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(XCALL, &[]), msg).unwrap();
+    assert_eq!(
+        ctx.get_message_conn_sn(deps.as_ref().storage, 5).unwrap(),
+        1
+    );
+
+    let msg = ExecuteMsg::AddFee { conn_sn: 2 };
+    let info = mock_info("user", &[Coin::new(100, DENOM)]);
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    assert_eq!("Unknown Message 2", res.unwrap_err().to_string());
+
+    let msg = ExecuteMsg::AddFee { conn_sn: 1 };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+    let res = execute(deps.as_mut(), env.clone(), info, msg.clone()).unwrap();
+    let event = Event::new("FeeAdded")
+        .add_attribute("connSn", "1")
+        .add_attribute("amount", "100")
+        .add_attribute("total", "200");
+    assert_eq!(res.events[0], event);
+    assert_eq!(ctx.get_added_fee(deps.as_ref().storage, 1), 200);
+
+    let info = mock_info("user", &[Coin::new(u128::MAX, DENOM)]);
+    let res = execute(deps.as_mut(), env, info, msg);
+    assert!(res.is_err());
+    assert_eq!(ctx.get_added_fee(deps.as_ref().storage, 1), 200);
+}
+
+#[test]
+pub fn test_send_message_paid_in_fee_denom() {
+    let (mut deps, env, _ctx) = instantiate(ADMIN);
+    let nid = NetId::from_str("0x2.icon").unwrap();
+    let msg = ExecuteMsg::SetFee {
+        network_id: nid.clone(),
+        message_fee: 100,
+        response_fee: 0,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::SetFeeDenom {
+        denom: "uusdc".to_string(),
+        rate: Decimal::from_str("2.5").unwrap(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN, &[]),
+        msg.clone(),
+    );
+    assert_eq!("Only Relayer", res.unwrap_err().to_string());
+    execute(deps.as_mut(), env.clone(), mock_info(RELAYER, &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::SendMessage {
+        to: nid,
+        sn: 0,
+        msg: vec![],
+    };
+    let info = mock_info(XCALL, &[Coin::new(249, "uusdc")]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
+    assert_eq!("InsufficientFunds", res.unwrap_err().to_string());
+    let info = mock_info(XCALL, &[Coin::new(250, "uusdc")]);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![Coin::new(250, "uusdc")]);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(RELAYER, &[]),
+        ExecuteMsg::ClaimFees {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: RELAYER.to_string(),
+            amount: vec![Coin::new(250, "uusdc")],
+        })
+    );
+}
+
+#[test]
+pub fn test_paid_amount_saturates() {
+    let (mut deps, env, ctx) = instantiate(ADMIN);
+    let msg = ExecuteMsg::SetFeeDenom {
+        denom: "uusdc".to_string(),
+        rate: Decimal::from_atomics(1u128, 18).unwrap(),
+    };
+    execute(deps.as_mut(), env, mock_info(RELAYER, &[]), msg).unwrap();
+
+    let funds = [Coin::new(u128::MAX, "uusdc"), Coin::new(1, DENOM)];
+    assert_eq!(
+        ctx.get_paid_amount(deps.as_ref().storage, &funds),
+        u128::MAX
+    );
+}
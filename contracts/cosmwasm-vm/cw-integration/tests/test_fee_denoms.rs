@@ -0,0 +1,170 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{coins, Addr, Decimal, Empty};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_xcall_lib::{
+    message::{call_message::CallMessage, envelope::Envelope, AnyMessage},
+    network_address::{NetId, NetworkAddress},
+    xcall_msg::ExecuteMsg,
+};
+
+const ADMIN: &str = "admin";
+const RELAYER: &str = "relayer";
+const USER: &str = "user";
+const BASE_DENOM: &str = "uarch";
+const FEE_DENOM: &str = "uusdc";
+
+fn xcall_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(cw_xcall::execute, cw_xcall::instantiate, cw_xcall::query)
+        .with_reply(cw_xcall::reply);
+    Box::new(contract)
+}
+
+fn centralized_connection_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw_centralized_connection::execute,
+        cw_centralized_connection::instantiate,
+        cw_centralized_connection::query,
+    )
+    .with_reply(cw_centralized_connection::reply);
+    Box::new(contract)
+}
+
+/// Deploys xcall with a centralized connection to "archway" charging 100 in the base denom per
+/// message, and accepting `FEE_DENOM` at 2.5 units per unit of base denom.
+fn setup() -> (App, Addr, Addr) {
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked(USER), coins(1_000, FEE_DENOM))
+            .unwrap();
+    });
+    let admin = Addr::unchecked(ADMIN);
+
+    let code_id = app.store_code(xcall_contract());
+    let xcall = app
+        .instantiate_contract(
+            code_id,
+            admin.clone(),
+            &cw_xcall::msg::InstantiateMsg {
+                network_id: "nid".to_string(),
+                denom: BASE_DENOM.to_string(),
+            },
+            &[],
+            "XCall",
+            None,
+        )
+        .unwrap();
+
+    let code_id = app.store_code(centralized_connection_contract());
+    let connection = app
+        .instantiate_contract(
+            code_id,
+            admin.clone(),
+            &cw_centralized_connection::types::InstantiateMsg {
+                relayer: RELAYER.to_string(),
+                xcall_address: xcall.to_string(),
+                denom: BASE_DENOM.to_string(),
+            },
+            &[],
+            "CentralizedConnection",
+            None,
+        )
+        .unwrap();
+
+    let nid = NetId::from_str("archway").unwrap();
+    app.execute_contract(
+        admin.clone(),
+        xcall.clone(),
+        &ExecuteMsg::SetDefaultConnection {
+            nid: nid.clone(),
+            address: connection.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        admin,
+        xcall.clone(),
+        &ExecuteMsg::SetFeeDenom {
+            denom: FEE_DENOM.to_string(),
+            rate: Decimal::from_str("2.5").unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(RELAYER),
+        connection.clone(),
+        &cw_centralized_connection::msg::ExecuteMsg::SetFee {
+            network_id: nid,
+            message_fee: 100,
+            response_fee: 0,
+        },
+        &[],
+    )
+    .unwrap();
+
+    (app, xcall, connection)
+}
+
+fn send_call(app: &mut App, xcall: &Addr) -> anyhow::Result<cw_multi_test::AppResponse> {
+    let envelope = Envelope::new(
+        AnyMessage::CallMessage(CallMessage {
+            data: vec![1, 2, 3],
+        }),
+        vec![],
+        vec![],
+    );
+    app.execute_contract(
+        Addr::unchecked(USER),
+        xcall.clone(),
+        &ExecuteMsg::SendCall {
+            envelope,
+            to: NetworkAddress::from_str("archway/dapp").unwrap(),
+            max_fee: None,
+        },
+        &coins(250, FEE_DENOM),
+    )
+}
+
+#[test]
+fn test_connection_paid_in_alternate_fee_denom() {
+    let (mut app, xcall, connection) = setup();
+
+    // the connection only knows its base denom
+    let err = send_call(&mut app, &xcall).unwrap_err();
+    assert!(err.root_cause().to_string().contains("InsufficientFunds"));
+
+    app.execute_contract(
+        Addr::unchecked(RELAYER),
+        connection.clone(),
+        &cw_centralized_connection::msg::ExecuteMsg::SetFeeDenom {
+            denom: FEE_DENOM.to_string(),
+            rate: Decimal::from_str("2.5").unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+    send_call(&mut app, &xcall).unwrap();
+
+    let balance = |app: &App, address: &str| {
+        app.wrap()
+            .query_balance(address, FEE_DENOM)
+            .unwrap()
+            .amount
+            .u128()
+    };
+    assert_eq!(balance(&app, connection.as_str()), 250);
+    assert_eq!(balance(&app, USER), 750);
+
+    app.execute_contract(
+        Addr::unchecked(RELAYER),
+        connection.clone(),
+        &cw_centralized_connection::msg::ExecuteMsg::ClaimFees {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(balance(&app, connection.as_str()), 0);
+    assert_eq!(balance(&app, RELAYER), 250);
+}
@@ -61,6 +61,7 @@ impl<'a> CwMockService<'a> {
             sources: Some(sources),
             destinations: Some(destinations),
             rollback,
+            max_fee: None,
         };
         let message: CosmosMsg<Empty> = CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: address,
@@ -120,7 +121,11 @@ impl<'a> CwMockService<'a> {
         };
         let envelope = Envelope::new(msg, sources, destinations);
 
-        let msg = ExecuteMsg::SendCall { envelope, to };
+        let msg = ExecuteMsg::SendCall {
+            envelope,
+            to,
+            max_fee: None,
+        };
         let message: CosmosMsg<Empty> = CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: address,
             msg: to_json_binary(&msg).unwrap(),
@@ -151,7 +156,11 @@ impl<'a> CwMockService<'a> {
             .load(deps.storage)
             .map_err(|_e| ContractError::ModuleAddressNotFound)?;
 
-        let msg = ExecuteMsg::SendCall { to, envelope };
+        let msg = ExecuteMsg::SendCall {
+            to,
+            envelope,
+            max_fee: None,
+        };
         let message: CosmosMsg<Empty> = CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: address,
             msg: to_json_binary(&msg).unwrap(),
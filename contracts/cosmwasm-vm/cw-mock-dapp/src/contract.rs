@@ -48,6 +48,7 @@ impl<'a> CwMockService<'a> {
             sources: None,
             destinations: None,
             rollback,
+            max_fee: None,
         };
         let message: CosmosMsg<Empty> = CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: address,
@@ -86,7 +87,11 @@ impl<'a> CwMockService<'a> {
         };
         let envelope = Envelope::new(msg, vec![], vec![]);
 
-        let msg = ExecuteMsg::SendCall { envelope, to };
+        let msg = ExecuteMsg::SendCall {
+            envelope,
+            to,
+            max_fee: None,
+        };
         let message: CosmosMsg<Empty> = CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: address,
             msg: to_json_binary(&msg).unwrap(),
@@ -0,0 +1,8 @@
+use cosmwasm_schema::cw_serde;
+
+/// Sent to the dapp that originated a two-way message with a result callback once the result of
+/// that message arrives.
+#[cw_serde]
+pub enum ExecuteMsg {
+    HandleCallResult { sn: u128, success: bool },
+}
@@ -1,6 +1,9 @@
+pub mod dapp_callback_msg;
 pub mod dapp_msg;
 pub mod dapp_multi_msg;
 pub mod message;
 pub mod network_address;
 pub mod xcall_connection_msg;
+pub mod xcall_events;
 pub mod xcall_msg;
+pub mod xcall_query_msg;
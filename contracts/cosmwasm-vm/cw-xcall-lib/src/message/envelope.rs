@@ -3,14 +3,34 @@ use cosmwasm_schema::cw_serde;
 
 use super::{
     call_message::CallMessage, call_message_persisted::CallMessagePersisted,
-    call_message_rollback::CallMessageWithRollback, msg_trait::IMessage, msg_type::MessageType,
-    AnyMessage,
+    call_message_rollback::CallMessageWithRollback, expiry::MessageExpiry, msg_trait::IMessage,
+    msg_type::MessageType, AnyMessage,
 };
 #[cw_serde]
 pub struct Envelope {
     pub message: AnyMessage,
     pub sources: Vec<String>,
     pub destinations: Vec<String>,
+    pub expiry: Option<MessageExpiry>,
+    /// Whether the sender is called with `HandleCallResult` once the result arrives. Only messages
+    /// with rollback get a result back.
+    #[serde(default)]
+    pub result_callback: bool,
+    /// Gas limit for the execution of the message by the destination dapp. A dapp running out of
+    /// gas fails the message like any other error.
+    #[serde(default)]
+    pub gas_limit: Option<u64>,
+    /// Number of protocols the sender asks to deliver the message before it is accepted. The
+    /// destination only honours it when the inbound policy of the destination dapp also sets a
+    /// threshold, and then requires the larger of the two. Otherwise every protocol must deliver
+    /// the message.
+    #[serde(default)]
+    pub threshold: Option<u32>,
+    /// Whether the message is delivered in order with the other ordered messages from the same
+    /// sender to the same destination dapp. The destination holds it until the previous ones have
+    /// been delivered.
+    #[serde(default)]
+    pub ordered: bool,
 }
 
 impl Envelope {
@@ -19,13 +39,58 @@ impl Envelope {
             message: msg,
             sources,
             destinations,
+            expiry: None,
+            result_callback: false,
+            gas_limit: None,
+            threshold: None,
+            ordered: false,
         }
     }
+
+    pub fn with_expiry(mut self, expiry: MessageExpiry) -> Self {
+        self.expiry = Some(expiry);
+        self
+    }
+
+    pub fn with_result_callback(mut self) -> Self {
+        self.result_callback = true;
+        self
+    }
+
+    pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+
+    pub fn with_threshold(mut self, threshold: u32) -> Self {
+        self.threshold = Some(threshold);
+        self
+    }
+
+    pub fn with_ordered(mut self) -> Self {
+        self.ordered = true;
+        self
+    }
 }
 
 impl Encodable for Envelope {
     fn rlp_append(&self, stream: &mut common::rlp::RlpStream) {
-        stream.begin_list(4);
+        // Optional fields are trailing items, written up to the last one that is set.
+        let optional_fields = match (
+            &self.expiry,
+            self.result_callback,
+            self.gas_limit,
+            self.threshold,
+            self.ordered,
+        ) {
+            (_, _, _, _, true) => 5,
+            (_, _, _, Some(_), false) => 4,
+            (_, _, Some(_), None, false) => 3,
+            (_, true, None, None, false) => 2,
+            (Some(_), false, None, None, false) => 1,
+            (None, false, None, None, false) => 0,
+        };
+        stream.begin_list(4 + optional_fields);
         stream.append(&Into::<u8>::into(self.message.msg_type().clone()));
         stream.append(&self.message.to_bytes().unwrap());
         stream.begin_list(self.sources.len());
@@ -36,13 +101,38 @@ impl Encodable for Envelope {
         for dest in self.destinations.iter() {
             stream.append(dest);
         }
+        if optional_fields > 0 {
+            match &self.expiry {
+                Some(expiry) => stream.append(expiry),
+                None => stream.begin_list(0),
+            };
+        }
+        if optional_fields > 1 {
+            stream.append(&self.result_callback);
+        }
+        if optional_fields > 2 {
+            match &self.gas_limit {
+                Some(gas_limit) => stream.append(gas_limit),
+                None => stream.begin_list(0),
+            };
+        }
+        if optional_fields > 3 {
+            match &self.threshold {
+                Some(threshold) => stream.append(threshold),
+                None => stream.begin_list(0),
+            };
+        }
+        if self.ordered {
+            stream.append(&self.ordered);
+        }
     }
 }
 
 impl Decodable for Envelope {
     fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
         let msg_int: u8 = rlp.val_at(0)?;
-        let msg_type = MessageType::from(msg_int);
+        let msg_type = MessageType::try_from_int(msg_int)
+            .ok_or(rlp::DecoderError::Custom("Invalid message type"))?;
         let message_bytes: Vec<u8> = rlp.val_at(1)?;
         let message = decode_message(msg_type, message_bytes)?;
 
@@ -50,11 +140,37 @@ impl Decodable for Envelope {
         let sources: Vec<String> = sources.as_list()?;
         let destinations = rlp.at(3)?;
         let destinations: Vec<String> = destinations.as_list()?;
+        let item_count = rlp.item_count()?;
+        let expiry = match item_count > 4 && !rlp.at(4)?.is_empty() {
+            true => Some(rlp.val_at(4)?),
+            false => None,
+        };
+        let result_callback = match item_count > 5 {
+            true => rlp.val_at(5)?,
+            false => false,
+        };
+        let gas_limit = match item_count > 6 && !rlp.at(6)?.is_empty() {
+            true => Some(rlp.val_at(6)?),
+            false => None,
+        };
+        let threshold = match item_count > 7 && !rlp.at(7)?.is_empty() {
+            true => Some(rlp.val_at(7)?),
+            false => None,
+        };
+        let ordered = match item_count > 8 {
+            true => rlp.val_at(8)?,
+            false => false,
+        };
 
         Ok(Envelope {
             message,
             sources,
             destinations,
+            expiry,
+            result_callback,
+            gas_limit,
+            threshold,
+            ordered,
         })
     }
 }
@@ -141,4 +257,86 @@ mod tests {
         assert_eq!(decoded.data(), vec![1, 2, 3]);
         assert_eq!(decoded.to_bytes().unwrap(), encoded)
     }
+
+    #[test]
+    fn test_envelope_with_expiry() {
+        let msg = AnyMessage::CallMessage(CallMessage {
+            data: vec![1, 2, 3],
+        });
+        let envelope = Envelope::new(msg, vec![], vec![]);
+        let legacy = envelope.rlp_bytes().to_vec();
+
+        let envelope = envelope.with_expiry(MessageExpiry::Height(100));
+        let encoded = envelope.rlp_bytes().to_vec();
+        assert_ne!(legacy, encoded);
+
+        let decoded = Envelope::decode(&rlp::Rlp::new(&encoded)).unwrap();
+        assert_eq!(decoded.expiry, Some(MessageExpiry::Height(100)));
+        assert_eq!(envelope, decoded);
+    }
+
+    #[test]
+    fn test_envelope_with_result_callback() {
+        let msg = AnyMessage::CallMessageWithRollback(CallMessageWithRollback {
+            data: vec![1, 2, 3],
+            rollback: vec![1, 2, 3],
+        });
+        let envelope = Envelope::new(msg, vec![], vec![]).with_result_callback();
+
+        let encoded = envelope.rlp_bytes().to_vec();
+        let decoded = Envelope::decode(&rlp::Rlp::new(&encoded)).unwrap();
+        assert_eq!(decoded.expiry, None);
+        assert!(decoded.result_callback);
+        assert_eq!(envelope, decoded);
+
+        let envelope = envelope.with_expiry(MessageExpiry::Timestamp(1_700_000_000));
+        let encoded = envelope.rlp_bytes().to_vec();
+        let decoded = Envelope::decode(&rlp::Rlp::new(&encoded)).unwrap();
+        assert_eq!(envelope, decoded);
+    }
+
+    #[test]
+    fn test_envelope_with_gas_limit() {
+        let msg = AnyMessage::CallMessage(CallMessage {
+            data: vec![1, 2, 3],
+        });
+        let envelope = Envelope::new(msg, vec![], vec![]).with_gas_limit(500_000);
+
+        let encoded = envelope.rlp_bytes().to_vec();
+        let decoded = Envelope::decode(&rlp::Rlp::new(&encoded)).unwrap();
+        assert_eq!(decoded.expiry, None);
+        assert!(!decoded.result_callback);
+        assert_eq!(decoded.gas_limit, Some(500_000));
+        assert_eq!(envelope, decoded);
+    }
+
+    #[test]
+    fn test_envelope_with_threshold() {
+        let msg = AnyMessage::CallMessage(CallMessage {
+            data: vec![1, 2, 3],
+        });
+        let sources = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let envelope = Envelope::new(msg, sources.clone(), sources).with_threshold(2);
+
+        let encoded = envelope.rlp_bytes().to_vec();
+        let decoded = Envelope::decode(&rlp::Rlp::new(&encoded)).unwrap();
+        assert_eq!(decoded.gas_limit, None);
+        assert_eq!(decoded.threshold, Some(2));
+        assert_eq!(envelope, decoded);
+    }
+
+    #[test]
+    fn test_envelope_ordered() {
+        let msg = AnyMessage::CallMessage(CallMessage {
+            data: vec![1, 2, 3],
+        });
+        let envelope = Envelope::new(msg, vec![], vec![]).with_ordered();
+
+        let encoded = envelope.rlp_bytes().to_vec();
+        let decoded = Envelope::decode(&rlp::Rlp::new(&encoded)).unwrap();
+        assert_eq!(decoded.gas_limit, None);
+        assert_eq!(decoded.threshold, None);
+        assert!(decoded.ordered);
+        assert_eq!(envelope, decoded);
+    }
 }
@@ -0,0 +1,82 @@
+use common::rlp::{self, Decodable, DecoderError, Encodable, RlpStream};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::BlockInfo;
+
+/// Deadline after which the destination chain refuses to execute a message.
+#[cw_serde]
+pub enum MessageExpiry {
+    /// Unix time in seconds.
+    Timestamp(u64),
+    /// Block height of the source chain. The destination cannot read the source chain, so it
+    /// only knows the height has passed once it received a message sent from the source chain
+    /// after that height. Until then a late message is still executed, use `Timestamp` for a hard
+    /// deadline.
+    Height(u64),
+}
+
+impl MessageExpiry {
+    /// Whether the deadline has passed at `block` of the destination chain. `source_height` is the
+    /// highest block height of the source chain known to the destination, if any.
+    pub fn is_expired(&self, block: &BlockInfo, source_height: Option<u64>) -> bool {
+        match self {
+            MessageExpiry::Timestamp(time) => block.time.seconds() > *time,
+            MessageExpiry::Height(height) => source_height.map_or(false, |known| known > *height),
+        }
+    }
+}
+
+impl Encodable for MessageExpiry {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        let (kind, value): (u8, u64) = match self {
+            MessageExpiry::Timestamp(time) => (0, *time),
+            MessageExpiry::Height(height) => (1, *height),
+        };
+        stream.begin_list(2).append(&kind).append(&value);
+    }
+}
+
+impl Decodable for MessageExpiry {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, DecoderError> {
+        let kind: u8 = rlp.val_at(0)?;
+        let value: u64 = rlp.val_at(1)?;
+        match kind {
+            0 => Ok(MessageExpiry::Timestamp(value)),
+            1 => Ok(MessageExpiry::Height(value)),
+            _ => Err(DecoderError::Custom("Invalid expiry type")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{testing::mock_env, Timestamp};
+    use rlp::Rlp;
+
+    use super::*;
+
+    #[test]
+    fn test_message_expiry_encoding() {
+        let expiry = MessageExpiry::Timestamp(1_700_000_000);
+        let encoded = expiry.rlp_bytes().to_vec();
+        let decoded = MessageExpiry::decode(&Rlp::new(&encoded)).unwrap();
+        assert_eq!(expiry, decoded);
+
+        let expiry = MessageExpiry::Height(100);
+        let encoded = expiry.rlp_bytes().to_vec();
+        let decoded = MessageExpiry::decode(&Rlp::new(&encoded)).unwrap();
+        assert_eq!(expiry, decoded);
+    }
+
+    #[test]
+    fn test_message_expiry_is_expired() {
+        let mut env = mock_env();
+        env.block.height = 100;
+        env.block.time = Timestamp::from_seconds(1_000);
+
+        assert!(!MessageExpiry::Height(99).is_expired(&env.block, None));
+        assert!(!MessageExpiry::Height(99).is_expired(&env.block, Some(99)));
+        assert!(MessageExpiry::Height(99).is_expired(&env.block, Some(100)));
+        assert!(!MessageExpiry::Timestamp(1_000).is_expired(&env.block, None));
+        assert!(MessageExpiry::Timestamp(999).is_expired(&env.block, None));
+    }
+}
@@ -10,6 +10,7 @@ pub mod call_message;
 pub mod call_message_persisted;
 pub mod call_message_rollback;
 pub mod envelope;
+pub mod expiry;
 pub mod msg_trait;
 pub mod msg_type;
 #[cw_serde]
@@ -1,6 +1,7 @@
+use schemars::JsonSchema;
 use serde::Serialize;
 
-#[derive(Clone, Debug, Serialize, serde::Deserialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Serialize, serde::Deserialize, PartialEq, Eq, JsonSchema)]
 pub enum MessageType {
     CallMessage = 0,
     CallMessageWithRollback = 1,
@@ -19,11 +20,9 @@ impl From<MessageType> for u8 {
 
 impl From<u8> for MessageType {
     fn from(value: u8) -> Self {
-        match value {
-            0 => MessageType::CallMessage,
-            1 => MessageType::CallMessageWithRollback,
-            2 => MessageType::CallMessagePersisted,
-            _ => panic!("unsupported message type"),
+        match MessageType::try_from_int(value) {
+            Some(msg_type) => msg_type,
+            None => panic!("unsupported message type"),
         }
     }
 }
@@ -35,6 +34,17 @@ impl MessageType {
     pub fn from_int(val: u8) -> Self {
         MessageType::from(val)
     }
+
+    /// Like `from_int`, but returns `None` for an unsupported value instead of panicking. Use it
+    /// for values read from untrusted input.
+    pub fn try_from_int(val: u8) -> Option<Self> {
+        match val {
+            0 => Some(MessageType::CallMessage),
+            1 => Some(MessageType::CallMessageWithRollback),
+            2 => Some(MessageType::CallMessagePersisted),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -58,6 +68,15 @@ mod tests {
         assert_eq!(MessageType::from_int(2), MessageType::CallMessagePersisted)
     }
 
+    #[test]
+    fn test_message_type_try_from_int() {
+        assert_eq!(
+            MessageType::try_from_int(1),
+            Some(MessageType::CallMessageWithRollback)
+        );
+        assert_eq!(MessageType::try_from_int(3), None);
+    }
+
     #[test]
     #[should_panic(expected = "unsupported message type")]
     fn test_message_type_from_int_fail() {
@@ -5,6 +5,7 @@ use crate::network_address::NetId;
 #[cw_serde]
 pub enum ExecuteMsg {
     SendMessage { to: NetId, sn: i64, msg: Vec<u8> },
+    AddFee { conn_sn: u128 },
 }
 
 #[cw_serde]
@@ -15,4 +16,6 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(u64)]
     GetFee { nid: NetId, response: bool },
+    #[returns(u128)]
+    GetConnSn { sn: u128 },
 }
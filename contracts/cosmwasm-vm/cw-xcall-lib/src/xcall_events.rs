@@ -0,0 +1,819 @@
+//! Typed definitions of the events emitted by cw-xcall.
+//!
+//! The contract builds its events from these structs and indexers parse them back with
+//! [`XcallEvent::parse`], so both sides share one schema. Every event carries a `version`
+//! attribute set to [`EVENT_SCHEMA_VERSION`], bumped whenever an attribute is added, renamed or
+//! re-encoded. Payloads are base64 encoded and protocol lists are comma separated.
+
+use std::str::FromStr;
+
+use cosmwasm_std::{Attribute, Binary, Event};
+use thiserror::Error;
+
+pub const EVENT_SCHEMA_VERSION: &str = "1";
+
+#[derive(Error, Debug, PartialEq)]
+pub enum EventParseError {
+    #[error("UnexpectedEventType expected {expected} found {found}")]
+    UnexpectedType { expected: String, found: String },
+    #[error("UnsupportedEventVersion {version}")]
+    UnsupportedVersion { version: String },
+    #[error("MissingAttribute {key}")]
+    MissingAttribute { key: String },
+    #[error("InvalidAttribute {key}: {value}")]
+    InvalidAttribute { key: String, value: String },
+}
+
+pub trait XcallEvent: Sized {
+    /// The event type, without the `wasm-` prefix the chain adds to contract events.
+    const EVENT_TYPE: &'static str;
+
+    fn attributes(&self) -> Vec<Attribute>;
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError>;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::EVENT_TYPE)
+            .add_attributes(self.attributes())
+            .add_attribute("version", EVENT_SCHEMA_VERSION)
+    }
+
+    /// Parses an event emitted by cw-xcall, as returned by the contract or as indexed on chain.
+    fn parse(event: &Event) -> Result<Self, EventParseError> {
+        let ty = event.ty.strip_prefix("wasm-").unwrap_or(&event.ty);
+        if ty != Self::EVENT_TYPE {
+            return Err(EventParseError::UnexpectedType {
+                expected: Self::EVENT_TYPE.to_string(),
+                found: event.ty.clone(),
+            });
+        }
+        let attributes = EventAttributes(&event.attributes);
+        let version = attributes.get("version")?;
+        if version != EVENT_SCHEMA_VERSION {
+            return Err(EventParseError::UnsupportedVersion {
+                version: version.to_string(),
+            });
+        }
+        Self::from_attributes(&attributes)
+    }
+}
+
+/// Lookup helpers over the attributes of an event.
+pub struct EventAttributes<'a>(&'a [Attribute]);
+
+impl<'a> EventAttributes<'a> {
+    pub fn get(&self, key: &str) -> Result<&'a str, EventParseError> {
+        self.0
+            .iter()
+            .find(|attribute| attribute.key == key)
+            .map(|attribute| attribute.value.as_str())
+            .ok_or(EventParseError::MissingAttribute {
+                key: key.to_string(),
+            })
+    }
+
+    pub fn parse<T: FromStr>(&self, key: &str) -> Result<T, EventParseError> {
+        let value = self.get(key)?;
+        value.parse().map_err(|_| invalid_attribute(key, value))
+    }
+
+    pub fn bytes(&self, key: &str) -> Result<Vec<u8>, EventParseError> {
+        let value = self.get(key)?;
+        Binary::from_base64(value)
+            .map(|data| data.to_vec())
+            .map_err(|_| invalid_attribute(key, value))
+    }
+
+    pub fn list(&self, key: &str) -> Result<Vec<String>, EventParseError> {
+        Ok(split_list(self.get(key)?))
+    }
+}
+
+fn invalid_attribute(key: &str, value: &str) -> EventParseError {
+    EventParseError::InvalidAttribute {
+        key: key.to_string(),
+        value: value.to_string(),
+    }
+}
+
+fn join_list(list: &[String]) -> String {
+    list.join(",")
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    match value.is_empty() {
+        true => vec![],
+        false => value.split(',').map(|item| item.to_string()).collect(),
+    }
+}
+
+/// A message sent from this chain, with the connections it was sent through.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallMessageSentEvent {
+    pub from: String,
+    pub to: String,
+    pub sn: u128,
+    pub protocols: Vec<String>,
+}
+
+impl XcallEvent for CallMessageSentEvent {
+    const EVENT_TYPE: &'static str = "CallMessageSent";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("from", &self.from),
+            Attribute::new("to", &self.to),
+            Attribute::new("sn", self.sn.to_string()),
+            Attribute::new("protocols", join_list(&self.protocols)),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            from: attributes.get("from")?.to_string(),
+            to: attributes.get("to")?.to_string(),
+            sn: attributes.parse("sn")?,
+            protocols: attributes.list("protocols")?,
+        })
+    }
+}
+
+/// A request from another chain stored for `ExecuteCall`. `connection` is the connection whose
+/// delivery made the request accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallMessageEvent {
+    pub from: String,
+    pub to: String,
+    pub sn: u128,
+    pub req_id: u128,
+    pub data: Vec<u8>,
+    pub connection: String,
+    pub protocols: Vec<String>,
+}
+
+impl XcallEvent for CallMessageEvent {
+    const EVENT_TYPE: &'static str = "CallMessage";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("from", &self.from),
+            Attribute::new("to", &self.to),
+            Attribute::new("sn", self.sn.to_string()),
+            Attribute::new("reqId", self.req_id.to_string()),
+            Attribute::new("data", Binary::from(self.data.as_slice()).to_base64()),
+            Attribute::new("connection", &self.connection),
+            Attribute::new("protocols", join_list(&self.protocols)),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            from: attributes.get("from")?.to_string(),
+            to: attributes.get("to")?.to_string(),
+            sn: attributes.parse("sn")?,
+            req_id: attributes.parse("reqId")?,
+            data: attributes.bytes("data")?,
+            connection: attributes.get("connection")?.to_string(),
+            protocols: attributes.list("protocols")?,
+        })
+    }
+}
+
+/// The execution of a request by the destination dapp.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallExecutedEvent {
+    pub req_id: u128,
+    pub code: u8,
+    pub msg: String,
+}
+
+impl XcallEvent for CallExecutedEvent {
+    const EVENT_TYPE: &'static str = "CallExecuted";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("reqId", self.req_id.to_string()),
+            Attribute::new("code", self.code.to_string()),
+            Attribute::new("msg", &self.msg),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            req_id: attributes.parse("reqId")?,
+            code: attributes.parse("code")?,
+            msg: attributes.get("msg")?.to_string(),
+        })
+    }
+}
+
+/// The result of a two-way message, delivered by `connection`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseMessageEvent {
+    pub sn: u128,
+    pub code: u8,
+    pub connection: String,
+}
+
+impl XcallEvent for ResponseMessageEvent {
+    const EVENT_TYPE: &'static str = "ResponseMessage";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("sn", self.sn.to_string()),
+            Attribute::new("code", self.code.to_string()),
+            Attribute::new("connection", &self.connection),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            sn: attributes.parse("sn")?,
+            code: attributes.parse("code")?,
+            connection: attributes.get("connection")?.to_string(),
+        })
+    }
+}
+
+/// A failed two-way message whose rollback can now be executed by the sending dapp `from`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollbackMessageEvent {
+    pub sn: u128,
+    pub from: String,
+}
+
+impl XcallEvent for RollbackMessageEvent {
+    const EVENT_TYPE: &'static str = "RollbackMessage";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("sn", self.sn.to_string()),
+            Attribute::new("from", &self.from),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            sn: attributes.parse("sn")?,
+            from: attributes.get("from")?.to_string(),
+        })
+    }
+}
+
+/// The execution of a rollback by the sending dapp `from`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollbackExecutedEvent {
+    pub sn: u128,
+    pub from: String,
+}
+
+impl XcallEvent for RollbackExecutedEvent {
+    const EVENT_TYPE: &'static str = "RollbackExecuted";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("sn", self.sn.to_string()),
+            Attribute::new("from", &self.from),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            sn: attributes.parse("sn")?,
+            from: attributes.get("from")?.to_string(),
+        })
+    }
+}
+
+/// The cancellation of a pending rollback by the sending dapp `from`, which ran the rollback
+/// without waiting for the response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollbackCancelledEvent {
+    pub sn: u128,
+    pub from: String,
+}
+
+impl XcallEvent for RollbackCancelledEvent {
+    const EVENT_TYPE: &'static str = "RollbackCancelled";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("sn", self.sn.to_string()),
+            Attribute::new("from", &self.from),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            sn: attributes.parse("sn")?,
+            from: attributes.get("from")?.to_string(),
+        })
+    }
+}
+
+/// An item of an `ExecuteCalls` batch that was not executed, with the error that skipped it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecuteCallSkippedEvent {
+    pub req_id: u128,
+    pub reason: String,
+}
+
+impl XcallEvent for ExecuteCallSkippedEvent {
+    const EVENT_TYPE: &'static str = "ExecuteCallSkipped";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("reqId", self.req_id.to_string()),
+            Attribute::new("reason", &self.reason),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            req_id: attributes.parse("reqId")?,
+            reason: attributes.get("reason")?.to_string(),
+        })
+    }
+}
+
+/// A request dropped by the destination dapp, failing the message `sn` of the source chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForcedRollbackEvent {
+    pub req_id: u128,
+    pub sn: u128,
+}
+
+impl XcallEvent for ForcedRollbackEvent {
+    const EVENT_TYPE: &'static str = "ForcedRollback";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("reqId", self.req_id.to_string()),
+            Attribute::new("sn", self.sn.to_string()),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            req_id: attributes.parse("reqId")?,
+            sn: attributes.parse("sn")?,
+        })
+    }
+}
+
+/// A request that reached this chain after its expiry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageExpiredEvent {
+    pub from: String,
+    pub sn: u128,
+}
+
+impl XcallEvent for MessageExpiredEvent {
+    const EVENT_TYPE: &'static str = "MessageExpired";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("from", &self.from),
+            Attribute::new("sn", self.sn.to_string()),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            from: attributes.get("from")?.to_string(),
+            sn: attributes.parse("sn")?,
+        })
+    }
+}
+
+/// The new pause switches of the network `nid`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkPauseUpdatedEvent {
+    pub nid: String,
+    pub outbound: bool,
+    pub inbound: bool,
+    pub execution: bool,
+}
+
+impl XcallEvent for NetworkPauseUpdatedEvent {
+    const EVENT_TYPE: &'static str = "NetworkPauseUpdated";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("nid", &self.nid),
+            Attribute::new("outbound", self.outbound.to_string()),
+            Attribute::new("inbound", self.inbound.to_string()),
+            Attribute::new("execution", self.execution.to_string()),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            nid: attributes.get("nid")?.to_string(),
+            outbound: attributes.parse("outbound")?,
+            inbound: attributes.parse("inbound")?,
+            execution: attributes.parse("execution")?,
+        })
+    }
+}
+
+/// A change of the global pause.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalPauseUpdatedEvent {
+    pub paused: bool,
+}
+
+impl XcallEvent for GlobalPauseUpdatedEvent {
+    const EVENT_TYPE: &'static str = "GlobalPauseUpdated";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![Attribute::new("paused", self.paused.to_string())]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            paused: attributes.parse("paused")?,
+        })
+    }
+}
+
+/// A message from the network `nid` stored under `id` while its inbound traffic was paused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageQueuedEvent {
+    pub id: u128,
+    pub nid: String,
+}
+
+impl XcallEvent for MessageQueuedEvent {
+    const EVENT_TYPE: &'static str = "MessageQueued";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("id", self.id.to_string()),
+            Attribute::new("nid", &self.nid),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            id: attributes.parse("id")?,
+            nid: attributes.get("nid")?.to_string(),
+        })
+    }
+}
+
+/// A queued message discarded by the pauser `dropped_by` without being replayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedMessageDroppedEvent {
+    pub id: u128,
+    pub nid: String,
+    pub dropped_by: String,
+}
+
+impl XcallEvent for QueuedMessageDroppedEvent {
+    const EVENT_TYPE: &'static str = "QueuedMessageDropped";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("id", self.id.to_string()),
+            Attribute::new("nid", &self.nid),
+            Attribute::new("dropped_by", &self.dropped_by),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            id: attributes.parse("id")?,
+            nid: attributes.get("nid")?.to_string(),
+            dropped_by: attributes.get("dropped_by")?.to_string(),
+        })
+    }
+}
+
+/// The replay of `count` queued messages from the network `nid`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InboundResumedEvent {
+    pub nid: String,
+    pub count: u64,
+}
+
+impl XcallEvent for InboundResumedEvent {
+    const EVENT_TYPE: &'static str = "InboundResumed";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("nid", &self.nid),
+            Attribute::new("count", self.count.to_string()),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            nid: attributes.get("nid")?.to_string(),
+            count: attributes.parse("count")?,
+        })
+    }
+}
+
+/// Extra fee for the message `sn`, forwarded to `connection` where the message is `conn_sn`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeBumpedEvent {
+    pub sn: u128,
+    pub connection: String,
+    pub conn_sn: u128,
+}
+
+impl XcallEvent for FeeBumpedEvent {
+    const EVENT_TYPE: &'static str = "FeeBumped";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("sn", self.sn.to_string()),
+            Attribute::new("connection", &self.connection),
+            Attribute::new("connSn", self.conn_sn.to_string()),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            sn: attributes.parse("sn")?,
+            connection: attributes.get("connection")?.to_string(),
+            conn_sn: attributes.parse("connSn")?,
+        })
+    }
+}
+
+/// A result callback the sending dapp failed to handle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallResultFailedEvent {
+    pub reason: String,
+}
+
+impl XcallEvent for CallResultFailedEvent {
+    const EVENT_TYPE: &'static str = "CallResultFailed";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![Attribute::new("reason", &self.reason)]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            reason: attributes.get("reason")?.to_string(),
+        })
+    }
+}
+
+/// A partially delivered message removed from storage. `kind` is `request` or `response` and
+/// `hash` the hash of the message payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingDeliveryPurgedEvent {
+    pub kind: String,
+    pub hash: Vec<u8>,
+    pub delivered: Vec<String>,
+}
+
+impl XcallEvent for PendingDeliveryPurgedEvent {
+    const EVENT_TYPE: &'static str = "PendingDeliveryPurged";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("kind", &self.kind),
+            Attribute::new("hash", Binary::from(self.hash.as_slice()).to_base64()),
+            Attribute::new("delivered", join_list(&self.delivered)),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            kind: attributes.get("kind")?.to_string(),
+            hash: attributes.bytes("hash")?,
+            delivered: attributes.list("delivered")?,
+        })
+    }
+}
+
+/// An ordered request held until the requests before it in its channel have been delivered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallMessageBufferedEvent {
+    pub from: String,
+    pub to: String,
+    pub sn: u128,
+    pub ordered_sn: u128,
+}
+
+impl XcallEvent for CallMessageBufferedEvent {
+    const EVENT_TYPE: &'static str = "CallMessageBuffered";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("from", &self.from),
+            Attribute::new("to", &self.to),
+            Attribute::new("sn", self.sn.to_string()),
+            Attribute::new("ordered_sn", self.ordered_sn.to_string()),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            from: attributes.get("from")?.to_string(),
+            to: attributes.get("to")?.to_string(),
+            sn: attributes.parse("sn")?,
+            ordered_sn: attributes.parse("ordered_sn")?,
+        })
+    }
+}
+
+/// A position of an ordered channel skipped by the admin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderedSequenceSkippedEvent {
+    pub from: String,
+    pub to: String,
+    pub ordered_sn: u128,
+}
+
+impl XcallEvent for OrderedSequenceSkippedEvent {
+    const EVENT_TYPE: &'static str = "OrderedSequenceSkipped";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("from", &self.from),
+            Attribute::new("to", &self.to),
+            Attribute::new("ordered_sn", self.ordered_sn.to_string()),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            from: attributes.get("from")?.to_string(),
+            to: attributes.get("to")?.to_string(),
+            ordered_sn: attributes.parse("ordered_sn")?,
+        })
+    }
+}
+
+/// A request rejected by the inbound policy of its destination dapp `to`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallMessageRejectedEvent {
+    pub from: String,
+    pub to: String,
+    pub sn: u128,
+}
+
+impl XcallEvent for CallMessageRejectedEvent {
+    const EVENT_TYPE: &'static str = "CallMessageRejected";
+
+    fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            Attribute::new("from", &self.from),
+            Attribute::new("to", &self.to),
+            Attribute::new("sn", self.sn.to_string()),
+        ]
+    }
+
+    fn from_attributes(attributes: &EventAttributes) -> Result<Self, EventParseError> {
+        Ok(Self {
+            from: attributes.get("from")?.to_string(),
+            to: attributes.get("to")?.to_string(),
+            sn: attributes.parse("sn")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_message_rejected_event_round_trip() {
+        let event = CallMessageRejectedEvent {
+            from: "archway/dapp".to_string(),
+            to: "dapp".to_string(),
+            sn: 5,
+        };
+        let emitted = event.to_event();
+        assert_eq!(emitted.ty, "CallMessageRejected");
+        assert_eq!(CallMessageRejectedEvent::parse(&emitted), Ok(event));
+    }
+
+    #[test]
+    fn test_operational_events_round_trip() {
+        let event = NetworkPauseUpdatedEvent {
+            nid: "archway".to_string(),
+            outbound: true,
+            inbound: false,
+            execution: true,
+        };
+        assert_eq!(
+            NetworkPauseUpdatedEvent::parse(&event.to_event()),
+            Ok(event)
+        );
+
+        let event = PendingDeliveryPurgedEvent {
+            kind: "request".to_string(),
+            hash: vec![4, 5, 6],
+            delivered: vec!["centralized".to_string()],
+        };
+        assert_eq!(
+            PendingDeliveryPurgedEvent::parse(&event.to_event()),
+            Ok(event)
+        );
+
+        let event = FeeBumpedEvent {
+            sn: 2,
+            connection: "centralized".to_string(),
+            conn_sn: 9,
+        };
+        assert_eq!(FeeBumpedEvent::parse(&event.to_event()), Ok(event));
+
+        let event = CallMessageBufferedEvent {
+            from: "archway/dapp".to_string(),
+            to: "dapp".to_string(),
+            sn: 4,
+            ordered_sn: 2,
+        };
+        assert_eq!(
+            CallMessageBufferedEvent::parse(&event.to_event()),
+            Ok(event)
+        );
+    }
+
+    #[test]
+    fn test_call_message_event_round_trip() {
+        let event = CallMessageEvent {
+            from: "archway/xcall".to_string(),
+            to: "dapp".to_string(),
+            sn: 7,
+            req_id: 3,
+            data: vec![1, 2, 3],
+            connection: "centralized".to_string(),
+            protocols: vec!["centralized".to_string(), "ibc".to_string()],
+        };
+        let emitted = event.to_event();
+        assert_eq!(emitted.ty, "CallMessage");
+        assert_eq!(CallMessageEvent::parse(&emitted), Ok(event.clone()));
+
+        let mut indexed = emitted;
+        indexed.ty = "wasm-CallMessage".to_string();
+        assert_eq!(CallMessageEvent::parse(&indexed), Ok(event));
+    }
+
+    #[test]
+    fn test_parse_empty_protocols() {
+        let event = CallMessageSentEvent {
+            from: "dapp".to_string(),
+            to: "archway/xcall".to_string(),
+            sn: 1,
+            protocols: vec![],
+        };
+        assert_eq!(CallMessageSentEvent::parse(&event.to_event()), Ok(event));
+    }
+
+    #[test]
+    fn test_parse_rejects_other_events() {
+        let event = RollbackMessageEvent {
+            sn: 1,
+            from: "dapp".to_string(),
+        }
+        .to_event();
+        assert_eq!(
+            RollbackExecutedEvent::parse(&event),
+            Err(EventParseError::UnexpectedType {
+                expected: "RollbackExecuted".to_string(),
+                found: "RollbackMessage".to_string(),
+            })
+        );
+
+        let unversioned = Event::new("RollbackMessage").add_attribute("sn", "1");
+        assert_eq!(
+            RollbackMessageEvent::parse(&unversioned),
+            Err(EventParseError::MissingAttribute {
+                key: "version".to_string()
+            })
+        );
+
+        let mut newer = event;
+        newer.attributes.last_mut().unwrap().value = "2".to_string();
+        assert_eq!(
+            RollbackMessageEvent::parse(&newer),
+            Err(EventParseError::UnsupportedVersion {
+                version: "2".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_attribute() {
+        let event = Event::new("CallExecuted")
+            .add_attribute("reqId", "one")
+            .add_attribute("code", "1")
+            .add_attribute("msg", "success")
+            .add_attribute("version", EVENT_SCHEMA_VERSION);
+        assert_eq!(
+            CallExecutedEvent::parse(&event),
+            Err(EventParseError::InvalidAttribute {
+                key: "reqId".to_string(),
+                value: "one".to_string(),
+            })
+        );
+    }
+}
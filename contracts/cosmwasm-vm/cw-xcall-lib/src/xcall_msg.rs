@@ -1,16 +1,52 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Decimal};
 
 use crate::{
     message::envelope::Envelope,
     network_address::{NetId, NetworkAddress},
+    xcall_query_msg::InboundPolicy,
 };
 
+/// The roles the admin grants next to its own. The admin can act with every role.
+#[cw_serde]
+pub enum Role {
+    /// Sets the protocol fee, the fee handler and the accepted fee denoms.
+    FeeManager,
+    /// Sets the default connections.
+    ConnectionManager,
+    /// Pauses and unpauses the contract.
+    Pauser,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::FeeManager => "fee_manager",
+            Role::ConnectionManager => "connection_manager",
+            Role::Pauser => "pauser",
+        }
+    }
+}
+
 #[cw_serde]
 pub enum ExecuteMsg {
+    /// Proposes `address` as the next admin. The admin changes once `address` sends
+    /// `AcceptAdmin`.
     SetAdmin {
         address: String,
     },
+    AcceptAdmin {},
+    GrantRole {
+        role: Role,
+        address: String,
+    },
+    RevokeRole {
+        role: Role,
+        address: String,
+    },
+    RenounceRole {
+        role: Role,
+    },
     SetProtocolFee {
         value: u128,
     },
@@ -24,10 +60,12 @@ pub enum ExecuteMsg {
         rollback: Option<Vec<u8>>,
         sources: Option<Vec<String>>,
         destinations: Option<Vec<String>>,
+        max_fee: Option<u128>,
     },
     SendCall {
         envelope: Envelope,
         to: NetworkAddress,
+        max_fee: Option<u128>,
     },
     HandleMessage {
         from_nid: NetId,
@@ -41,6 +79,14 @@ pub enum ExecuteMsg {
         request_id: u128,
         data: Vec<u8>,
     },
+    ExecuteCallWithGasLimit {
+        request_id: u128,
+        data: Vec<u8>,
+        gas_limit: u64,
+    },
+    ExecuteCalls {
+        items: Vec<(u128, Vec<u8>)>,
+    },
 
     ExecuteRollback {
         sequence_no: u128,
@@ -49,4 +95,106 @@ pub enum ExecuteMsg {
         nid: NetId,
         address: Addr,
     },
+    HandleForcedRollback {
+        req_id: u128,
+    },
+    /// Grants the pauser role to `address`, same as `GrantRole`.
+    SetPauser {
+        address: String,
+    },
+    SetNetworkPause {
+        nid: NetId,
+        outbound: bool,
+        inbound: bool,
+        execution: bool,
+    },
+    SetGlobalPause {
+        paused: bool,
+    },
+    ResumeInbound {
+        nid: NetId,
+        limit: Option<u32>,
+    },
+    /// Discards a message queued while inbound traffic from `nid` was paused. Pauser only.
+    DropQueuedMessage {
+        nid: NetId,
+        id: u128,
+    },
+    SetPayloadLimits {
+        nid: Option<NetId>,
+        max_data_size: u64,
+        max_rollback_size: u64,
+    },
+    RemovePayloadLimits {
+        nid: NetId,
+    },
+    SetFeeDenom {
+        denom: String,
+        rate: Decimal,
+    },
+    RemoveFeeDenom {
+        denom: String,
+    },
+    DepositFees {
+        dapp: String,
+    },
+    WithdrawFees {
+        denom: String,
+        amount: u128,
+        to: Option<String>,
+    },
+    AddFeeSponsor {
+        sponsor: String,
+    },
+    RemoveFeeSponsor {
+        sponsor: String,
+    },
+    BumpFee {
+        sn: u128,
+        connection: String,
+    },
+    SetPendingDeliveryTtl {
+        ttl: Option<u64>,
+    },
+    PurgeExpiredDeliveries {
+        limit: Option<u32>,
+    },
+    PurgePendingDelivery {
+        hash: Vec<u8>,
+    },
+    SkipOrderedSequence {
+        from: NetworkAddress,
+        to: String,
+        ordered_sn: u128,
+    },
+    ReleaseBuffered {
+        from: NetworkAddress,
+        to: String,
+        limit: Option<u32>,
+    },
+    ContinueBackfill {
+        limit: Option<u32>,
+    },
+    SetInboundPolicy {
+        policy: InboundPolicy,
+    },
+    RemoveInboundPolicy {},
+    SetDappConnections {
+        nid: NetId,
+        sources: Vec<String>,
+        destinations: Vec<String>,
+    },
+    RemoveDappConnections {
+        nid: NetId,
+    },
+    SetRollbackGracePeriod {
+        period: Option<u64>,
+    },
+    CancelRollback {
+        sn: u128,
+    },
+    SetExtendedMessages {
+        nid: NetId,
+        enabled: bool,
+    },
 }
@@ -0,0 +1,191 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal};
+
+use crate::{
+    message::{expiry::MessageExpiry, msg_type::MessageType},
+    network_address::{NetId, NetworkAddress},
+};
+
+/// A two-way message sent from this chain that is still waiting for its result.
+#[cw_serde]
+pub struct RollbackResponse {
+    pub sn: u128,
+    pub from: Addr,
+    pub to: NetworkAddress,
+    pub protocols: Vec<String>,
+    pub rollback: Vec<u8>,
+    pub enabled: bool,
+}
+
+#[cw_serde]
+pub struct RollbacksResponse {
+    pub rollbacks: Vec<RollbackResponse>,
+}
+
+/// A request received from another chain that is waiting for `ExecuteCall`. Only the hash of the
+/// payload is kept on chain.
+#[cw_serde]
+pub struct ProxyRequestResponse {
+    pub req_id: u128,
+    pub from: NetworkAddress,
+    pub to: Addr,
+    pub sn: u128,
+    pub msg_type: MessageType,
+    pub data_hash: Vec<u8>,
+    pub protocols: Vec<String>,
+}
+
+#[cw_serde]
+pub struct ProxyRequestsResponse {
+    pub requests: Vec<ProxyRequestResponse>,
+}
+
+/// Lifecycle of a message sent from this chain. One-way messages stay `Sent`, two-way messages
+/// move from `AwaitingResponse` to either `Succeeded` or `RollbackEnabled` and then `RolledBack`.
+#[cw_serde]
+pub enum MessageStatus {
+    Sent,
+    AwaitingResponse,
+    RollbackEnabled,
+    RolledBack,
+    Succeeded,
+    Cancelled,
+}
+
+#[cw_serde]
+pub struct PauseStateResponse {
+    pub global: bool,
+    pub outbound: bool,
+    pub inbound: bool,
+    pub execution: bool,
+    pub queued_messages: u64,
+}
+
+/// Maximum sizes, in bytes, of the encoded message and of the rollback data sent to a network.
+#[cw_serde]
+pub struct PayloadLimits {
+    pub max_data_size: u64,
+    pub max_rollback_size: u64,
+}
+
+/// The messages a dapp accepts from other chains. A message is delivered when its sender is one of
+/// `sources` or belongs to one of `networks`, and the protocols that delivered it form one of
+/// `protocol_sets`. An empty list puts no restriction. A message sent without protocols is matched
+/// as delivered by the connection that delivered it.
+///
+/// `threshold` is the number of protocols that must deliver a request sent with a threshold
+/// before it is accepted. The threshold claimed by the request can only raise it, and without
+/// one every protocol of the request must deliver it.
+#[cw_serde]
+#[derive(Default)]
+pub struct InboundPolicy {
+    #[serde(default)]
+    pub sources: Vec<NetworkAddress>,
+    #[serde(default)]
+    pub networks: Vec<NetId>,
+    #[serde(default)]
+    pub protocol_sets: Vec<Vec<String>>,
+    #[serde(default)]
+    pub threshold: Option<u32>,
+}
+
+/// The connections a dapp sends through to a network when its envelope names none. `sources` are
+/// the connections on this chain and `destinations` their counterparts on the destination chain.
+#[cw_serde]
+pub struct DappConnections {
+    pub sources: Vec<String>,
+    pub destinations: Vec<String>,
+}
+
+/// A denom accepted for fees next to the base denom. `rate` is the amount of `denom` charged for
+/// one unit of fee quoted in the base denom.
+#[cw_serde]
+pub struct FeeDenom {
+    pub denom: String,
+    pub rate: Decimal,
+}
+
+/// A fee drawn from a dapp's prepaid balance by `send_call`.
+#[cw_serde]
+pub struct FeeUsage {
+    pub sn: u128,
+    pub to: NetId,
+    pub denom: String,
+    pub amount: u128,
+}
+
+/// A message sent over several protocols that some of them have not delivered yet. `accepted` is
+/// set once enough protocols delivered it under a threshold, the entry then only waits for the
+/// remaining deliveries. `received_at` is the time of the first delivery in seconds.
+#[cw_serde]
+pub struct PendingDeliveryResponse {
+    pub hash: Vec<u8>,
+    pub delivered: Vec<String>,
+    pub missing: Vec<String>,
+    pub received_at: u64,
+    pub accepted: bool,
+}
+
+#[cw_serde]
+pub struct PendingDeliveriesResponse {
+    pub deliveries: Vec<PendingDeliveryResponse>,
+}
+
+/// A request of an ordered channel that arrived ahead of its turn.
+#[cw_serde]
+pub struct BufferedRequestResponse {
+    pub ordered_sn: u128,
+    pub sn: u128,
+    pub msg_type: MessageType,
+    pub data: Vec<u8>,
+    pub protocols: Vec<String>,
+}
+
+#[cw_serde]
+pub struct OrderedChannelResponse {
+    pub next_sn: u128,
+    pub buffered: Vec<BufferedRequestResponse>,
+}
+
+#[cw_serde]
+pub enum DecodedMessageType {
+    Request,
+    Result,
+    /// A request encoded with optional fields after the legacy ones.
+    RequestV2,
+}
+
+/// A `CSMessage` decoded from its RLP encoding. `request` is set for requests and `result` for
+/// results.
+#[cw_serde]
+pub struct DecodedCsMessage {
+    pub message_type: DecodedMessageType,
+    pub request: Option<DecodedRequest>,
+    pub result: Option<DecodedResult>,
+}
+
+#[cw_serde]
+pub struct DecodedRequest {
+    pub from: NetworkAddress,
+    pub to: String,
+    pub sn: u128,
+    pub msg_type: MessageType,
+    pub data: Vec<u8>,
+    pub protocols: Vec<String>,
+    pub expiry: Option<MessageExpiry>,
+    pub gas_limit: Option<u64>,
+    pub threshold: Option<u32>,
+    pub ordered_sn: Option<u128>,
+}
+
+/// The result of a two-way message. `code` is 1 on success and 0 on failure, and `reply` is the
+/// request sent back by the destination dapp, if any.
+#[cw_serde]
+pub struct DecodedResult {
+    pub sn: u128,
+    pub code: u8,
+    pub reply: Option<DecodedRequest>,
+    /// The replies that follow `reply`, in the order they were sent.
+    #[serde(default)]
+    pub additional_replies: Vec<DecodedRequest>,
+}
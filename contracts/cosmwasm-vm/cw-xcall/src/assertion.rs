@@ -1,10 +1,11 @@
 use cosmwasm_std::to_json_binary;
+use cw_xcall_lib::{message::envelope::Envelope, network_address::NetId};
 
 use super::*;
 
 use crate::{
     error::ContractError,
-    state::{CwCallService, MAX_DATA_SIZE, MAX_ROLLBACK_SIZE},
+    state::CwCallService,
     types::{request::CSMessageRequest, rollback::Rollback},
 };
 
@@ -47,6 +48,8 @@ impl<'a> CwCallService<'a> {
     ///
     /// Arguments:
     ///
+    /// * `store`: The contract storage holding the configured payload limits.
+    /// * `nid`: The destination network, whose override takes precedence over the default limit.
     /// * `data_len`: `data_len` is a variable of type `usize` that represents the length of some data. It
     /// is used as a parameter in the `ensure_data_length` function to check if the length of the data is
     /// within the maximum allowed size. If the length of the data exceeds the maximum size,
@@ -55,9 +58,15 @@ impl<'a> CwCallService<'a> {
     ///
     /// The `ensure_data_length` function returns a `Result` type with the success case containing an empty
     /// tuple `()` and the error case containing a `ContractError`.
-    pub fn ensure_data_length(&self, data_len: usize) -> Result<(), ContractError> {
+    pub fn ensure_data_length(
+        &self,
+        store: &dyn Storage,
+        nid: &NetId,
+        data_len: usize,
+    ) -> Result<(), ContractError> {
+        let limits = self.get_payload_limits(store, nid);
         ensure!(
-            data_len <= MAX_DATA_SIZE as usize,
+            data_len as u64 <= limits.max_data_size,
             ContractError::MaxDataSizeExceeded
         );
 
@@ -68,18 +77,26 @@ impl<'a> CwCallService<'a> {
     ///
     /// Arguments:
     ///
+    /// * `store`: The contract storage holding the configured payload limits.
+    /// * `nid`: The destination network, whose override takes precedence over the default limit.
     /// * `rollback`: `rollback` is a slice of bytes (`&[u8]`) that represents the data to be rolled back in
     /// a smart contract. The function `ensure_rollback_length` checks if the length of the `rollback` slice
-    /// is within the maximum allowed size (`MAX_ROLLBACK_SIZE`) and is not empty.
+    /// is within the maximum allowed rollback size for `nid` and is not empty.
     ///
     /// Returns:
     ///
     /// a `Result` type with the `Ok` variant containing an empty tuple `()` and the `Err` variant
     /// containing a `ContractError` if the condition in the `ensure!` macro is not met.
 
-    pub fn ensure_rollback_length(&self, rollback: &[u8]) -> Result<(), ContractError> {
+    pub fn ensure_rollback_length(
+        &self,
+        store: &dyn Storage,
+        nid: &NetId,
+        rollback: &[u8],
+    ) -> Result<(), ContractError> {
+        let limits = self.get_payload_limits(store, nid);
         ensure!(
-            rollback.is_empty() || rollback.len() <= MAX_ROLLBACK_SIZE as usize,
+            rollback.is_empty() || rollback.len() as u64 <= limits.max_rollback_size,
             ContractError::MaxRollbackSizeExceeded
         );
 
@@ -105,7 +122,7 @@ impl<'a> CwCallService<'a> {
         req_id: u128,
         message: &CSMessageRequest,
     ) -> Result<(), ContractError> {
-        let data = to_json_binary(message).unwrap();
+        let data = to_json_binary(message).map_err(ContractError::Std)?;
         ensure!(
             !(data.is_empty()),
             ContractError::InvalidRequestId { id: req_id }
@@ -132,7 +149,7 @@ impl<'a> CwCallService<'a> {
         sequence_no: u128,
         message: &Rollback,
     ) -> Result<(), ContractError> {
-        let data = to_json_binary(message).unwrap();
+        let data = to_json_binary(message).map_err(ContractError::Std)?;
         ensure!(
             !(data.is_empty()),
             ContractError::InvalidSequenceId { id: sequence_no }
@@ -182,6 +199,29 @@ impl<'a> CwCallService<'a> {
 
         Ok(())
     }
+
+    /// Ensures the threshold of an envelope, if any, can be met by the protocols it is sent and
+    /// answered over.
+    ///
+    /// Arguments:
+    ///
+    /// * `envelope`: The envelope being sent.
+    ///
+    /// Returns:
+    ///
+    /// `ContractError::InvalidThreshold` if the threshold is zero or larger than the number of
+    /// sources or destinations.
+    pub fn ensure_valid_threshold(&self, envelope: &Envelope) -> Result<(), ContractError> {
+        if let Some(threshold) = envelope.threshold {
+            let protocols = envelope.sources.len().min(envelope.destinations.len());
+            ensure!(
+                threshold > 0 && threshold as usize <= protocols,
+                ContractError::InvalidThreshold { threshold }
+            );
+        }
+
+        Ok(())
+    }
 }
 
 /// The function checks if a given address is a valid smart contract by querying its information using a
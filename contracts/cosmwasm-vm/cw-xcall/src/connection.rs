@@ -1,20 +1,25 @@
 use crate::types::{message::CSMessage, LOG_PREFIX};
 use common::rlp;
+use cosmwasm_std::{ensure, MessageInfo, Reply, Response, SubMsgResult};
 use cosmwasm_std::{
-    to_json_binary, Addr, Coin, CosmosMsg, Deps, DepsMut, QueryRequest, SubMsg, WasmMsg,
+    to_json_binary, Addr, Coin, CosmosMsg, Deps, DepsMut, QueryRequest, Storage, SubMsg, WasmMsg,
 };
-use cosmwasm_std::{MessageInfo, Response};
+use cw_xcall_lib::message::envelope::Envelope;
 use cw_xcall_lib::network_address::NetId;
 use cw_xcall_lib::xcall_connection_msg;
+use cw_xcall_lib::xcall_msg::Role;
+use cw_xcall_lib::xcall_query_msg::DappConnections;
 
 use crate::{
     error::ContractError,
+    events::event_fee_bumped,
     state::{CwCallService, SEND_CALL_MESSAGE_REPLY_ID},
 };
 
 impl<'a> CwCallService<'a> {
     pub fn call_connection_send_message(
         &self,
+        store: &dyn Storage,
         address: &Addr,
         fee: Vec<Coin>,
         to: NetId,
@@ -22,7 +27,7 @@ impl<'a> CwCallService<'a> {
         msg: &CSMessage,
     ) -> Result<SubMsg, ContractError> {
         let msg = rlp::encode(msg).to_vec();
-        self.ensure_data_length(msg.len())?;
+        self.ensure_data_length(store, &to, msg.len())?;
         let message = xcall_connection_msg::ExecuteMsg::SendMessage { to, sn, msg };
 
         let cosm_msg = CosmosMsg::Wasm(WasmMsg::Execute {
@@ -30,11 +35,17 @@ impl<'a> CwCallService<'a> {
             msg: to_json_binary(&message).map_err(ContractError::Std)?,
             funds: fee,
         });
+        // Sends of new messages reply so the sequence number the connection assigned can be
+        // recorded for fee bumps. Results sent back (`sn` < 0) can not be bumped.
+        let reply_on = match sn >= 0 {
+            true => cosmwasm_std::ReplyOn::Success,
+            false => cosmwasm_std::ReplyOn::Never,
+        };
         let submessage = SubMsg {
             id: SEND_CALL_MESSAGE_REPLY_ID,
             msg: cosm_msg,
             gas_limit: None,
-            reply_on: cosmwasm_std::ReplyOn::Never,
+            reply_on,
         };
         println!("{LOG_PREFIX} sent message to connection :{address}");
         Ok(submessage)
@@ -63,6 +74,119 @@ impl<'a> CwCallService<'a> {
         Ok(fee)
     }
 
+    /// Handles the reply of a connection `SendMessage`, recording the sequence number the
+    /// connection assigned to the message in its `Message` event. Connections that do not report
+    /// one are not recorded, and messages sent through them can only be bumped while a response
+    /// is pending.
+    pub fn send_call_message_reply(
+        &self,
+        deps: DepsMut,
+        msg: Reply,
+    ) -> Result<Response, ContractError> {
+        let response = match msg.result {
+            SubMsgResult::Ok(response) => response,
+            SubMsgResult::Err(_) => return Ok(Response::new()),
+        };
+        let sn = self.get_sending_sn(deps.storage)?;
+
+        let recorded = response
+            .events
+            .iter()
+            .filter(|event| event.ty == "wasm-Message")
+            .filter_map(|event| {
+                let attribute = |key: &str| {
+                    event
+                        .attributes
+                        .iter()
+                        .find(|attr| attr.key == key)
+                        .map(|attr| attr.value.clone())
+                };
+                let connection = attribute("_contract_address")?;
+                let conn_sn = attribute("connSn")?.parse::<u128>().ok()?;
+                Some((connection, conn_sn))
+            })
+            .last();
+        if let Some((connection, conn_sn)) = recorded {
+            self.store_message_conn_sn(deps.storage, sn, &connection, conn_sn)?;
+        }
+
+        Ok(Response::new())
+    }
+
+    /// Forwards the attached funds to `connection` as extra fee for the message sent with `sn`.
+    ///
+    /// The sequence number `connection` assigned to the message is taken from the one recorded
+    /// when the message was sent, so messages that do not expect a response can be bumped too.
+    /// Messages sent before it was recorded are looked up with a `GetConnSn` query, which only
+    /// works while a response is pending and on connections implementing it.
+    ///
+    /// Arguments:
+    ///
+    /// * `sn`: The sequence number of the message on this chain.
+    /// * `connection`: The address of the connection the fee is added to.
+    pub fn bump_fee(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        sn: u128,
+        connection: String,
+    ) -> Result<Response, ContractError> {
+        ensure!(!info.funds.is_empty(), ContractError::InsufficientFunds);
+
+        let conn_sn = match self.get_message_conn_sn(deps.storage, sn, &connection) {
+            Some(conn_sn) => conn_sn,
+            None => self.query_pending_conn_sn(deps.as_ref(), sn, &connection)?,
+        };
+        let address = deps.api.addr_validate(&connection)?;
+
+        let message = xcall_connection_msg::ExecuteMsg::AddFee { conn_sn };
+        let cosm_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: address.to_string(),
+            msg: to_json_binary(&message)?,
+            funds: info.funds,
+        });
+
+        Ok(Response::new()
+            .add_attribute("action", "call_service")
+            .add_attribute("method", "bump_fee")
+            .add_message(cosm_msg)
+            .add_event(event_fee_bumped(sn, &connection, conn_sn)))
+    }
+
+    /// Asks `connection` for the sequence number it assigned to the message sent with `sn`, which
+    /// must still be waiting for a response and have been sent through `connection`.
+    fn query_pending_conn_sn(
+        &self,
+        deps: Deps,
+        sn: u128,
+        connection: &str,
+    ) -> Result<u128, ContractError> {
+        let rollback = self
+            .get_call_request(deps.storage, sn)
+            .map_err(|_| ContractError::CallRequestNotFound { sn })?;
+        ensure!(!rollback.enabled(), ContractError::RollbackNotPossible);
+
+        let mut protocols = rollback.protocols().clone();
+        if protocols.is_empty() {
+            let default = self.get_default_connection(deps.storage, rollback.to().nid())?;
+            protocols = vec![default.to_string()];
+        }
+        ensure!(
+            protocols.iter().any(|protocol| protocol == connection),
+            ContractError::ProtocolsMismatch
+        );
+
+        let query_request = QueryRequest::Wasm(cosmwasm_std::WasmQuery::Smart {
+            contract_addr: connection.to_string(),
+            msg: to_json_binary(&xcall_connection_msg::QueryMsg::GetConnSn { sn })?,
+        });
+        deps.querier
+            .query(&query_request)
+            .map_err(|_| ContractError::FeeBumpNotSupported {
+                connection: connection.to_string(),
+            })
+    }
+
     pub fn set_default_connection(
         &self,
         deps: DepsMut,
@@ -70,10 +194,70 @@ impl<'a> CwCallService<'a> {
         nid: NetId,
         address: Addr,
     ) -> Result<Response, ContractError> {
-        self.ensure_admin(deps.storage, info.sender)?;
+        self.ensure_role(deps.storage, Role::ConnectionManager, &info.sender)?;
         deps.api.addr_validate(address.as_str())?;
         self.store_default_connection(deps.storage, nid, address)?;
 
         Ok(Response::new().add_attribute("method", "set_default_connection"))
     }
+
+    /// Registers the connections the sender sends through to `nid` when its envelope names none,
+    /// replacing the previous ones. `sources` must not be empty and must be contract addresses
+    /// on this chain.
+    pub fn set_dapp_connections(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        nid: NetId,
+        connections: DappConnections,
+    ) -> Result<Response, ContractError> {
+        ensure!(
+            !connections.sources.is_empty(),
+            ContractError::InvalidDappConnections
+        );
+        for source in connections.sources.iter() {
+            deps.api.addr_validate(source)?;
+        }
+        self.store_dapp_connections(deps.storage, &info.sender, nid.clone(), &connections)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "set_dapp_connections")
+            .add_attribute("dapp", info.sender.to_string())
+            .add_attribute("nid", nid.to_string()))
+    }
+
+    /// Removes the connections the sender registered for `nid`, which then falls back to the
+    /// default connection of `nid`.
+    pub fn remove_dapp_connections_by_dapp(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        nid: NetId,
+    ) -> Result<Response, ContractError> {
+        self.remove_dapp_connections(deps.storage, &info.sender, nid.clone());
+
+        Ok(Response::new()
+            .add_attribute("method", "remove_dapp_connections")
+            .add_attribute("dapp", info.sender.to_string())
+            .add_attribute("nid", nid.to_string()))
+    }
+
+    /// Fills the sources and destinations of an envelope that names none with the connections
+    /// `dapp` registered for `nid`. Envelopes naming any connection are returned unchanged.
+    pub fn with_dapp_connections(
+        &self,
+        store: &dyn Storage,
+        dapp: &Addr,
+        nid: &NetId,
+        mut envelope: Envelope,
+    ) -> Envelope {
+        if !envelope.sources.is_empty() || !envelope.destinations.is_empty() {
+            return envelope;
+        }
+        if let Some(connections) = self.get_dapp_connections(store, dapp, nid) {
+            envelope.sources = connections.sources;
+            envelope.destinations = connections.destinations;
+        }
+        envelope
+    }
 }
@@ -1,6 +1,12 @@
-use cw_xcall_lib::network_address::NetworkAddress;
+use cw_xcall_lib::{
+    network_address::NetworkAddress,
+    xcall_query_msg::{DappConnections, PayloadLimits},
+};
 
-use crate::types::{config::Config, LOG_PREFIX};
+use crate::{
+    migrations::{parse_version, pending_migrations},
+    types::{config::Config, pause::NetworkPause, pending::DeliveryKind, LOG_PREFIX},
+};
 
 use super::*;
 // version info for migration info
@@ -72,12 +78,11 @@ impl<'a> CwCallService<'a> {
         msg: ExecuteMsg,
     ) -> Result<Response, ContractError> {
         match msg {
-            ExecuteMsg::SetAdmin { address } => {
-                let validated_address =
-                    CwCallService::validate_address(deps.api, address.as_str())?;
-                self.ensure_admin(deps.storage, info.sender)?;
-                self.set_admin(deps.storage, validated_address)
-            }
+            ExecuteMsg::SetAdmin { address } => self.propose_admin(deps, info, address),
+            ExecuteMsg::AcceptAdmin {} => self.accept_admin(deps, info),
+            ExecuteMsg::GrantRole { role, address } => self.grant_role(deps, info, role, address),
+            ExecuteMsg::RevokeRole { role, address } => self.revoke_role(deps, info, role, address),
+            ExecuteMsg::RenounceRole { role } => self.renounce_role(deps, info, role),
             ExecuteMsg::SetProtocolFee { value } => self.set_protocol_fee(deps, info, value),
             ExecuteMsg::SetProtocolFeeHandler { address } => {
                 self.set_protocol_feehandler(deps, &info, address)
@@ -88,26 +93,129 @@ impl<'a> CwCallService<'a> {
                 destinations,
                 data,
                 rollback,
+                max_fee,
             } => {
                 println!("{LOG_PREFIX} Received Send Call Message");
                 let sources = sources.unwrap_or(vec![]);
                 let dests = destinations.unwrap_or(vec![]);
-                self.send_call_message(deps, info, env, to, data, rollback, sources, dests)
+                self.send_call_message(deps, info, env, to, data, rollback, sources, dests, max_fee)
             }
-            ExecuteMsg::SendCall { envelope, to } => self.send_call(deps, info, to, envelope),
+            ExecuteMsg::SendCall {
+                envelope,
+                to,
+                max_fee,
+            } => self.send_call(deps, info, env, to, envelope, max_fee),
             ExecuteMsg::HandleMessage { msg, from_nid } => {
-                self.handle_message(deps, info, from_nid, msg)
+                self.handle_message(deps, env, info, from_nid, msg)
             }
-            ExecuteMsg::HandleError { sn } => self.handle_error(deps, info, sn),
+            ExecuteMsg::HandleError { sn } => self.handle_error(deps, env, info, sn),
             ExecuteMsg::ExecuteCall { request_id, data } => {
-                self.execute_call(deps, info, request_id, data)
+                self.execute_call(deps, env, info, request_id, data)
             }
+            ExecuteMsg::ExecuteCallWithGasLimit {
+                request_id,
+                data,
+                gas_limit,
+            } => self.execute_call_with_gas_limit(deps, env, info, request_id, data, gas_limit),
+            ExecuteMsg::ExecuteCalls { items } => self.execute_calls(deps, env, info, items),
             ExecuteMsg::ExecuteRollback { sequence_no } => {
                 self.execute_rollback(deps, env, info, sequence_no)
             }
             ExecuteMsg::SetDefaultConnection { nid, address } => {
                 self.set_default_connection(deps, info, nid, address)
             }
+            ExecuteMsg::HandleForcedRollback { req_id } => {
+                self.handle_forced_rollback(deps, info, req_id)
+            }
+            ExecuteMsg::SetPauser { address } => self.set_pauser(deps, info, address),
+            ExecuteMsg::SetNetworkPause {
+                nid,
+                outbound,
+                inbound,
+                execution,
+            } => self.set_network_pause(
+                deps,
+                info,
+                nid,
+                NetworkPause {
+                    outbound,
+                    inbound,
+                    execution,
+                },
+            ),
+            ExecuteMsg::SetGlobalPause { paused } => self.set_global_pause(deps, info, paused),
+            ExecuteMsg::ResumeInbound { nid, limit } => self.resume_inbound(deps, env, nid, limit),
+            ExecuteMsg::DropQueuedMessage { nid, id } => {
+                self.drop_queued_message(deps, info, nid, id)
+            }
+            ExecuteMsg::SetPayloadLimits {
+                nid,
+                max_data_size,
+                max_rollback_size,
+            } => self.set_payload_limits(
+                deps,
+                info,
+                nid,
+                PayloadLimits {
+                    max_data_size,
+                    max_rollback_size,
+                },
+            ),
+            ExecuteMsg::RemovePayloadLimits { nid } => self.remove_payload_limits(deps, info, nid),
+            ExecuteMsg::SetFeeDenom { denom, rate } => self.set_fee_denom(deps, info, denom, rate),
+            ExecuteMsg::RemoveFeeDenom { denom } => self.remove_fee_denom(deps, info, denom),
+            ExecuteMsg::DepositFees { dapp } => self.deposit_fees(deps, info, dapp),
+            ExecuteMsg::WithdrawFees { denom, amount, to } => {
+                self.withdraw_fees(deps, info, denom, amount, to)
+            }
+            ExecuteMsg::AddFeeSponsor { sponsor } => self.add_fee_sponsor(deps, info, sponsor),
+            ExecuteMsg::RemoveFeeSponsor { sponsor } => {
+                self.remove_fee_sponsor(deps, info, sponsor)
+            }
+            ExecuteMsg::BumpFee { sn, connection } => self.bump_fee(deps, info, sn, connection),
+            ExecuteMsg::SetPendingDeliveryTtl { ttl } => {
+                self.set_pending_delivery_ttl(deps, info, ttl)
+            }
+            ExecuteMsg::PurgeExpiredDeliveries { limit } => {
+                self.purge_expired_deliveries(deps, env, limit)
+            }
+            ExecuteMsg::PurgePendingDelivery { hash } => {
+                self.purge_pending_delivery_by_admin(deps, info, hash)
+            }
+            ExecuteMsg::SkipOrderedSequence {
+                from,
+                to,
+                ordered_sn,
+            } => self.skip_ordered_sequence(deps, env, info, from, to, ordered_sn),
+            ExecuteMsg::ContinueBackfill { limit } => self.continue_backfill(deps, env, limit),
+            ExecuteMsg::ReleaseBuffered { from, to, limit } => {
+                self.release_buffered(deps, env, from, to, limit)
+            }
+            ExecuteMsg::SetInboundPolicy { policy } => self.set_inbound_policy(deps, info, policy),
+            ExecuteMsg::RemoveInboundPolicy {} => self.remove_inbound_policy_by_dapp(deps, info),
+            ExecuteMsg::SetDappConnections {
+                nid,
+                sources,
+                destinations,
+            } => self.set_dapp_connections(
+                deps,
+                info,
+                nid,
+                DappConnections {
+                    sources,
+                    destinations,
+                },
+            ),
+            ExecuteMsg::RemoveDappConnections { nid } => {
+                self.remove_dapp_connections_by_dapp(deps, info, nid)
+            }
+            ExecuteMsg::SetRollbackGracePeriod { period } => {
+                self.set_rollback_grace_period(deps, info, period)
+            }
+            ExecuteMsg::CancelRollback { sn } => self.cancel_rollback(deps, env, info, sn),
+            ExecuteMsg::SetExtendedMessages { nid, enabled } => {
+                self.set_extended_messages(deps, info, nid, enabled)
+            }
         }
     }
 
@@ -143,26 +251,150 @@ impl<'a> CwCallService<'a> {
 
             QueryMsg::GetProtocolFee {} => to_json_binary(&self.get_protocol_fee(deps.storage)),
             QueryMsg::GetProtocolFeeHandler {} => {
-                to_json_binary(&self.get_protocol_feehandler(deps))
+                let fee_handler = self
+                    .get_protocol_feehandler(deps)
+                    .map_err(|e| StdError::generic_err(e.to_string()))?;
+                to_json_binary(&fee_handler)
             }
             QueryMsg::GetNetworkAddress {} => {
-                to_json_binary(&self.get_own_network_address(deps.storage, &env).unwrap())
+                let address = self
+                    .get_own_network_address(deps.storage, &env)
+                    .map_err(|e| StdError::generic_err(e.to_string()))?;
+                to_json_binary(&address)
             }
             QueryMsg::VerifySuccess { sn } => {
                 to_json_binary(&self.get_successful_response(deps.storage, sn))
             }
             QueryMsg::GetDefaultConnection { nid } => {
-                to_json_binary(&self.get_default_connection(deps.storage, nid).unwrap())
+                let connection = self
+                    .get_default_connection(deps.storage, nid)
+                    .map_err(|e| StdError::generic_err(e.to_string()))?;
+                to_json_binary(&connection)
             }
             QueryMsg::GetFee {
                 nid,
                 rollback,
                 sources,
-            } => to_json_binary(
+                denom,
+            } => {
+                let fee = self
+                    .get_fee(deps, nid, rollback, sources.unwrap_or(vec![]), denom)
+                    .map_err(|e| StdError::generic_err(e.to_string()))?;
+                to_json_binary(&fee)
+            }
+            QueryMsg::GetFeeDenoms {} => to_json_binary(&self.query_fee_denoms(deps.storage)?),
+            QueryMsg::GetPendingRollbacks {
+                start_after,
+                limit,
+                dapp,
+                nid,
+            } => to_json_binary(&self.query_pending_rollbacks(
+                deps.storage,
+                start_after,
+                limit,
+                dapp,
+                nid,
+            )?),
+            QueryMsg::GetRollback { sn } => to_json_binary(&self.query_rollback(deps.storage, sn)?),
+            QueryMsg::GetProxyRequests {
+                start_after,
+                limit,
+                dapp,
+                nid,
+            } => to_json_binary(&self.query_proxy_requests(
+                deps.storage,
+                start_after,
+                limit,
+                dapp,
+                nid,
+            )?),
+            QueryMsg::GetProxyRequest { req_id } => {
+                to_json_binary(&self.query_proxy_request(deps.storage, req_id)?)
+            }
+            QueryMsg::GetMessageStatus { sn } => {
+                to_json_binary(&self.query_message_status(deps.storage, sn)?)
+            }
+            QueryMsg::GetPauseState { nid } => {
+                to_json_binary(&self.query_pause_state(deps.storage, nid)?)
+            }
+            QueryMsg::GetPayloadLimits { nid } => match nid {
+                Some(nid) => to_json_binary(&self.get_payload_limits(deps.storage, &nid)),
+                None => to_json_binary(&self.get_default_payload_limits(deps.storage)),
+            },
+            QueryMsg::GetFeeBalance { dapp, denom } => {
+                let dapp = deps.api.addr_validate(&dapp)?;
+                let denom = match denom {
+                    Some(denom) => denom,
+                    None => {
+                        self.get_config(deps.storage)
+                            .map_err(|e| StdError::generic_err(e.to_string()))?
+                            .denom
+                    }
+                };
+                to_json_binary(&self.get_fee_balance(deps.storage, &dapp, &denom))
+            }
+            QueryMsg::GetFeeUsage {
+                dapp,
+                start_after,
+                limit,
+            } => to_json_binary(&self.query_fee_usage(deps.storage, dapp, start_after, limit)?),
+            QueryMsg::GetPendingRequests { start_after, limit } => {
+                to_json_binary(&self.query_pending_deliveries(
+                    deps.storage,
+                    DeliveryKind::Request,
+                    start_after,
+                    limit,
+                )?)
+            }
+            QueryMsg::GetPendingResponses { start_after, limit } => {
+                to_json_binary(&self.query_pending_deliveries(
+                    deps.storage,
+                    DeliveryKind::Response,
+                    start_after,
+                    limit,
+                )?)
+            }
+            QueryMsg::GetPendingDeliveryTtl {} => {
+                to_json_binary(&self.get_pending_delivery_ttl(deps.storage))
+            }
+            QueryMsg::GetOrderedChannel {
+                from,
+                to,
+                start_after,
+                limit,
+            } => to_json_binary(&self.query_ordered_channel(deps, from, to, start_after, limit)?),
+            QueryMsg::GetInboundPolicy { dapp } => {
+                let dapp = deps.api.addr_validate(&dapp)?;
+                to_json_binary(&self.get_inbound_policy(deps.storage, &dapp))
+            }
+            QueryMsg::GetPendingAdmin {} => to_json_binary(
                 &self
-                    .get_fee(deps, nid, rollback, sources.unwrap_or(vec![]))
-                    .unwrap(),
+                    .get_pending_admin(deps.storage)
+                    .map(|admin| admin.to_string()),
             ),
+            QueryMsg::GetRoleMembers { role } => {
+                to_json_binary(&self.get_role_members(deps.storage, &role)?)
+            }
+            QueryMsg::HasRole { role, address } => {
+                let address = deps.api.addr_validate(&address)?;
+                to_json_binary(&self.can_act_as(deps.storage, role, &address))
+            }
+            QueryMsg::GetDappConnections { dapp, nid } => {
+                let dapp = deps.api.addr_validate(&dapp)?;
+                to_json_binary(&self.get_dapp_connections(deps.storage, &dapp, &nid))
+            }
+            QueryMsg::GetRollbackGracePeriod {} => {
+                to_json_binary(&self.get_rollback_grace_period(deps.storage))
+            }
+            QueryMsg::GetExtendedMessages { nid } => {
+                to_json_binary(&self.supports_extended_messages(deps.storage, &nid))
+            }
+            QueryMsg::DecodeCsMessage { msg } => {
+                let decoded = self
+                    .decode_cs_message(&msg)
+                    .map_err(|e| StdError::generic_err(e.to_string()))?;
+                to_json_binary(&decoded)
+            }
         }
     }
     /// This function handles different types of reply messages and calls corresponding functions based on
@@ -188,7 +420,9 @@ impl<'a> CwCallService<'a> {
 
     pub fn reply(&self, deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
         match msg.id {
-            EXECUTE_CALL_ID => self.execute_call_reply(deps, env, msg),
+            id if is_execute_call_reply(id) => self.execute_call_reply(deps, env, msg),
+            CALL_RESULT_REPLY_ID => self.call_result_reply(msg),
+            SEND_CALL_MESSAGE_REPLY_ID => self.send_call_message_reply(deps, msg),
             _ => Err(ContractError::ReplyError {
                 code: msg.id,
                 msg: "Unknown".to_string(),
@@ -196,15 +430,51 @@ impl<'a> CwCallService<'a> {
         }
     }
 
+    /// Migrates the storage written by the version recorded with cw2 to the layout of this version,
+    /// running the steps of every version in between in order, then applies the parameters of
+    /// `msg`. Migrating from another contract or to an older version is refused.
     pub fn migrate(
         &self,
         deps: DepsMut,
-        _env: Env,
-        _msg: MigrateMsg,
+        env: Env,
+        msg: MigrateMsg,
     ) -> Result<Response, ContractError> {
+        let stored = get_contract_version(deps.storage)?;
+        ensure_eq!(
+            stored.contract,
+            CONTRACT_NAME,
+            ContractError::CannotMigrateFromContract {
+                contract: stored.contract
+            }
+        );
+        let from = parse_version(&stored.version)?;
+        let to = parse_version(CONTRACT_VERSION)?;
+        ensure!(
+            from <= to,
+            ContractError::CannotDowngrade {
+                stored: stored.version,
+                current: CONTRACT_VERSION.to_string(),
+            }
+        );
+
+        let mut res = Response::default()
+            .add_attribute("migrate", "successful")
+            .add_attribute("from_version", stored.version)
+            .add_attribute("to_version", CONTRACT_VERSION);
+        for step in pending_migrations(from, to)? {
+            (step.run)(self, deps.storage, &env)?;
+            res = res.add_attribute("migration", step.name);
+        }
+        if self.get_backfill_cursor(deps.storage).is_some() {
+            res = res.add_attribute("backfill", "pending");
+        }
+        if let Some(ttl) = msg.pending_delivery_ttl {
+            self.store_pending_delivery_ttl(deps.storage, Some(ttl))?;
+        }
+
         set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)
             .map_err(ContractError::Std)?;
-        Ok(Response::default().add_attribute("migrate", "successful"))
+        Ok(res)
     }
 }
 
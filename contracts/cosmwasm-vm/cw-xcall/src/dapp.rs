@@ -1,18 +1,20 @@
-use cosmwasm_std::{
-    to_json_binary, Addr, Binary, CosmosMsg, MessageInfo, StdError, SubMsg, WasmMsg,
-};
-use cw_xcall_lib::{dapp_msg, dapp_multi_msg, network_address::NetworkAddress};
+use cosmwasm_std::{to_json_binary, Addr, Binary, Coin, CosmosMsg, StdError, SubMsg, WasmMsg};
+use cw_xcall_lib::{dapp_callback_msg, dapp_msg, dapp_multi_msg, network_address::NetworkAddress};
 
-use crate::{error::ContractError, state::CwCallService};
+use crate::{
+    error::ContractError,
+    state::{CwCallService, CALL_RESULT_REPLY_ID},
+};
 
 impl<'a> CwCallService<'a> {
     pub fn call_dapp_handle_message(
         &self,
-        info: MessageInfo,
+        funds: Vec<Coin>,
         to: Addr,
         from: NetworkAddress,
         data: Vec<u8>,
         protocols: Vec<String>,
+        gas_limit: Option<u64>,
         reply_id: u64,
     ) -> Result<SubMsg, ContractError> {
         let cosm_msg = CosmosMsg::Wasm(WasmMsg::Execute {
@@ -20,18 +22,37 @@ impl<'a> CwCallService<'a> {
             msg: self
                 .get_handle_message(from, data, protocols)
                 .map_err(ContractError::Std)?,
-            funds: info.funds,
+            funds,
         });
         let submessage = SubMsg {
             id: reply_id,
             msg: cosm_msg,
-            gas_limit: None,
+            gas_limit,
             reply_on: cosmwasm_std::ReplyOn::Always,
         };
 
         Ok(submessage)
     }
 
+    /// Notifies the dapp that sent a two-way message with a result callback about the outcome of
+    /// that message. The callback only replies on error so a failing dapp does not revert the
+    /// result being handled.
+    pub fn call_dapp_handle_result(
+        &self,
+        to: &Addr,
+        sn: u128,
+        success: bool,
+    ) -> Result<SubMsg, ContractError> {
+        let message = dapp_callback_msg::ExecuteMsg::HandleCallResult { sn, success };
+        let cosm_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: to.to_string(),
+            msg: to_json_binary(&message).map_err(ContractError::Std)?,
+            funds: vec![],
+        });
+
+        Ok(SubMsg::reply_on_error(cosm_msg, CALL_RESULT_REPLY_ID))
+    }
+
     pub fn get_handle_message(
         &self,
         from: NetworkAddress,
@@ -18,6 +18,8 @@ pub enum ContractError {
     MaxDataSizeExceeded,
     #[error("MaxRollbackSizeExceeded")]
     MaxRollbackSizeExceeded,
+    #[error("InvalidPayloadLimits")]
+    InvalidPayloadLimits,
     #[error("NotExistRequestId {id}")]
     NotExistRequestId { id: u128 },
     #[error("InvalidRequestId {id}")]
@@ -48,4 +50,70 @@ pub enum ContractError {
     InvalidReplyReceived,
     #[error("CallRequest Not Found For {sn}")]
     CallRequestNotFound { sn: u128 },
+    #[error("InvalidGasLimit {limit}")]
+    InvalidGasLimit { limit: u64 },
+    #[error("FeeBumpNotSupported {connection}")]
+    FeeBumpNotSupported { connection: String },
+    #[error("OnlyDapp")]
+    OnlyDapp,
+    #[error("ContractPaused")]
+    ContractPaused,
+    #[error("NetworkPaused {nid}")]
+    NetworkPaused { nid: String },
+    #[error("UnsupportedFeeDenom {denom}")]
+    UnsupportedFeeDenom { denom: String },
+    #[error("MultipleFeeDenoms")]
+    MultipleFeeDenoms,
+    #[error("MaxFeeExceeded required {required} max {max_fee}")]
+    MaxFeeExceeded { required: u128, max_fee: u128 },
+    #[error("FundsNotAccepted")]
+    FundsNotAccepted,
+    #[error("TooManyExecuteCalls max {max}")]
+    TooManyExecuteCalls { max: u64 },
+    #[error("DuplicateRequestId {id}")]
+    DuplicateRequestId { id: u128 },
+    #[error("InvalidThreshold {threshold}")]
+    InvalidThreshold { threshold: u32 },
+    #[error("PendingDeliveryTtlNotSet")]
+    PendingDeliveryTtlNotSet,
+    #[error("PendingDeliveryAccepted")]
+    PendingDeliveryAccepted,
+    #[error("DefaultConnectionNotFound {nid}")]
+    DefaultConnectionNotFound { nid: String },
+    #[error("OrderedRequestAlreadyDelivered {ordered_sn}")]
+    OrderedRequestAlreadyDelivered { ordered_sn: u128 },
+    #[error("OrderedRequestAlreadyBuffered {ordered_sn}")]
+    OrderedRequestAlreadyBuffered { ordered_sn: u128 },
+    #[error("OrderedRequestTooFarAhead expected {expected} got {ordered_sn}")]
+    OrderedRequestTooFarAhead { expected: u128, ordered_sn: u128 },
+    #[error("InvalidOrderedSequence expected {expected} got {ordered_sn}")]
+    InvalidOrderedSequence { expected: u128, ordered_sn: u128 },
+    #[error("InvalidVersion {version}")]
+    InvalidVersion { version: String },
+    #[error("CannotMigrateFromContract {contract}")]
+    CannotMigrateFromContract { contract: String },
+    #[error("CannotDowngrade from {stored} to {current}")]
+    CannotDowngrade { stored: String, current: String },
+    #[error("NoBackfillInProgress")]
+    NoBackfillInProgress,
+    #[error("InvalidInboundPolicy")]
+    InvalidInboundPolicy,
+    #[error("InvalidDappConnections")]
+    InvalidDappConnections,
+    #[error("MissingRole {role}")]
+    MissingRole { role: String },
+    #[error("OnlyPendingAdmin")]
+    OnlyPendingAdmin,
+    #[error("RollbackGracePeriodNotSet")]
+    RollbackGracePeriodNotSet,
+    #[error("RollbackGracePeriodNotElapsed {sn}")]
+    RollbackGracePeriodNotElapsed { sn: u128 },
+    #[error("CallRequestCancelled {sn}")]
+    CallRequestCancelled { sn: u128 },
+    #[error("ExtendedMessagesNotSupported {nid}")]
+    ExtendedMessagesNotSupported { nid: String },
+    #[error("QueuedMessageFailed {id}: {error}")]
+    QueuedMessageFailed { id: u128, error: String },
+    #[error("QueuedMessageNotFound {id}")]
+    QueuedMessageNotFound { id: u128 },
 }
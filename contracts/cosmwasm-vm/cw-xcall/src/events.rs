@@ -1,3 +1,17 @@
+use cw_xcall_lib::{
+    network_address::NetId,
+    xcall_events::{
+        CallExecutedEvent, CallMessageBufferedEvent, CallMessageEvent, CallMessageRejectedEvent,
+        CallMessageSentEvent, CallResultFailedEvent, ExecuteCallSkippedEvent, FeeBumpedEvent,
+        ForcedRollbackEvent, GlobalPauseUpdatedEvent, InboundResumedEvent, MessageExpiredEvent,
+        MessageQueuedEvent, NetworkPauseUpdatedEvent, OrderedSequenceSkippedEvent,
+        PendingDeliveryPurgedEvent, QueuedMessageDroppedEvent, ResponseMessageEvent,
+        RollbackCancelledEvent, RollbackExecutedEvent, RollbackMessageEvent, XcallEvent,
+    },
+};
+
+use crate::types::{pause::NetworkPause, pending::DeliveryKind};
+
 use super::*;
 
 /// The function creates a new event with attributes for a call execution and returns it.
@@ -16,10 +30,30 @@ use super::*;
 /// This code returns an instance of the `Event` struct with attributes `request_id`, `code`, and `msg`
 /// added to it. The event represents the execution of a call.
 pub fn event_call_executed(request_id: u128, code: u8, msg: &str) -> Event {
-    Event::new("CallExecuted")
-        .add_attribute("reqId", request_id.to_string())
-        .add_attribute("code", code.to_string())
-        .add_attribute("msg", msg.to_string())
+    CallExecutedEvent {
+        req_id: request_id,
+        code,
+        msg: msg.to_string(),
+    }
+    .to_event()
+}
+
+/// The function creates an event for an item of an `ExecuteCalls` batch that was not executed.
+///
+/// Arguments:
+///
+/// * `request_id`: The request id of the skipped item.
+/// * `reason`: The error that kept the item from being executed.
+///
+/// Returns:
+///
+/// A new `Event` object with the attributes `reqId` and `reason`.
+pub fn event_execute_call_skipped(request_id: u128, reason: &str) -> Event {
+    ExecuteCallSkippedEvent {
+        req_id: request_id,
+        reason: reason.to_string(),
+    }
+    .to_event()
 }
 
 /// This Rust function creates an event for a message sent through a cross-chain communication protocol.
@@ -35,17 +69,24 @@ pub fn event_call_executed(request_id: u128, code: u8, msg: &str) -> Event {
 /// * `data`: The `data` parameter is of type `&CallServiceMessage`, which is a reference to a struct
 /// that contains information about a cross-chain message being sent. It likely includes details such as
 /// the recipient chain, the message payload, and any required signatures or authentication.
+/// * `protocols`: The connections the message was sent through.
 ///
 /// Returns:
 ///
 /// an instance of the `Event` struct.
-pub fn event_xcall_message_sent(from: String, destination: String, sn: u128) -> Event {
-    let event = Event::new("CallMessageSent");
-
-    event
-        .add_attribute("from", from)
-        .add_attribute("to", destination)
-        .add_attribute("sn", sn.to_string())
+pub fn event_xcall_message_sent(
+    from: String,
+    destination: String,
+    sn: u128,
+    protocols: &[String],
+) -> Event {
+    CallMessageSentEvent {
+        from,
+        to: destination,
+        sn,
+        protocols: protocols.to_vec(),
+    }
+    .to_event()
 }
 
 /// The function creates an event object for a rollback execution with sequence number
@@ -53,12 +94,35 @@ pub fn event_xcall_message_sent(from: String, destination: String, sn: u128) ->
 /// Arguments:
 ///
 /// * `sequence_no`: The sequence number of the rollback request that was executed.
+/// * `from`: The dapp that executed the rollback.
 ///
 /// Returns:
 ///
 /// A new `Event` object with attributes "sequence_no" added to it.
-pub fn event_rollback_executed(sequence_no: u128) -> Event {
-    Event::new("RollbackExecuted").add_attribute("sn", sequence_no.to_string())
+pub fn event_rollback_executed(sequence_no: u128, from: &Addr) -> Event {
+    RollbackExecutedEvent {
+        sn: sequence_no,
+        from: from.to_string(),
+    }
+    .to_event()
+}
+
+/// The function creates an event for a rollback cancelled by the dapp that sent the message.
+///
+/// Arguments:
+///
+/// * `sequence_no`: The sequence number of the message whose rollback was cancelled.
+/// * `from`: The dapp that cancelled the rollback.
+///
+/// Returns:
+///
+/// A new `Event` object with attributes "sn" and "from" added to it.
+pub fn event_rollback_cancelled(sequence_no: u128, from: &Addr) -> Event {
+    RollbackCancelledEvent {
+        sn: sequence_no,
+        from: from.to_string(),
+    }
+    .to_event()
 }
 
 /// The function creates a new event with attributes for a call message in Rust.
@@ -75,6 +139,9 @@ pub fn event_rollback_executed(sequence_no: u128) -> Event {
 /// * `request_id`: The `request_id` parameter is a unique identifier for a specific request being made
 /// in the `call_message` event. It is of type `u128`, which means it can hold a very large integer
 /// value. This identifier can be used to track the progress of the request and match it with the
+/// * `data`: The payload of the request, written base64 encoded.
+/// * `connection`: The connection whose delivery made the request accepted.
+/// * `protocols`: The protocols the request was sent over.
 ///
 /// Returns:
 ///
@@ -86,13 +153,19 @@ pub fn event_call_message(
     sequence_no: u128,
     request_id: u128,
     data: Vec<u8>,
+    connection: &str,
+    protocols: &[String],
 ) -> Event {
-    Event::new("CallMessage")
-        .add_attribute("from", from)
-        .add_attribute("to", to)
-        .add_attribute("sn", sequence_no.to_string())
-        .add_attribute("reqId", request_id.to_string())
-        .add_attribute("data", format!("{:?}", data))
+    CallMessageEvent {
+        from,
+        to,
+        sn: sequence_no,
+        req_id: request_id,
+        data,
+        connection: connection.to_string(),
+        protocols: protocols.to_vec(),
+    }
+    .to_event()
 }
 
 /// The function creates an event with a "rollback_message" type and a sequence number attribute.
@@ -102,13 +175,18 @@ pub fn event_call_message(
 /// * `sequence_no`: The `sequence_no` parameter is an unsigned 128-bit integer that represents the
 /// sequence number of a rollback message event. It is used to uniquely identify the event and keep
 /// track of the order in which events occur.
+/// * `from`: The dapp that sent the message and can now execute the rollback.
 ///
 /// Returns:
 ///
 /// A new `Event` object with the name "rollback_message" and an attribute "sequence_no" with the value
 /// of `sequence_no` converted to a string.
-pub fn event_rollback_message(sequence_no: u128) -> Event {
-    Event::new("RollbackMessage ").add_attribute("sn", sequence_no.to_string())
+pub fn event_rollback_message(sequence_no: u128, from: &Addr) -> Event {
+    RollbackMessageEvent {
+        sn: sequence_no,
+        from: from.to_string(),
+    }
+    .to_event()
 }
 
 /// This Rust function creates an event with attributes for a response message.
@@ -120,12 +198,270 @@ pub fn event_rollback_message(sequence_no: u128) -> Event {
 /// * `response_code`: The `response_code` parameter is an integer value representing the response code
 /// of an event. It is of type `i8`, which means it can hold values from -128 to 127. The response code
 /// is typically used to indicate the status or outcome of an operation or request.
+/// * `connection`: The connection that delivered the response.
 ///
 /// Returns:
 ///
 /// A new `Event` object with the attributes `sequence_no` and `response_code` added to it.
-pub fn event_response_message(sequence_no: u128, response_code: u8) -> Event {
-    Event::new("ResponseMessage")
-        .add_attribute("sn", sequence_no.to_string())
-        .add_attribute("code", response_code.to_string())
+pub fn event_response_message(sequence_no: u128, response_code: u8, connection: &str) -> Event {
+    ResponseMessageEvent {
+        sn: sequence_no,
+        code: response_code,
+        connection: connection.to_string(),
+    }
+    .to_event()
+}
+
+/// The function creates an event for a rollback forced by the destination dapp.
+///
+/// Arguments:
+///
+/// * `request_id`: The id of the proxy request that was dropped by the dapp.
+/// * `sequence_no`: The sequence number of the original message on the source chain.
+///
+/// Returns:
+///
+/// A new `Event` object with the attributes `reqId` and `sn`.
+pub fn event_forced_rollback(request_id: u128, sequence_no: u128) -> Event {
+    ForcedRollbackEvent {
+        req_id: request_id,
+        sn: sequence_no,
+    }
+    .to_event()
+}
+
+/// The function creates an event for a request that reached this chain after its expiry.
+///
+/// Arguments:
+///
+/// * `from`: The network address of the sender on the source chain.
+/// * `sequence_no`: The sequence number of the message on the source chain.
+///
+/// Returns:
+///
+/// A new `Event` object with the attributes `from` and `sn`.
+pub fn event_message_expired(from: String, sequence_no: u128) -> Event {
+    MessageExpiredEvent {
+        from,
+        sn: sequence_no,
+    }
+    .to_event()
+}
+
+/// The function creates an event for a change of the pause switches of a network.
+///
+/// Arguments:
+///
+/// * `nid`: The network whose switches were updated.
+/// * `pause`: The new switches.
+///
+/// Returns:
+///
+/// A new `Event` object with the attributes `nid`, `outbound`, `inbound` and `execution`.
+pub fn event_network_pause_updated(nid: &NetId, pause: &NetworkPause) -> Event {
+    NetworkPauseUpdatedEvent {
+        nid: nid.to_string(),
+        outbound: pause.outbound,
+        inbound: pause.inbound,
+        execution: pause.execution,
+    }
+    .to_event()
+}
+
+/// The function creates an event for a change of the global pause.
+///
+/// Arguments:
+///
+/// * `paused`: Whether the contract is now paused.
+///
+/// Returns:
+///
+/// A new `Event` object with the attribute `paused`.
+pub fn event_global_pause_updated(paused: bool) -> Event {
+    GlobalPauseUpdatedEvent { paused }.to_event()
+}
+
+/// The function creates an event for a message stored while inbound traffic was paused.
+///
+/// Arguments:
+///
+/// * `id`: The id under which the message was queued.
+/// * `from_nid`: The network the message was delivered from.
+///
+/// Returns:
+///
+/// A new `Event` object with the attributes `id` and `nid`.
+pub fn event_message_queued(id: u128, from_nid: &NetId) -> Event {
+    MessageQueuedEvent {
+        id,
+        nid: from_nid.to_string(),
+    }
+    .to_event()
+}
+
+/// The function creates an event for a queued message that was discarded without being replayed.
+///
+/// Arguments:
+///
+/// * `id`: The id under which the message was queued.
+/// * `nid`: The network the message was delivered from.
+/// * `dropped_by`: The pauser who discarded the message.
+///
+/// Returns:
+///
+/// A new `Event` object with the attributes `id`, `nid` and `dropped_by`.
+pub fn event_queued_message_dropped(id: u128, nid: &NetId, dropped_by: &Addr) -> Event {
+    QueuedMessageDroppedEvent {
+        id,
+        nid: nid.to_string(),
+        dropped_by: dropped_by.to_string(),
+    }
+    .to_event()
+}
+
+/// The function creates an event for the replay of queued inbound messages.
+///
+/// Arguments:
+///
+/// * `nid`: The network whose messages were replayed.
+/// * `count`: The number of messages taken from the queue.
+///
+/// Returns:
+///
+/// A new `Event` object with the attributes `nid` and `count`.
+pub fn event_inbound_resumed(nid: &NetId, count: usize) -> Event {
+    InboundResumedEvent {
+        nid: nid.to_string(),
+        count: count as u64,
+    }
+    .to_event()
+}
+
+/// The function creates an event for extra fee added to a message that is already in flight.
+///
+/// Arguments:
+///
+/// * `sequence_no`: The sequence number of the message on this chain.
+/// * `connection`: The connection the fee was forwarded to.
+/// * `conn_sn`: The sequence number of the message in the connection.
+///
+/// Returns:
+///
+/// A new `Event` object with the attributes `sn`, `connection` and `connSn`.
+pub fn event_fee_bumped(sequence_no: u128, connection: &str, conn_sn: u128) -> Event {
+    FeeBumpedEvent {
+        sn: sequence_no,
+        connection: connection.to_string(),
+        conn_sn,
+    }
+    .to_event()
+}
+
+/// The function creates an event for a result callback the originating dapp failed to handle.
+///
+/// Arguments:
+///
+/// * `error`: The error returned by the dapp.
+///
+/// Returns:
+///
+/// A new `Event` object with the attribute `reason`.
+pub fn event_call_result_failed(error: &str) -> Event {
+    CallResultFailedEvent {
+        reason: error.to_string(),
+    }
+    .to_event()
+}
+
+/// The function creates an event for a partially delivered message removed from storage.
+///
+/// Arguments:
+///
+/// * `kind`: Whether the message is a request or a response.
+/// * `hash`: The hash of the message payload.
+/// * `delivered`: The protocols that had delivered the message.
+///
+/// Returns:
+///
+/// A new `Event` object with the attributes `kind`, `hash` and `delivered`.
+pub fn event_pending_delivery_purged(
+    kind: DeliveryKind,
+    hash: &[u8],
+    delivered: &[String],
+) -> Event {
+    PendingDeliveryPurgedEvent {
+        kind: kind.as_str().to_string(),
+        hash: hash.to_vec(),
+        delivered: delivered.to_vec(),
+    }
+    .to_event()
+}
+
+/// The function creates an event for an ordered request held until the requests before it in its
+/// channel have been delivered.
+///
+/// Arguments:
+///
+/// * `from`: The network address of the sender.
+/// * `to`: The destination dapp.
+/// * `sequence_no`: The sequence number of the request on the source chain.
+/// * `ordered_sn`: The position of the request in the channel.
+///
+/// Returns:
+///
+/// A new `Event` object with the attributes `from`, `to`, `sn` and `ordered_sn`.
+pub fn event_call_message_buffered(
+    from: String,
+    to: String,
+    sequence_no: u128,
+    ordered_sn: u128,
+) -> Event {
+    CallMessageBufferedEvent {
+        from,
+        to,
+        sn: sequence_no,
+        ordered_sn,
+    }
+    .to_event()
+}
+
+/// The function creates an event for a position of an ordered channel skipped by the admin.
+///
+/// Arguments:
+///
+/// * `from`: The network address of the sender.
+/// * `to`: The destination dapp.
+/// * `ordered_sn`: The skipped position.
+///
+/// Returns:
+///
+/// A new `Event` object with the attributes `from`, `to` and `ordered_sn`.
+pub fn event_ordered_sequence_skipped(from: String, to: String, ordered_sn: u128) -> Event {
+    OrderedSequenceSkippedEvent {
+        from,
+        to,
+        ordered_sn,
+    }
+    .to_event()
+}
+
+/// The function creates an event for a request rejected by the inbound policy of its destination
+/// dapp.
+///
+/// Arguments:
+///
+/// * `from`: The network address of the sender.
+/// * `to`: The destination dapp.
+/// * `sequence_no`: The sequence number of the request on the source chain.
+///
+/// Returns:
+///
+/// A new `Event` object with the attributes `from`, `to` and `sn`.
+pub fn event_call_message_rejected(from: String, to: String, sequence_no: u128) -> Event {
+    CallMessageRejectedEvent {
+        from,
+        to,
+        sn: sequence_no,
+    }
+    .to_event()
 }
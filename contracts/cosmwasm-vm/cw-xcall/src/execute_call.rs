@@ -1,12 +1,16 @@
 use common::{rlp, utils::keccak256};
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Reply, Response, SubMsg};
+use cosmwasm_std::{
+    ensure, Coin, Deps, DepsMut, Env, MessageInfo, Reply, Response, Storage, SubMsg,
+};
 
 use crate::{
     error::ContractError,
-    events::event_call_executed,
-    state::{CwCallService, EXECUTE_CALL_ID},
+    events::{event_call_executed, event_execute_call_skipped},
+    state::{CwCallService, EXECUTE_CALLS_REPLY_ID, EXECUTE_CALL_ID, MAX_EXECUTE_CALLS},
     types::{
         message::CSMessage,
+        pause::PauseDirection,
+        request::CSMessageRequest,
         result::{CSMessageResult, CallServiceResponseType},
     },
 };
@@ -35,35 +39,190 @@ impl<'a> CwCallService<'a> {
     pub fn execute_call(
         &self,
         deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        request_id: u128,
+        data: Vec<u8>,
+    ) -> Result<Response, ContractError> {
+        self.ensure_no_call_in_progress(deps.storage)?;
+        let res = self.prepare_call(
+            deps,
+            &env,
+            info.funds,
+            request_id,
+            data,
+            None,
+            EXECUTE_CALL_ID,
+        )?;
+
+        Ok(res
+            .add_attribute("action", "call_message")
+            .add_attribute("method", "execute_call"))
+    }
+
+    /// Executes a pending request whose dapp ran out of the gas limit it was sent with. A request
+    /// kept for a retry would otherwise fail the same way on every attempt, so the caller can
+    /// execute it with a higher limit instead. The limit of other requests is left as the sender
+    /// set it, since they are resolved by the failure result sent for their first attempt.
+    ///
+    /// Arguments:
+    ///
+    /// * `request_id`: The id of the request to execute.
+    /// * `data`: The data the request was sent with.
+    /// * `gas_limit`: The gas limit for the dapp execution, above the one of the request.
+    pub fn execute_call_with_gas_limit(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        request_id: u128,
+        data: Vec<u8>,
+        gas_limit: u64,
+    ) -> Result<Response, ContractError> {
+        self.ensure_no_call_in_progress(deps.storage)?;
+        let res = self.prepare_call(
+            deps,
+            &env,
+            info.funds,
+            request_id,
+            data,
+            Some(gas_limit),
+            EXECUTE_CALL_ID,
+        )?;
+
+        Ok(res
+            .add_attribute("action", "call_message")
+            .add_attribute("method", "execute_call_with_gas_limit"))
+    }
+
+    /// Executes several pending requests in one transaction. Every item runs as its own
+    /// submessage with its own reply id, so an item whose dapp fails only resolves that item and
+    /// leaves the others executed. Items that can not be executed, such as already executed or
+    /// unknown request ids, are skipped and reported in an `ExecuteCallSkipped` event instead of
+    /// reverting the batch. Items are called without funds.
+    ///
+    /// Arguments:
+    ///
+    /// * `items`: The request ids to execute with the data each of them was sent with, executed in
+    /// the given order.
+    pub fn execute_calls(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
         info: MessageInfo,
+        items: Vec<(u128, Vec<u8>)>,
+    ) -> Result<Response, ContractError> {
+        ensure!(info.funds.is_empty(), ContractError::FundsNotAccepted);
+        ensure!(
+            items.len() as u64 <= MAX_EXECUTE_CALLS,
+            ContractError::TooManyExecuteCalls {
+                max: MAX_EXECUTE_CALLS
+            }
+        );
+        self.ensure_no_call_in_progress(deps.storage)?;
+
+        let mut res = Response::new()
+            .add_attribute("action", "call_message")
+            .add_attribute("method", "execute_calls");
+        let mut request_ids: Vec<u128> = vec![];
+        for (index, (request_id, data)) in items.into_iter().enumerate() {
+            ensure!(
+                !request_ids.contains(&request_id),
+                ContractError::DuplicateRequestId { id: request_id }
+            );
+            request_ids.push(request_id);
+
+            let reply_id = EXECUTE_CALLS_REPLY_ID + index as u64;
+            match self.prepare_call(
+                deps.branch(),
+                &env,
+                vec![],
+                request_id,
+                data,
+                None,
+                reply_id,
+            ) {
+                Ok(item) => res = res.add_submessages(item.messages).add_events(item.events),
+                Err(error) => {
+                    res = res.add_event(event_execute_call_skipped(request_id, &error.to_string()))
+                }
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Validates a pending request and builds the submessage calling its dapp, recording
+    /// `request_id` under `reply_id` for the reply. Expired requests are rejected instead.
+    /// `gas_limit` replaces the gas limit of the request and must be above it.
+    ///
+    /// Nothing is written before the last fallible step, so a batch can skip an item that fails
+    /// without leaving part of it applied.
+    fn prepare_call(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        funds: Vec<Coin>,
         request_id: u128,
         data: Vec<u8>,
+        gas_limit: Option<u64>,
+        reply_id: u64,
     ) -> Result<Response, ContractError> {
-        let proxy_requests = self.get_proxy_request(deps.storage, request_id).unwrap();
+        let proxy_requests = self
+            .get_proxy_request(deps.storage, request_id)
+            .map_err(|_| ContractError::InvalidRequestId { id: request_id })?;
 
-        self.ensure_request_not_null(request_id, &proxy_requests)
-            .unwrap();
+        self.ensure_request_not_null(request_id, &proxy_requests)?;
 
         let data_hash = keccak256(&data).to_vec();
-        if data_hash != proxy_requests.data().unwrap().to_vec() {
+        if data_hash != proxy_requests.data()?.to_vec() {
             return Err(ContractError::DataMismatch);
         }
+        self.ensure_not_paused(
+            deps.storage,
+            &proxy_requests.from().nid(),
+            PauseDirection::Execution,
+        )?;
+        if let Some(limit) = gas_limit {
+            ensure!(
+                proxy_requests.allow_retry()
+                    && matches!(proxy_requests.gas_limit(), Some(current) if limit > current),
+                ContractError::InvalidGasLimit { limit }
+            );
+        }
+
+        let source_height = self.get_source_height(deps.storage, &proxy_requests.from().nid());
+        if proxy_requests.is_expired(&env.block, source_height) {
+            let code = CallServiceResponseType::CallServiceResponseFailure.into();
+            let event = event_call_executed(request_id, code, "MessageExpired");
+            let res = self
+                .reject_expired_request(deps.as_ref(), &proxy_requests)?
+                .add_event(event);
+            self.remove_proxy_request(deps.storage, request_id);
+            return Ok(res);
+        }
 
         let sub_msg = self.call_dapp_handle_message(
-            info,
+            funds,
             proxy_requests.to().clone(),
             proxy_requests.from().clone(),
             data,
             proxy_requests.protocols().clone(),
-            EXECUTE_CALL_ID,
+            gas_limit.or(proxy_requests.gas_limit()),
+            reply_id,
         )?;
 
-        self.store_execute_request_id(deps.storage, request_id)?;
+        self.store_execute_item_request_id(deps.storage, reply_id, request_id)?;
 
-        Ok(Response::new()
-            .add_attribute("action", "call_message")
-            .add_attribute("method", "execute_call")
-            .add_submessage(sub_msg))
+        Ok(Response::new().add_submessage(sub_msg))
+    }
+
+    fn ensure_no_call_in_progress(&self, store: &dyn Storage) -> Result<(), ContractError> {
+        ensure!(
+            self.get_execute_request_id(store).is_err(),
+            ContractError::CallAlreadyInProgress
+        );
+        Ok(())
     }
 
     pub fn execute_call_reply(
@@ -72,14 +231,16 @@ impl<'a> CwCallService<'a> {
         _env: Env,
         msg: Reply,
     ) -> Result<Response, ContractError> {
-        let req_id = self.get_execute_request_id(deps.storage)?;
-        self.remove_execute_request_id(deps.storage);
+        let req_id = self.get_execute_item_request_id(deps.storage, msg.id)?;
+        self.remove_execute_request_id(deps.storage, msg.id);
 
         let request = self.get_proxy_request(deps.storage, req_id)?;
         self.remove_proxy_request(deps.storage, req_id);
-        let reply = self
-            .pop_call_reply(deps.storage)
-            .map(|msg| rlp::encode(&msg).to_vec());
+        let replies = self
+            .pop_call_replies(deps.storage)
+            .iter()
+            .map(|msg| rlp::encode(msg).to_vec())
+            .collect();
 
         let (response, event) = match msg.result {
             cosmwasm_std::SubMsgResult::Ok(_res) => {
@@ -87,12 +248,14 @@ impl<'a> CwCallService<'a> {
                 let message_response = CSMessageResult::new(
                     request.sequence_no(),
                     CallServiceResponseType::CallServiceResponseSuccess,
-                    reply,
-                );
+                    None,
+                )
+                .with_replies(replies);
 
                 let event = event_call_executed(req_id, code, "success");
                 (message_response, event)
             }
+            // A dapp exceeding the gas limit of the request also ends up here.
             cosmwasm_std::SubMsgResult::Err(err) => {
                 let code = CallServiceResponseType::CallServiceResponseFailure;
                 let error_message = format!("CallService Reverted : {err}");
@@ -100,36 +263,26 @@ impl<'a> CwCallService<'a> {
                     CSMessageResult::new(request.sequence_no(), code.clone(), None);
                 let event = event_call_executed(req_id, code.into(), &error_message);
                 if request.allow_retry() {
-                    return Err(ContractError::ReplyError {
-                        code: msg.id,
-                        msg: err,
-                    });
+                    if msg.id == EXECUTE_CALL_ID {
+                        return Err(ContractError::ReplyError {
+                            code: msg.id,
+                            msg: err,
+                        });
+                    }
+                    // Reverting would undo the other items of the batch, so the request is
+                    // kept for a retry instead.
+                    self.store_proxy_request(deps.storage, req_id, &request)?;
+                    return Ok(Response::new()
+                        .add_attribute("action", "call_message")
+                        .add_attribute("method", "execute_callback")
+                        .add_event(event));
                 }
                 (message_response, event)
             }
         };
         let mut submsgs: Vec<SubMsg> = vec![];
-        let sn: i64 = -(request.sequence_no() as i64);
         if request.need_response() {
-            let message: CSMessage = response.into();
-            let mut reply_address = request.protocols().clone();
-            let from = request.from().clone();
-            if request.protocols().is_empty() {
-                let default_connection = self.get_default_connection(deps.storage, from.nid())?;
-                reply_address = vec![default_connection.to_string()];
-            }
-            submsgs = reply_address
-                .iter()
-                .map(|to| {
-                    self.call_connection_send_message(
-                        &deps.api.addr_validate(to)?,
-                        vec![],
-                        from.nid(),
-                        sn,
-                        &message,
-                    )
-                })
-                .collect::<Result<Vec<SubMsg>, ContractError>>()?;
+            submsgs = self.send_call_result(deps.as_ref(), &request, response)?;
         }
 
         Ok(Response::new()
@@ -138,4 +291,35 @@ impl<'a> CwCallService<'a> {
             .add_attribute("method", "execute_callback")
             .add_event(event))
     }
+
+    /// Builds the submessages that deliver a `CSMessageResult` for `request` back to the source
+    /// chain, over the protocols the request arrived on or the default connection when it has none.
+    pub fn send_call_result(
+        &self,
+        deps: Deps,
+        request: &CSMessageRequest,
+        result: CSMessageResult,
+    ) -> Result<Vec<SubMsg>, ContractError> {
+        let message: CSMessage = result.into();
+        let sn: i64 = -(request.sequence_no() as i64);
+        let from = request.from().clone();
+        let mut reply_address = request.protocols().clone();
+        if reply_address.is_empty() {
+            let default_connection = self.get_default_connection(deps.storage, from.nid())?;
+            reply_address = vec![default_connection.to_string()];
+        }
+        reply_address
+            .iter()
+            .map(|to| {
+                self.call_connection_send_message(
+                    deps.storage,
+                    &deps.api.addr_validate(to)?,
+                    vec![],
+                    from.nid(),
+                    sn,
+                    &message,
+                )
+            })
+            .collect::<Result<Vec<SubMsg>, ContractError>>()
+    }
 }
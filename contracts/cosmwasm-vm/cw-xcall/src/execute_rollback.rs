@@ -2,11 +2,13 @@ use cosmwasm_std::DepsMut;
 use cosmwasm_std::MessageInfo;
 use cosmwasm_std::ReplyOn;
 
-use cosmwasm_std::{Env, Response};
+use cosmwasm_std::{ensure, ensure_eq, Coin, Deps, Env, Response, SubMsg};
+use cw_xcall_lib::xcall_query_msg::MessageStatus;
 
 use crate::error::ContractError;
-use crate::events::event_rollback_executed;
+use crate::events::{event_rollback_cancelled, event_rollback_executed};
 use crate::state::{CwCallService, EXECUTE_ROLLBACK_ID};
+use crate::types::rollback::Rollback;
 
 impl<'a> CwCallService<'a> {
     /// This function executes a rollback operation for a previously made call request.
@@ -36,29 +38,102 @@ impl<'a> CwCallService<'a> {
         let call_request = self.get_call_request(deps.storage, sequence_no)?;
         self.cleanup_request(deps.storage, sequence_no);
 
-        self.ensure_call_request_not_null(sequence_no, &call_request)
-            .unwrap();
-        self.ensure_rollback_enabled(call_request.enabled())
-            .unwrap();
-        let from = self.get_own_network_address(deps.as_ref().storage, &env)?;
+        self.ensure_call_request_not_null(sequence_no, &call_request)?;
+        self.ensure_rollback_enabled(call_request.enabled())?;
+        self.store_message_status(deps.storage, sequence_no, MessageStatus::RolledBack)?;
+        let sub_msg = self.rollback_sub_msg(deps.as_ref(), &env, info.funds, &call_request)?;
+
+        let event = event_rollback_executed(sequence_no, call_request.from());
+
+        Ok(Response::new()
+            .add_attribute("action", "call_message")
+            .add_attribute("method", "execute_rollback")
+            .add_event(event)
+            .add_submessage(sub_msg))
+    }
+
+    /// Sets how long, in seconds, the sender of a two-way message waits for its response before
+    /// it can cancel the message with `CancelRollback`. `None` disables cancellation.
+    pub fn set_rollback_grace_period(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        period: Option<u64>,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender)?;
+        self.store_rollback_grace_period(deps.storage, period)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "set_rollback_grace_period")
+            .add_attribute(
+                "period",
+                period.map_or("none".to_string(), |period| period.to_string()),
+            ))
+    }
+
+    /// Gives up on the response to a two-way message and runs its rollback right away. Only the
+    /// dapp that sent the message can cancel it, once the grace period has elapsed since it was
+    /// sent. The sequence number is marked as cancelled, so any response delivered later is
+    /// rejected.
+    ///
+    /// Arguments:
+    ///
+    /// * `sequence_no`: The sequence number of the message whose rollback is run.
+    pub fn cancel_rollback(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        sequence_no: u128,
+    ) -> Result<Response, ContractError> {
+        let call_request = self.get_call_request(deps.storage, sequence_no)?;
+        ensure_eq!(&info.sender, call_request.from(), ContractError::OnlyDapp);
+
+        let period = self
+            .get_rollback_grace_period(deps.storage)
+            .ok_or(ContractError::RollbackGracePeriodNotSet)?;
+        let elapsed = call_request.sent_at().map_or(false, |sent_at| {
+            sent_at.plus_seconds(period) <= env.block.time
+        });
+        ensure!(
+            elapsed,
+            ContractError::RollbackGracePeriodNotElapsed { sn: sequence_no }
+        );
+
+        self.cleanup_request(deps.storage, sequence_no);
+        self.store_message_status(deps.storage, sequence_no, MessageStatus::Cancelled)?;
+        let sub_msg = self.rollback_sub_msg(deps.as_ref(), &env, info.funds, &call_request)?;
+
+        let event = event_rollback_cancelled(sequence_no, call_request.from());
+
+        Ok(Response::new()
+            .add_attribute("action", "call_message")
+            .add_attribute("method", "cancel_rollback")
+            .add_event(event)
+            .add_submessage(sub_msg))
+    }
+
+    /// Builds the call delivering the rollback data of `call_request` to the dapp that sent it.
+    fn rollback_sub_msg(
+        &self,
+        deps: Deps,
+        env: &Env,
+        funds: Vec<Coin>,
+        call_request: &Rollback,
+    ) -> Result<SubMsg, ContractError> {
+        let from = self.get_own_network_address(deps.storage, env)?;
 
         let mut sub_msg = self.call_dapp_handle_message(
-            info,
+            funds,
             // the original caller is stored as from in call request
             call_request.from().clone(),
             from,
             call_request.rollback().to_vec(),
             call_request.protocols().clone(),
+            None,
             EXECUTE_ROLLBACK_ID,
         )?;
         sub_msg.reply_on = ReplyOn::Never;
-
-        let event = event_rollback_executed(sequence_no);
-
-        Ok(Response::new()
-            .add_attribute("action", "call_message")
-            .add_attribute("method", "execute_rollback")
-            .add_event(event)
-            .add_submessage(sub_msg))
+        Ok(sub_msg)
     }
 }
@@ -0,0 +1,46 @@
+use cw_xcall_lib::{message::envelope::Envelope, network_address::NetId};
+
+use super::*;
+
+impl<'a> CwCallService<'a> {
+    /// Records whether xcall on `nid` decodes the extended message encodings: requests sent as
+    /// `CSMessageRequestV2`, which carry an expiry, a gas limit, a threshold or an ordered sequence,
+    /// and results carrying more than one reply. Networks are assumed not to until enabled, since
+    /// their decoders reject or silently drop the extra fields.
+    pub fn set_extended_messages(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        nid: NetId,
+        enabled: bool,
+    ) -> Result<Response, ContractError> {
+        self.ensure_role(deps.storage, Role::ConnectionManager, &info.sender)?;
+        self.store_extended_messages(deps.storage, nid.clone(), enabled)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "set_extended_messages")
+            .add_attribute("nid", nid.to_string())
+            .add_attribute("enabled", enabled.to_string()))
+    }
+
+    /// Ensures a message whose request uses the extended encoding is only sent to a network that
+    /// decodes it. Checked before anything is stored for the message.
+    pub fn ensure_extended_messages_supported(
+        &self,
+        store: &dyn Storage,
+        nid: &NetId,
+        envelope: &Envelope,
+    ) -> Result<(), ContractError> {
+        let extended = envelope.expiry.is_some()
+            || envelope.gas_limit.is_some()
+            || envelope.threshold.is_some()
+            || envelope.ordered;
+        ensure!(
+            !extended || self.supports_extended_messages(store, nid),
+            ContractError::ExtendedMessagesNotSupported {
+                nid: nid.to_string()
+            }
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,213 @@
+use cosmwasm_std::{coins, BankMsg, Decimal};
+use cw_storage_plus::Bound;
+use cw_xcall_lib::{network_address::NetId, xcall_query_msg::FeeUsage};
+
+use crate::state::{DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT};
+
+use super::*;
+
+impl<'a> CwCallService<'a> {
+    /// Credits the attached funds to the prepaid fee balance of `dapp`. `send_call` draws from this
+    /// balance whenever the funds attached by the dapp do not cover the fee.
+    ///
+    /// Arguments:
+    ///
+    /// * `info`: The sender must be the dapp itself or one of its sponsors. Every attached coin must
+    /// be in a denom accepted for fees.
+    /// * `dapp`: The address of the dapp whose balance is topped up.
+    pub fn deposit_fees(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        dapp: String,
+    ) -> Result<Response, ContractError> {
+        let dapp = CwCallService::validate_address(deps.api, &dapp)?;
+        ensure!(
+            info.sender == dapp || self.is_fee_sponsor(deps.storage, &dapp, &info.sender),
+            ContractError::Unauthorized {}
+        );
+        ensure!(!info.funds.is_empty(), ContractError::InsufficientFunds);
+
+        for coin in info.funds.iter() {
+            self.get_fee_denom_rate(deps.storage, &coin.denom)?;
+            let balance = self.get_fee_balance(deps.storage, &dapp, &coin.denom);
+            let balance = balance
+                .checked_add(coin.amount.u128())
+                .ok_or(ContractError::InsufficientFunds)?;
+            self.store_fee_balance(deps.storage, &dapp, &coin.denom, balance)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "call_service")
+            .add_attribute("method", "deposit_fees")
+            .add_attribute("dapp", dapp.to_string())
+            .add_attribute("sender", info.sender.to_string()))
+    }
+
+    /// Withdraws from the prepaid fee balance of the sender.
+    ///
+    /// Arguments:
+    ///
+    /// * `denom`: The denom to withdraw.
+    /// * `amount`: The amount to withdraw, which must not exceed the balance.
+    /// * `to`: The receiver of the funds, defaults to the sender.
+    pub fn withdraw_fees(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        denom: String,
+        amount: u128,
+        to: Option<String>,
+    ) -> Result<Response, ContractError> {
+        let receiver = match to {
+            Some(to) => CwCallService::validate_address(deps.api, &to)?,
+            None => info.sender.clone(),
+        };
+        let balance = self.get_fee_balance(deps.storage, &info.sender, &denom);
+        ensure!(
+            amount > 0 && amount <= balance,
+            ContractError::InsufficientFunds
+        );
+        self.store_fee_balance(deps.storage, &info.sender, &denom, balance - amount)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "call_service")
+            .add_attribute("method", "withdraw_fees")
+            .add_attribute("dapp", info.sender.to_string())
+            .add_message(BankMsg::Send {
+                to_address: receiver.to_string(),
+                amount: coins(amount, denom),
+            }))
+    }
+
+    /// Allows `sponsor` to top up the prepaid fee balance of the sender.
+    pub fn add_fee_sponsor(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        sponsor: String,
+    ) -> Result<Response, ContractError> {
+        let sponsor = CwCallService::validate_address(deps.api, &sponsor)?;
+        self.store_fee_sponsor(deps.storage, &info.sender, &sponsor)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "add_fee_sponsor")
+            .add_attribute("dapp", info.sender.to_string())
+            .add_attribute("sponsor", sponsor.to_string()))
+    }
+
+    /// Revokes the permission of `sponsor` to top up the prepaid fee balance of the sender.
+    pub fn remove_fee_sponsor(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        sponsor: String,
+    ) -> Result<Response, ContractError> {
+        let sponsor = CwCallService::validate_address(deps.api, &sponsor)?;
+        self.delete_fee_sponsor(deps.storage, &info.sender, &sponsor);
+
+        Ok(Response::new()
+            .add_attribute("method", "remove_fee_sponsor")
+            .add_attribute("dapp", info.sender.to_string())
+            .add_attribute("sponsor", sponsor.to_string()))
+    }
+
+    /// Draws `amount` from the prepaid fee balance of `dapp` and records it in the usage history.
+    ///
+    /// Arguments:
+    ///
+    /// * `dapp`: The dapp that sent the message.
+    /// * `denom`: The denom the fee is charged in.
+    /// * `amount`: The part of the fee that was not covered by the attached funds.
+    /// * `sn`: The sequence number of the message the fee was paid for.
+    /// * `to`: The destination network of the message.
+    pub fn charge_fee_balance(
+        &self,
+        store: &mut dyn Storage,
+        dapp: &Addr,
+        denom: &str,
+        amount: u128,
+        sn: u128,
+        to: NetId,
+    ) -> Result<(), ContractError> {
+        let balance = self.get_fee_balance(store, dapp, denom);
+        ensure!(amount <= balance, ContractError::InsufficientFunds);
+        self.store_fee_balance(store, dapp, denom, balance - amount)?;
+        self.save_fee_usage(
+            store,
+            dapp,
+            &FeeUsage {
+                sn,
+                to,
+                denom: denom.to_string(),
+                amount,
+            },
+        )
+    }
+
+    /// Picks the denom the fee of a message sent by `dapp` is paid in, and the amount drawn from
+    /// the prepaid balance of `dapp` in that denom.
+    ///
+    /// The attached funds are used first, topped up from the balance in their denom. When that
+    /// balance falls short, the whole fee is drawn from the first balance of `dapp` that covers it,
+    /// converted at the rate of its denom, so that attached funds are only required when no
+    /// balance is enough.
+    ///
+    /// Arguments:
+    ///
+    /// * `paid_denom`: The denom of the attached funds, the base denom when none were attached.
+    /// * `paid`: The amount of the attached funds.
+    /// * `total_fee`: Returns the fee of the message converted at the given rate.
+    pub fn select_fee_denom(
+        &self,
+        store: &dyn Storage,
+        dapp: &Addr,
+        paid_denom: &String,
+        paid: u128,
+        total_fee: impl Fn(Decimal) -> Result<u128, ContractError>,
+    ) -> Result<(String, u128), ContractError> {
+        let required = total_fee(self.get_fee_denom_rate(store, paid_denom)?)?;
+        if paid >= required {
+            return Ok((paid_denom.clone(), 0));
+        }
+        let shortfall = required - paid;
+        if self.get_fee_balance(store, dapp, paid_denom) >= shortfall {
+            return Ok((paid_denom.clone(), shortfall));
+        }
+
+        for (denom, balance) in self.get_fee_balances(store, dapp)? {
+            // balances left in a denom that is no longer accepted cannot pay fees
+            let rate = match self.get_fee_denom_rate(store, &denom) {
+                Ok(rate) => rate,
+                Err(_) => continue,
+            };
+            let required = total_fee(rate)?;
+            if denom != *paid_denom && balance >= required {
+                return Ok((denom, required));
+            }
+        }
+        Err(ContractError::InsufficientFunds)
+    }
+
+    /// Lists the fees drawn from the prepaid balance of `dapp`, oldest first.
+    pub fn query_fee_usage(
+        &self,
+        store: &dyn Storage,
+        dapp: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<FeeUsage>> {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+        self.fee_usage()
+            .prefix(dapp)
+            .range(
+                store,
+                start_after.map(Bound::exclusive),
+                None,
+                cosmwasm_std::Order::Ascending,
+            )
+            .take(limit)
+            .map(|item| item.map(|(_, usage)| usage))
+            .collect()
+    }
+}
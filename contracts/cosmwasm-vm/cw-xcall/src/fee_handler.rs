@@ -27,7 +27,7 @@ impl<'a> CwCallService<'a> {
         info: &MessageInfo,
         address: String,
     ) -> Result<Response, ContractError> {
-        self.ensure_admin(deps.storage, info.sender.clone())?;
+        self.ensure_role(deps.storage, Role::FeeManager, &info.sender)?;
         deps.api.addr_validate(&address)?;
         self.add_feehandler(deps.storage, &address)?;
         Ok(Response::new().add_attribute("method", "set_protocol_feehandler"))
@@ -43,9 +43,9 @@ impl<'a> CwCallService<'a> {
     ///
     /// Returns:
     ///
-    /// A string representing the protocol fee handler.
-    pub fn get_protocol_feehandler(&self, deps: Deps) -> String {
-        self.query_feehandler(deps.storage).unwrap()
+    /// The protocol fee handler address, or an error if none has been set.
+    pub fn get_protocol_feehandler(&self, deps: Deps) -> Result<String, ContractError> {
+        self.query_feehandler(deps.storage)
     }
 }
 
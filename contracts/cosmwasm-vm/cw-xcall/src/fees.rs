@@ -1,5 +1,5 @@
-use cosmwasm_std::Coin;
-use cw_xcall_lib::network_address::NetId;
+use cosmwasm_std::{Coin, Decimal, Uint128, Uint256};
+use cw_xcall_lib::{network_address::NetId, xcall_query_msg::FeeDenom};
 
 use super::*;
 /// This is an implementation of two methods for the `CwCallService` struct.
@@ -32,24 +32,32 @@ impl<'a> CwCallService<'a> {
         info: MessageInfo,
         value: u128,
     ) -> Result<Response, ContractError> {
-        self.ensure_admin(deps.storage, info.sender)?;
+        self.ensure_role(deps.storage, Role::FeeManager, &info.sender)?;
         self.store_protocol_fee(deps.storage, value)?;
 
         Ok(Response::new().add_attribute("method", "set_protocolfee"))
     }
 
+    /// Returns the total fee for sending a message to `nid`, quoted in `denom` or in the base denom
+    /// when no denom is given. Every fee component is converted separately, the same way `send_call`
+    /// splits the paid funds.
     pub fn get_fee(
         &self,
         deps: Deps,
         nid: NetId,
         rollback: bool,
         sources: Vec<String>,
+        denom: Option<String>,
     ) -> Result<u128, ContractError> {
         if !rollback && self.is_reply(deps, nid.clone(), &sources) {
             return Ok(0_u128);
         }
 
-        let protocol_fee = self.get_protocol_fee(deps.storage);
+        let rate = match denom {
+            Some(denom) => self.get_fee_denom_rate(deps.storage, &denom)?,
+            None => Decimal::one(),
+        };
+        let protocol_fee = convert_fee(self.get_protocol_fee(deps.storage), rate)?;
         let mut sources = sources;
         if sources.is_empty() {
             let conn = self.get_default_connection(deps.storage, nid.clone())?;
@@ -57,21 +65,116 @@ impl<'a> CwCallService<'a> {
         }
         let conn_fees = sources
             .into_iter()
-            .map(|s| self.query_connection_fee(deps, nid.clone(), rollback, &s))
+            .map(|s| {
+                self.query_connection_fee(deps, nid.clone(), rollback, &s)
+                    .and_then(|fee| convert_fee(fee, rate))
+            })
             .collect::<Result<Vec<u128>, ContractError>>()?;
         let conn_total: u128 = conn_fees.iter().sum();
 
         Ok(protocol_fee + conn_total)
     }
 
-    pub fn get_total_paid(&self, deps: Deps, coins: &Vec<Coin>) -> Result<u128, ContractError> {
+    /// Returns the denom the fee was paid in and the amount paid in it. Funds in denoms that are not
+    /// accepted for fees are ignored, and paying in more than one accepted denom at once is rejected.
+    /// When no accepted funds were sent the base denom is returned with a zero amount.
+    pub fn get_total_paid(
+        &self,
+        deps: Deps,
+        coins: &Vec<Coin>,
+    ) -> Result<(String, u128), ContractError> {
         let config = self.get_config(deps.storage)?;
-        let mut total = 0_u128;
+        let mut paid: Option<(String, u128)> = None;
         for c in coins.iter() {
-            if c.denom == config.denom {
-                total += c.amount.u128();
+            if c.amount.is_zero()
+                || (c.denom != config.denom
+                    && !self.fee_denoms().has(deps.storage, c.denom.clone()))
+            {
+                continue;
             }
+            match &mut paid {
+                Some((denom, total)) if *denom == c.denom => *total += c.amount.u128(),
+                Some(_) => return Err(ContractError::MultipleFeeDenoms),
+                None => paid = Some((c.denom.clone(), c.amount.u128())),
+            }
+        }
+        Ok(paid.unwrap_or((config.denom, 0)))
+    }
+
+    /// Returns how many units of `denom` are charged for one unit of fee quoted in the base denom.
+    pub fn get_fee_denom_rate(
+        &self,
+        store: &dyn Storage,
+        denom: &String,
+    ) -> Result<Decimal, ContractError> {
+        let config = self.get_config(store)?;
+        if *denom == config.denom {
+            return Ok(Decimal::one());
         }
-        Ok(total)
+        self.fee_denoms().load(store, denom.clone()).map_err(|_| {
+            ContractError::UnsupportedFeeDenom {
+                denom: denom.clone(),
+            }
+        })
+    }
+
+    /// Accepts `denom` for fees, charging `rate` units of it for every unit of fee quoted in the base
+    /// denom. Connections are paid in the denom the user paid in, so they must accept it as well,
+    /// at the same rate, with their own `SetFeeDenom`.
+    pub fn set_fee_denom(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        denom: String,
+        rate: Decimal,
+    ) -> Result<Response, ContractError> {
+        self.ensure_role(deps.storage, Role::FeeManager, &info.sender)?;
+        let config = self.get_config(deps.storage)?;
+        ensure!(
+            denom != config.denom && !rate.is_zero(),
+            ContractError::UnsupportedFeeDenom { denom }
+        );
+        self.fee_denoms().save(deps.storage, denom.clone(), &rate)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "set_fee_denom")
+            .add_attribute("denom", denom)
+            .add_attribute("rate", rate.to_string()))
+    }
+
+    pub fn remove_fee_denom(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        denom: String,
+    ) -> Result<Response, ContractError> {
+        self.ensure_role(deps.storage, Role::FeeManager, &info.sender)?;
+        self.fee_denoms().remove(deps.storage, denom.clone());
+
+        Ok(Response::new()
+            .add_attribute("method", "remove_fee_denom")
+            .add_attribute("denom", denom))
+    }
+
+    pub fn query_fee_denoms(&self, store: &dyn Storage) -> StdResult<Vec<FeeDenom>> {
+        self.fee_denoms()
+            .range(store, None, None, cosmwasm_std::Order::Ascending)
+            .map(|item| item.map(|(denom, rate)| FeeDenom { denom, rate }))
+            .collect()
+    }
+}
+
+/// Converts a fee quoted in the base denom with `rate`, rounding up so that the converted fee never
+/// falls short of the quoted one.
+pub fn convert_fee(amount: u128, rate: Decimal) -> Result<u128, ContractError> {
+    if rate == Decimal::one() {
+        return Ok(amount);
     }
+    let denominator = Uint256::from(Decimal::one().atomics());
+    let converted = Uint128::new(amount)
+        .full_mul(rate.atomics())
+        .checked_add(denominator - Uint256::one())
+        .map_err(StdError::from)?
+        / denominator;
+    Ok(Uint128::try_from(converted).map_err(StdError::from)?.u128())
 }
@@ -1,5 +1,10 @@
 use common::{rlp, utils::keccak256};
-use cw_xcall_lib::network_address::NetId;
+use cw_xcall_lib::{network_address::NetId, xcall_query_msg::MessageStatus};
+
+use crate::{
+    events::event_call_result_failed,
+    types::{pause::PauseDirection, pending::DeliveryKind},
+};
 
 use super::*;
 
@@ -7,22 +12,26 @@ impl<'a> CwCallService<'a> {
     pub fn handle_message(
         &self,
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         from_nid: NetId,
         message: Vec<u8>,
     ) -> Result<Response, ContractError> {
-        let cfg = self.get_config(deps.storage).unwrap();
+        let cfg = self.get_config(deps.storage)?;
         if cfg.network_id == from_nid.to_string() {
             return Err(ContractError::ProtocolsMismatch);
         }
+        if self.is_paused(deps.storage, &from_nid, PauseDirection::Inbound) {
+            return self.queue_paused_message(deps.storage, info, from_nid, message);
+        }
 
         let call_service_message: CSMessage = CSMessage::try_from(message)?;
         match call_service_message.message_type() {
-            CSMessageType::CSMessageRequest => {
-                self.handle_request(deps, info, from_nid, call_service_message.payload())
+            CSMessageType::CSMessageRequest | CSMessageType::CSMessageRequestV2 => {
+                self.handle_request(deps, env, info, from_nid, call_service_message.payload())
             }
             CSMessageType::CSMessageResult => {
-                self.handle_result(deps, info, call_service_message.payload())
+                self.handle_result(deps, env, info, call_service_message.payload())
             }
         }
     }
@@ -30,6 +39,7 @@ impl<'a> CwCallService<'a> {
     pub fn handle_reply(
         &self,
         deps: DepsMut,
+        connection: &str,
         rollback: Rollback,
         request: CSMessageRequest,
     ) -> Result<Event, ContractError> {
@@ -44,7 +54,7 @@ impl<'a> CwCallService<'a> {
             request.to().clone(),
             request.sequence_no(),
             request.msg_type(),
-            keccak256(request.data().unwrap()).to_vec(),
+            keccak256(request.data()?).to_vec(),
             rollback.protocols().clone(),
         );
         self.store_proxy_request(deps.storage, request_id, &req)?;
@@ -54,7 +64,9 @@ impl<'a> CwCallService<'a> {
             request.to().to_string(),
             request.sequence_no(),
             request_id,
-            request.data().unwrap().to_vec(),
+            request.data()?.to_vec(),
+            connection,
+            rollback.protocols(),
         );
         Ok(event)
     }
@@ -62,14 +74,14 @@ impl<'a> CwCallService<'a> {
     pub fn handle_request(
         &self,
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         src_net: NetId,
         data: &[u8],
     ) -> Result<Response, ContractError> {
-        let request: CSMessageRequest = rlp::decode(data).unwrap();
+        let mut request = CSMessageRequest::try_from(data)?;
 
-        let from = request.from().clone();
-        if from.nid() != src_net {
+        if request.from().nid() != src_net {
             return Err(ContractError::ProtocolsMismatch);
         }
         let source = info.sender.to_string();
@@ -79,20 +91,58 @@ impl<'a> CwCallService<'a> {
             return Err(ContractError::ProtocolsMismatch);
         }
 
-        let to = deps.api.addr_validate(request.to().as_str())?;
+        deps.api.addr_validate(request.to().as_str())?;
 
         if request.protocols().len() > 1 {
             let key = keccak256(data).to_vec();
-            let caller = info.sender;
-            self.save_pending_requests(deps.storage, key.clone(), caller.to_string())?;
-            let registered =
-                self.get_pending_requests_by_hash(deps.as_ref().storage, key.clone())?;
-
-            if registered.len() != request.protocols().len() {
+            let threshold =
+                self.get_inbound_threshold(deps.storage, request.to(), request.threshold());
+            let accepted = self.register_delivery(
+                deps.storage,
+                DeliveryKind::Request,
+                key.clone(),
+                info.sender.to_string(),
+                request.protocols(),
+                threshold,
+                env.block.time,
+            )?;
+            if !accepted {
                 return Ok(Response::new());
             }
+            // a request accepted before every protocol delivered it only reports the protocols
+            // that did, which are also the ones the result is sent back through
+            let delivered =
+                self.get_delivered_protocols(deps.storage, DeliveryKind::Request, key)?;
+            if !delivered.is_empty() {
+                request = request.with_protocols(delivered);
+            }
+        }
+        if let Some(ordered_sn) = request.ordered_sn() {
+            return self.handle_ordered_request(deps, &env, source, request, ordered_sn);
+        }
+        self.deliver_request(deps, &env, &source, request)
+    }
 
-            self.remove_pending_request_by_hash(deps.storage, key)?;
+    /// Stores an accepted request for `ExecuteCall` and emits its `CallMessage` event, or rejects
+    /// it when it has expired or does not match the inbound policy of its destination dapp.
+    /// `connection` is the connection whose delivery made the request accepted.
+    pub fn deliver_request(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        connection: &str,
+        request: CSMessageRequest,
+    ) -> Result<Response, ContractError> {
+        let source_nid = request.from().nid();
+        if let Some(sent_height) = request.sent_height() {
+            self.store_source_height(deps.storage, &source_nid, sent_height)?;
+        }
+        let source_height = self.get_source_height(deps.storage, &source_nid);
+        if request.is_expired(&env.block, source_height) {
+            return self.reject_expired_request(deps.as_ref(), &request);
+        }
+        if !self.is_allowed_inbound(deps.storage, connection, &request) {
+            return self.reject_unauthorized_request(deps.as_ref(), &request);
         }
         let request_id = self.increment_last_request_id(deps.storage)?;
 
@@ -101,17 +151,22 @@ impl<'a> CwCallService<'a> {
             request.to().clone(),
             request.sequence_no(),
             request.msg_type(),
-            keccak256(request.data().unwrap()).to_vec(),
+            keccak256(request.data()?).to_vec(),
             request.protocols().clone(),
-        );
+        )
+        .with_expiry(request.expiry().clone())
+        .with_sent_height(request.sent_height())
+        .with_gas_limit(request.gas_limit());
         self.store_proxy_request(deps.storage, request_id, &req)?;
 
         let event = event_call_message(
-            from.to_string(),
-            to.to_string(),
+            request.from().to_string(),
+            request.to().to_string(),
             request.sequence_no(),
             request_id,
-            request.data().unwrap().to_vec(),
+            request.data()?.to_vec(),
+            connection,
+            request.protocols(),
         );
 
         Ok(Response::new()
@@ -122,19 +177,49 @@ impl<'a> CwCallService<'a> {
 
     pub fn handle_result(
         &self,
-        deps: DepsMut,
+        mut deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         data: &[u8],
     ) -> Result<Response, ContractError> {
-        let result: CSMessageResult = rlp::decode(data).unwrap();
+        let result = CSMessageResult::try_from(data)?;
 
         let response_sequence_no = result.sequence_no();
+        // the sending dapp already ran the rollback of a cancelled request
+        ensure!(
+            self.get_message_status(deps.storage, response_sequence_no)
+                != Some(MessageStatus::Cancelled),
+            ContractError::CallRequestCancelled {
+                sn: response_sequence_no
+            }
+        );
 
-        let mut call_request = self
-            .get_call_request(deps.storage, response_sequence_no)
-            .map_err(|_e| ContractError::CallRequestNotFound {
-                sn: response_sequence_no,
-            })?;
+        let key = keccak256(data).to_vec();
+        let accepted = self
+            .get_pending_delivery(deps.storage, DeliveryKind::Response, &key)
+            .map_or(false, |pending| pending.accepted);
+        let mut call_request = match self.get_call_request(deps.storage, response_sequence_no) {
+            Ok(call_request) => call_request,
+            // a late delivery of a response accepted with a threshold, after the call request was
+            // resolved
+            Err(_) if accepted => {
+                self.register_delivery(
+                    deps.storage,
+                    DeliveryKind::Response,
+                    key,
+                    info.sender.to_string(),
+                    &[],
+                    None,
+                    env.block.time,
+                )?;
+                return Ok(Response::new());
+            }
+            Err(_) => {
+                return Err(ContractError::CallRequestNotFound {
+                    sn: response_sequence_no,
+                })
+            }
+        };
 
         let source = info.sender.to_string();
         let source_valid = self.is_valid_source(
@@ -148,56 +233,124 @@ impl<'a> CwCallService<'a> {
         }
 
         if call_request.protocols().len() > 1 {
-            let key = keccak256(data).to_vec();
-            let caller = info.sender;
-            self.save_pending_responses(deps.storage, key.clone(), caller.to_string())?;
-            let registered =
-                self.get_pending_responses_by_hash(deps.as_ref().storage, key.clone())?;
-
-            if registered.len() != call_request.protocols().len() {
+            let accepted = self.register_delivery(
+                deps.storage,
+                DeliveryKind::Response,
+                key,
+                info.sender.to_string(),
+                call_request.protocols(),
+                call_request.threshold(),
+                env.block.time,
+            )?;
+            if !accepted {
                 return Ok(Response::new());
             }
-
-            self.remove_pending_responses_by_hash(deps.storage, key)?;
         }
         let response_event = event_response_message(
             response_sequence_no,
             (result.response_code().clone()).into(),
+            &source,
         );
 
         match result.response_code() {
             CallServiceResponseType::CallServiceResponseSuccess => {
                 self.cleanup_request(deps.storage, response_sequence_no);
                 self.set_successful_response(deps.storage, response_sequence_no)?;
+                self.store_message_status(
+                    deps.storage,
+                    response_sequence_no,
+                    MessageStatus::Succeeded,
+                )?;
                 let mut res = Response::new()
                     .add_attribute("action", "call_service")
                     .add_attribute("method", "handle_response")
                     .add_event(response_event);
-                if result.get_message().is_some() {
-                    let reply = result.get_message().unwrap();
-                    let event = self.handle_reply(deps, call_request, reply)?;
+                if call_request.result_callback() {
+                    let callback = self.call_dapp_handle_result(
+                        call_request.from(),
+                        response_sequence_no,
+                        true,
+                    )?;
+                    res = res.add_submessage(callback);
+                }
+                for reply in result.get_messages() {
+                    let event =
+                        self.handle_reply(deps.branch(), &source, call_request.clone(), reply)?;
                     res = res.add_event(event);
                 }
 
                 Ok(res)
             }
             _ => {
-                self.ensure_rollback_length(call_request.rollback())
-                    .unwrap();
+                // the rollback was checked against the limits when it was sent, a limit lowered
+                // since then must not keep it from running
                 call_request.set_enabled();
                 self.store_call_request(deps.storage, response_sequence_no, &call_request)?;
+                self.store_message_status(
+                    deps.storage,
+                    response_sequence_no,
+                    MessageStatus::RollbackEnabled,
+                )?;
 
-                let rollback_event = event_rollback_message(response_sequence_no);
+                let rollback_event =
+                    event_rollback_message(response_sequence_no, call_request.from());
 
-                Ok(Response::new()
+                let mut res = Response::new()
                     .add_attribute("action", "call_service")
                     .add_attribute("method", "handle_response")
                     .add_event(response_event)
-                    .add_event(rollback_event))
+                    .add_event(rollback_event);
+                if call_request.result_callback() {
+                    let callback = self.call_dapp_handle_result(
+                        call_request.from(),
+                        response_sequence_no,
+                        false,
+                    )?;
+                    res = res.add_submessage(callback);
+                }
+
+                Ok(res)
             }
         }
     }
 
+    /// Drops a request whose expiry has passed. When the sender expects a response, a failure
+    /// result is sent back so the rollback is enabled on the source chain.
+    pub fn reject_expired_request(
+        &self,
+        deps: Deps,
+        request: &CSMessageRequest,
+    ) -> Result<Response, ContractError> {
+        let submsgs = self.send_failure_result(deps, request)?;
+
+        Ok(Response::new()
+            .add_submessages(submsgs)
+            .add_attribute("action", "call_service")
+            .add_attribute("method", "reject_expired_request")
+            .add_event(event_message_expired(
+                request.from().to_string(),
+                request.sequence_no(),
+            )))
+    }
+
+    /// Sends a failure result for a request that is dropped before reaching its destination dapp,
+    /// when the sender expects a response.
+    pub fn send_failure_result(
+        &self,
+        deps: Deps,
+        request: &CSMessageRequest,
+    ) -> Result<Vec<SubMsg>, ContractError> {
+        if !request.need_response() {
+            return Ok(vec![]);
+        }
+        let result = CSMessageResult::new(
+            request.sequence_no(),
+            CallServiceResponseType::CallServiceResponseFailure,
+            None,
+        );
+        self.send_call_result(deps, request, result)
+    }
+
     /// The function removes a call request from storage based on a given sequence number.
     ///
     /// Arguments:
@@ -234,14 +387,32 @@ impl<'a> CwCallService<'a> {
     pub fn handle_error(
         &self,
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         sn: u128,
     ) -> Result<Response, ContractError> {
+        if let Ok(rollback) = self.get_call_request(deps.storage, sn) {
+            self.ensure_not_paused(deps.storage, &rollback.to().nid(), PauseDirection::Inbound)?;
+        }
         let msg = CSMessageResult::new(
             sn,
             CallServiceResponseType::CallServiceResponseFailure,
             None,
         );
-        self.handle_result(deps, info, &rlp::encode(&msg))
+        self.handle_result(deps, env, info, &rlp::encode(&msg))
+    }
+
+    /// Handles the reply of a `HandleCallResult` callback. The callback only replies on error, which
+    /// is recorded in an event instead of reverting the result that triggered it.
+    pub fn call_result_reply(&self, msg: Reply) -> Result<Response, ContractError> {
+        let error = match msg.result {
+            cosmwasm_std::SubMsgResult::Err(err) => err,
+            cosmwasm_std::SubMsgResult::Ok(_) => return Ok(Response::new()),
+        };
+
+        Ok(Response::new()
+            .add_attribute("action", "call_service")
+            .add_attribute("method", "call_result_reply")
+            .add_event(event_call_result_failed(&error)))
     }
 }
@@ -0,0 +1,60 @@
+use cosmwasm_std::{ensure, ensure_eq, DepsMut, MessageInfo, Response};
+
+use crate::{
+    error::ContractError,
+    events::{event_call_executed, event_forced_rollback},
+    state::CwCallService,
+    types::result::{CSMessageResult, CallServiceResponseType},
+};
+
+impl<'a> CwCallService<'a> {
+    /// Lets the destination dapp give up on a stored `CallMessageWithRollback` request and send a
+    /// failure result back to the source chain, so the rollback can be executed there.
+    ///
+    /// Arguments:
+    ///
+    /// * `deps`: A mutable reference to the dependencies of the contract.
+    /// * `info`: `info` holds the sender, which must be the dapp the request was addressed to.
+    /// * `request_id`: The id of the proxy request emitted in the `CallMessage` event.
+    ///
+    /// Returns:
+    ///
+    /// a `Result<Response, ContractError>` carrying the submessages that deliver the failure result
+    /// over every protocol of the request, or over the default connection when it has none.
+    pub fn handle_forced_rollback(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        request_id: u128,
+    ) -> Result<Response, ContractError> {
+        let request = self
+            .get_proxy_request(deps.storage, request_id)
+            .map_err(|_| ContractError::InvalidRequestId { id: request_id })?;
+
+        ensure_eq!(&info.sender, request.to(), ContractError::OnlyDapp);
+        ensure!(request.need_response(), ContractError::RollbackNotPossible);
+        if let Ok(executing) = self.get_execute_request_id(deps.storage) {
+            ensure!(
+                executing != request_id,
+                ContractError::CallAlreadyInProgress
+            );
+        }
+
+        self.remove_proxy_request(deps.storage, request_id);
+
+        let code = CallServiceResponseType::CallServiceResponseFailure;
+        let result = CSMessageResult::new(request.sequence_no(), code.clone(), None);
+        let submsgs = self.send_call_result(deps.as_ref(), &request, result)?;
+
+        Ok(Response::new()
+            .add_submessages(submsgs)
+            .add_attribute("action", "call_message")
+            .add_attribute("method", "handle_forced_rollback")
+            .add_event(event_call_executed(
+                request_id,
+                code.into(),
+                "ForcedRollback",
+            ))
+            .add_event(event_forced_rollback(request_id, request.sequence_no())))
+    }
+}
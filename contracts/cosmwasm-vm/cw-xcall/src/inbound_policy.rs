@@ -0,0 +1,116 @@
+use cw_xcall_lib::xcall_query_msg::InboundPolicy;
+
+use crate::events::event_call_message_rejected;
+
+use super::*;
+
+impl<'a> CwCallService<'a> {
+    /// Registers the inbound policy of the sender, replacing the previous one. Requests to the
+    /// sender that do not match it are rejected instead of being delivered.
+    pub fn set_inbound_policy(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        policy: InboundPolicy,
+    ) -> Result<Response, ContractError> {
+        ensure!(
+            policy.protocol_sets.iter().all(|set| !set.is_empty()) && policy.threshold != Some(0),
+            ContractError::InvalidInboundPolicy
+        );
+        self.store_inbound_policy(deps.storage, &info.sender, &policy)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "set_inbound_policy")
+            .add_attribute("dapp", info.sender.to_string()))
+    }
+
+    /// Removes the inbound policy of the sender, which then accepts requests from any source.
+    pub fn remove_inbound_policy_by_dapp(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        self.remove_inbound_policy(deps.storage, &info.sender);
+
+        Ok(Response::new()
+            .add_attribute("method", "remove_inbound_policy")
+            .add_attribute("dapp", info.sender.to_string()))
+    }
+
+    /// Whether the destination dapp of `request` accepts it. `connection` is the connection whose
+    /// delivery made the request accepted.
+    ///
+    /// Protocol sets are matched against the protocols that delivered the request, never the ones
+    /// it claims: `handle_request` narrows the protocols of a request accepted by a threshold to
+    /// the ones that delivered it, and the accepting connection is the only protocol of a request
+    /// sent without protocols.
+    pub fn is_allowed_inbound(
+        &self,
+        store: &dyn Storage,
+        connection: &str,
+        request: &CSMessageRequest,
+    ) -> bool {
+        let policy = match self.get_inbound_policy(store, request.to()) {
+            Some(policy) => policy,
+            None => return true,
+        };
+        let from = request.from();
+        let source_allowed = (policy.sources.is_empty() && policy.networks.is_empty())
+            || policy.sources.contains(from)
+            || policy.networks.contains(&from.nid());
+        if !source_allowed {
+            return false;
+        }
+
+        let mut protocols = request.protocols().clone();
+        protocols.push(connection.to_string());
+        protocols.sort();
+        protocols.dedup();
+        policy.protocol_sets.is_empty()
+            || policy.protocol_sets.into_iter().any(|mut set| {
+                set.sort();
+                set.dedup();
+                set == protocols
+            })
+    }
+
+    /// The threshold a request to `dapp` is accepted with. Thresholds are only honoured when
+    /// both the request and the inbound policy of `dapp` set one, the larger of the two applying,
+    /// so a single connection can not lower the quorum by rewriting the request. `None` requires
+    /// every protocol of the request.
+    pub fn get_inbound_threshold(
+        &self,
+        store: &dyn Storage,
+        dapp: &Addr,
+        claimed: Option<u32>,
+    ) -> Option<u32> {
+        let configured = self
+            .get_inbound_policy(store, dapp)
+            .and_then(|policy| policy.threshold);
+        match (claimed, configured) {
+            (Some(claimed), Some(configured)) => Some(claimed.max(configured)),
+            _ => None,
+        }
+    }
+
+    /// Drops a request rejected by the inbound policy of its destination dapp. When the sender
+    /// expects a response, a failure result is sent back so the rollback is enabled on the source
+    /// chain.
+    pub fn reject_unauthorized_request(
+        &self,
+        deps: Deps,
+        request: &CSMessageRequest,
+    ) -> Result<Response, ContractError> {
+        let submsgs = self.send_failure_result(deps, request)?;
+
+        Ok(Response::new()
+            .add_submessages(submsgs)
+            .add_attribute("action", "call_service")
+            .add_attribute("method", "reject_unauthorized_request")
+            .add_event(event_call_message_rejected(
+                request.from().to_string(),
+                request.to().to_string(),
+                request.sequence_no(),
+            )))
+    }
+}
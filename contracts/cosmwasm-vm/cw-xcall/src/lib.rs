@@ -7,11 +7,22 @@ pub mod error;
 pub mod events;
 pub mod execute_call;
 pub mod execute_rollback;
+pub mod extended_messages;
+pub mod fee_account;
 pub mod fee_handler;
 pub mod fees;
 pub mod handle_call_message;
+pub mod handle_forced_rollback;
+pub mod inbound_policy;
+pub mod migrations;
 pub mod msg;
+pub mod ordered_delivery;
+pub mod pause;
+pub mod payload_limits;
+pub mod pending_deliveries;
+pub mod query;
 pub mod requests;
+pub mod roles;
 pub mod send_call_message;
 pub mod state;
 pub mod types;
@@ -19,11 +30,13 @@ pub mod types;
 use crate::{
     error::ContractError,
     events::{
-        event_call_message, event_response_message, event_rollback_message,
+        event_call_message, event_message_expired, event_response_message, event_rollback_message,
         event_xcall_message_sent,
     },
     msg::{InstantiateMsg, QueryMsg},
-    state::{CwCallService, EXECUTE_CALL_ID},
+    state::{
+        is_execute_call_reply, CwCallService, CALL_RESULT_REPLY_ID, SEND_CALL_MESSAGE_REPLY_ID,
+    },
     types::{
         message::{CSMessage, CSMessageType},
         request::CSMessageRequest,
@@ -39,9 +52,9 @@ use cosmwasm_std::{
     MessageInfo, QuerierWrapper, Reply, Response, StdError, StdResult, Storage, SubMsg,
 };
 
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
 use cw_storage_plus::{Item, Map};
-use cw_xcall_lib::xcall_msg::ExecuteMsg;
+use cw_xcall_lib::xcall_msg::{ExecuteMsg, Role};
 
 use serde::Serialize;
 use thiserror::Error;
@@ -163,8 +176,15 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
     call_service.reply(deps, env, msg)
 }
 
+/// Parameters applied once the storage migrations have run.
 #[cw_serde]
-pub struct MigrateMsg {}
+#[derive(Default)]
+pub struct MigrateMsg {
+    /// Sets the pending delivery ttl, see `ExecuteMsg::SetPendingDeliveryTtl`.
+    #[serde(default)]
+    pub pending_delivery_ttl: Option<u64>,
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
     let call_service = CwCallService::default();
@@ -0,0 +1,190 @@
+use cosmwasm_std::Order;
+use cw_storage_plus::Bound;
+use cw_xcall_lib::xcall_query_msg::MessageStatus;
+
+use crate::types::pending::DeliveryKind;
+
+use super::*;
+
+/// Call requests backfilled by `migrate` and, at most, by a single `ContinueBackfill`.
+pub const BACKFILL_BATCH_SIZE: usize = 100;
+
+type MigrationFn = fn(&CwCallService, &mut dyn Storage, &Env) -> Result<(), ContractError>;
+
+/// A storage rewrite introduced in `version`. It runs when a contract stored at an older version
+/// is migrated to `version` or later.
+pub struct MigrationStep {
+    pub version: &'static str,
+    pub name: &'static str,
+    pub run: MigrationFn,
+}
+
+/// The migration steps, ordered by version and then by the order they must run in.
+pub const MIGRATIONS: &[MigrationStep] = &[
+    MigrationStep {
+        version: "0.3.0",
+        name: "backfill_call_requests",
+        run: backfill_call_requests,
+    },
+    MigrationStep {
+        version: "0.3.0",
+        name: "backfill_pending_deliveries",
+        run: backfill_pending_deliveries,
+    },
+];
+
+/// Parses a `major.minor.patch` version, ignoring any pre-release or build suffix.
+pub fn parse_version(version: &str) -> Result<(u64, u64, u64), ContractError> {
+    let invalid = || ContractError::InvalidVersion {
+        version: version.to_string(),
+    };
+    let core = version
+        .split(|c: char| c == '-' || c == '+')
+        .next()
+        .unwrap_or_default();
+    let parts = core
+        .split('.')
+        .map(|part| part.parse::<u64>().map_err(|_| invalid()))
+        .collect::<Result<Vec<u64>, ContractError>>()?;
+    match parts.as_slice() {
+        [major, minor, patch] => Ok((*major, *minor, *patch)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Returns the steps to run when migrating from version `from` to version `to`, in order.
+pub fn pending_migrations(
+    from: (u64, u64, u64),
+    to: (u64, u64, u64),
+) -> Result<Vec<&'static MigrationStep>, ContractError> {
+    let mut steps = vec![];
+    for step in MIGRATIONS {
+        let version = parse_version(step.version)?;
+        if from < version && version <= to {
+            steps.push(step);
+        }
+    }
+    Ok(steps)
+}
+
+/// Records the status and a send time of the two-way messages sent before they were tracked, so
+/// they no longer rely on the fallback of `query_message_status` and their rollback can be
+/// cancelled. The actual send time is unknown and is set to the time of the migration.
+///
+/// Only the first `BACKFILL_BATCH_SIZE` call requests are backfilled, the rest with
+/// `ContinueBackfill`.
+fn backfill_call_requests(
+    call_service: &CwCallService,
+    store: &mut dyn Storage,
+    env: &Env,
+) -> Result<(), ContractError> {
+    call_service.backfill_call_requests(store, env, None, BACKFILL_BATCH_SIZE)
+}
+
+/// Tracks the messages partially delivered before the pending delivery records existed, so they
+/// show up in the pending queries and can be purged. Their first delivery time is unknown and is
+/// set to the time of the migration.
+fn backfill_pending_deliveries(
+    call_service: &CwCallService,
+    store: &mut dyn Storage,
+    env: &Env,
+) -> Result<(), ContractError> {
+    for kind in [DeliveryKind::Request, DeliveryKind::Response] {
+        call_service.backfill_pending_deliveries(store, kind, env.block.time)?;
+    }
+    Ok(())
+}
+
+impl<'a> CwCallService<'a> {
+    /// Backfills up to `limit` call requests after `start_after`, recording where it stopped when
+    /// more remain.
+    pub fn backfill_call_requests(
+        &self,
+        store: &mut dyn Storage,
+        env: &Env,
+        start_after: Option<u128>,
+        limit: usize,
+    ) -> Result<(), ContractError> {
+        let requests = self
+            .call_requests()
+            .range(
+                store,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit + 1)
+            .collect::<StdResult<Vec<(u128, Rollback)>>>()?;
+        let cursor = match requests.len() > limit {
+            true => requests.get(limit - 1).map(|(sn, _)| *sn),
+            false => None,
+        };
+        for (sn, rollback) in requests.into_iter().take(limit) {
+            if self.get_message_status(store, sn).is_none() {
+                let status = match rollback.enabled() {
+                    true => MessageStatus::RollbackEnabled,
+                    false => MessageStatus::AwaitingResponse,
+                };
+                self.store_message_status(store, sn, status)?;
+            }
+            if rollback.sent_at().is_none() {
+                let rollback = rollback.with_sent_at(Some(env.block.time));
+                self.store_call_request(store, sn, &rollback)?;
+            }
+        }
+        self.store_backfill_cursor(store, cursor)
+    }
+
+    /// Continues the backfill of call requests a migration left unfinished. Callable by anyone.
+    ///
+    /// Arguments:
+    ///
+    /// * `limit`: Maximum number of call requests backfilled, defaults to and is capped at
+    /// `BACKFILL_BATCH_SIZE`.
+    pub fn continue_backfill(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        let cursor = self
+            .get_backfill_cursor(deps.storage)
+            .ok_or(ContractError::NoBackfillInProgress)?;
+        let limit = limit.map_or(BACKFILL_BATCH_SIZE, |limit| {
+            (limit as usize).clamp(1, BACKFILL_BATCH_SIZE)
+        });
+        self.backfill_call_requests(deps.storage, &env, Some(cursor), limit)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "call_service")
+            .add_attribute("method", "continue_backfill")
+            .add_attribute(
+                "done",
+                self.get_backfill_cursor(deps.storage).is_none().to_string(),
+            ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("0.2.1").unwrap(), (0, 2, 1));
+        assert_eq!(parse_version("1.10.0-beta.1").unwrap(), (1, 10, 0));
+        assert!(parse_version("0.2").is_err());
+        assert!(parse_version("v0.2.1").is_err());
+    }
+
+    #[test]
+    fn test_pending_migrations() {
+        let steps = pending_migrations((0, 2, 1), (0, 3, 0)).unwrap();
+        let names: Vec<&str> = steps.iter().map(|step| step.name).collect();
+        assert_eq!(
+            names,
+            vec!["backfill_call_requests", "backfill_pending_deliveries"]
+        );
+        assert!(pending_migrations((0, 3, 0), (0, 3, 0)).unwrap().is_empty());
+    }
+}
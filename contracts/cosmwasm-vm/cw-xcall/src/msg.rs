@@ -1,4 +1,11 @@
-use cw_xcall_lib::network_address::NetId;
+use cw_xcall_lib::{
+    network_address::{NetId, NetworkAddress},
+    xcall_query_msg::{
+        DappConnections, DecodedCsMessage, FeeDenom, FeeUsage, InboundPolicy, MessageStatus,
+        OrderedChannelResponse, PauseStateResponse, PayloadLimits, PendingDeliveriesResponse,
+        ProxyRequestResponse, ProxyRequestsResponse, RollbackResponse, RollbacksResponse,
+    },
+};
 
 use super::*;
 #[cw_serde]
@@ -32,5 +39,77 @@ pub enum QueryMsg {
         nid: NetId,
         rollback: bool,
         sources: Option<Vec<String>>,
+        denom: Option<String>,
+    },
+    #[returns(Vec<FeeDenom>)]
+    GetFeeDenoms {},
+    #[returns(RollbacksResponse)]
+    GetPendingRollbacks {
+        start_after: Option<u128>,
+        limit: Option<u32>,
+        dapp: Option<String>,
+        nid: Option<NetId>,
+    },
+    #[returns(RollbackResponse)]
+    GetRollback { sn: u128 },
+    #[returns(ProxyRequestsResponse)]
+    GetProxyRequests {
+        start_after: Option<u128>,
+        limit: Option<u32>,
+        dapp: Option<String>,
+        nid: Option<NetId>,
+    },
+    #[returns(ProxyRequestResponse)]
+    GetProxyRequest { req_id: u128 },
+    #[returns(MessageStatus)]
+    GetMessageStatus { sn: u128 },
+    #[returns(PauseStateResponse)]
+    GetPauseState { nid: NetId },
+    #[returns(PayloadLimits)]
+    GetPayloadLimits { nid: Option<NetId> },
+    #[returns(u128)]
+    GetFeeBalance { dapp: String, denom: Option<String> },
+    #[returns(Vec<FeeUsage>)]
+    GetFeeUsage {
+        dapp: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
     },
+    #[returns(PendingDeliveriesResponse)]
+    GetPendingRequests {
+        start_after: Option<Vec<u8>>,
+        limit: Option<u32>,
+    },
+    #[returns(PendingDeliveriesResponse)]
+    GetPendingResponses {
+        start_after: Option<Vec<u8>>,
+        limit: Option<u32>,
+    },
+    #[returns(Option<u64>)]
+    GetPendingDeliveryTtl {},
+    #[returns(OrderedChannelResponse)]
+    GetOrderedChannel {
+        from: NetworkAddress,
+        to: String,
+        start_after: Option<u128>,
+        limit: Option<u32>,
+    },
+    #[returns(DecodedCsMessage)]
+    DecodeCsMessage { msg: Binary },
+    #[returns(Option<InboundPolicy>)]
+    GetInboundPolicy { dapp: String },
+    #[returns(Option<DappConnections>)]
+    GetDappConnections { dapp: String, nid: NetId },
+    #[returns(Option<String>)]
+    GetPendingAdmin {},
+    #[returns(Vec<String>)]
+    GetRoleMembers { role: Role },
+    /// Whether `address` can act with `role`, as a member of the role or as the admin.
+    #[returns(bool)]
+    HasRole { role: Role, address: String },
+    #[returns(Option<u64>)]
+    GetRollbackGracePeriod {},
+    /// Whether extended requests and results with several replies can be sent to `nid`.
+    #[returns(bool)]
+    GetExtendedMessages { nid: NetId },
 }
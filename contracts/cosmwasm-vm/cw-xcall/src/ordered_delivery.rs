@@ -0,0 +1,205 @@
+use cw_xcall_lib::{
+    network_address::NetworkAddress,
+    xcall_query_msg::{BufferedRequestResponse, OrderedChannelResponse},
+};
+
+use crate::{
+    events::{event_call_message_buffered, event_ordered_sequence_skipped},
+    state::{DEFAULT_PAGE_LIMIT, MAX_ORDERED_LOOKAHEAD, MAX_ORDERED_RELEASES, MAX_PAGE_LIMIT},
+    types::ordered::BufferedRequest,
+};
+
+use super::*;
+
+impl<'a> CwCallService<'a> {
+    /// Delivers an ordered request once the requests before it in its channel, keyed by sender and
+    /// destination dapp, have been delivered. A request that arrives ahead of its turn is buffered,
+    /// up to `MAX_ORDERED_LOOKAHEAD` positions ahead, and delivering a request releases up to
+    /// `MAX_ORDERED_RELEASES` of the buffered ones that follow it.
+    ///
+    /// Arguments:
+    ///
+    /// * `connection`: The connection whose delivery made the request accepted.
+    /// * `request`: The accepted request.
+    /// * `ordered_sn`: The position of the request in its channel.
+    pub fn handle_ordered_request(
+        &self,
+        mut deps: DepsMut,
+        env: &Env,
+        connection: String,
+        request: CSMessageRequest,
+        ordered_sn: u128,
+    ) -> Result<Response, ContractError> {
+        let from = request.from().clone();
+        let to = request.to().clone();
+        let expected = self.get_last_ordered_sn(deps.storage, &from, &to) + 1;
+        if ordered_sn < expected {
+            return Err(ContractError::OrderedRequestAlreadyDelivered { ordered_sn });
+        }
+        if ordered_sn > expected {
+            ensure!(
+                ordered_sn - expected <= MAX_ORDERED_LOOKAHEAD,
+                ContractError::OrderedRequestTooFarAhead {
+                    expected,
+                    ordered_sn
+                }
+            );
+            if self
+                .get_buffered_request(deps.storage, &from, &to, ordered_sn)
+                .is_some()
+            {
+                return Err(ContractError::OrderedRequestAlreadyBuffered { ordered_sn });
+            }
+            let event = event_call_message_buffered(
+                from.to_string(),
+                to.to_string(),
+                request.sequence_no(),
+                ordered_sn,
+            );
+            let buffered = BufferedRequest {
+                request,
+                connection,
+            };
+            self.store_buffered_request(deps.storage, ordered_sn, &buffered)?;
+            return Ok(Response::new()
+                .add_attribute("action", "call_service")
+                .add_attribute("method", "handle_ordered_request")
+                .add_event(event));
+        }
+
+        self.store_last_ordered_sn(deps.storage, &from, &to, ordered_sn)?;
+        let res = self.deliver_request(deps.branch(), env, &connection, request)?;
+        self.release_buffered_requests(deps, env, &from, &to, MAX_ORDERED_RELEASES, res)
+    }
+
+    /// Skips the next expected position of the ordered channel from `from` to the dapp `to`, for a
+    /// request that will never arrive, and releases up to `MAX_ORDERED_RELEASES` of the buffered
+    /// requests that follow it. Only the admin can skip.
+    pub fn skip_ordered_sequence(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        from: NetworkAddress,
+        to: String,
+        ordered_sn: u128,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender)?;
+        let to = deps.api.addr_validate(&to)?;
+
+        let expected = self.get_last_ordered_sn(deps.storage, &from, &to) + 1;
+        ensure_eq!(
+            ordered_sn,
+            expected,
+            ContractError::InvalidOrderedSequence {
+                expected,
+                ordered_sn
+            }
+        );
+        self.store_last_ordered_sn(deps.storage, &from, &to, ordered_sn)?;
+
+        let res = Response::new()
+            .add_attribute("action", "call_service")
+            .add_attribute("method", "skip_ordered_sequence")
+            .add_event(event_ordered_sequence_skipped(
+                from.to_string(),
+                to.to_string(),
+                ordered_sn,
+            ));
+        self.release_buffered_requests(deps, &env, &from, &to, MAX_ORDERED_RELEASES, res)
+    }
+
+    /// Releases the buffered requests that directly follow the last delivered position of the
+    /// ordered channel from `from` to the dapp `to`, for the ones a previous transaction left
+    /// buffered. Callable by anyone.
+    ///
+    /// Arguments:
+    ///
+    /// * `limit`: Maximum number of requests released, defaults to `DEFAULT_PAGE_LIMIT` and is
+    /// capped at `MAX_PAGE_LIMIT`.
+    pub fn release_buffered(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        from: NetworkAddress,
+        to: String,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        let to = deps.api.addr_validate(&to)?;
+        let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+
+        let res = Response::new()
+            .add_attribute("action", "call_service")
+            .add_attribute("method", "release_buffered");
+        self.release_buffered_requests(deps, &env, &from, &to, limit, res)
+    }
+
+    /// Delivers up to `limit` buffered requests that directly follow the last delivered position
+    /// of the channel, adding their messages and events to `res`.
+    fn release_buffered_requests(
+        &self,
+        mut deps: DepsMut,
+        env: &Env,
+        from: &NetworkAddress,
+        to: &Addr,
+        limit: usize,
+        mut res: Response,
+    ) -> Result<Response, ContractError> {
+        let mut next = self.get_last_ordered_sn(deps.storage, from, to) + 1;
+        for _ in 0..limit {
+            let buffered = match self.get_buffered_request(deps.storage, from, to, next) {
+                Some(buffered) => buffered,
+                None => break,
+            };
+            self.remove_buffered_request(deps.storage, from, to, next);
+            self.store_last_ordered_sn(deps.storage, from, to, next)?;
+            let released =
+                self.deliver_request(deps.branch(), env, &buffered.connection, buffered.request)?;
+            res = res
+                .add_submessages(released.messages)
+                .add_events(released.events);
+            next += 1;
+        }
+        Ok(res)
+    }
+
+    /// Returns the next expected position of the ordered channel from `from` to the dapp `to` and
+    /// the requests buffered ahead of it, ordered by position.
+    ///
+    /// Arguments:
+    ///
+    /// * `start_after`: The last position of the previous page, if any.
+    /// * `limit`: Page size, defaults to `DEFAULT_PAGE_LIMIT` and is capped at `MAX_PAGE_LIMIT`.
+    pub fn query_ordered_channel(
+        &self,
+        deps: Deps,
+        from: NetworkAddress,
+        to: String,
+        start_after: Option<u128>,
+        limit: Option<u32>,
+    ) -> StdResult<OrderedChannelResponse> {
+        let to = deps.api.addr_validate(&to)?;
+        let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+        let buffered = self
+            .get_buffered_requests(deps.storage, &from, &to, start_after, limit)
+            .map_err(|e| StdError::generic_err(e.to_string()))?
+            .into_iter()
+            .map(|(ordered_sn, buffered)| BufferedRequestResponse {
+                ordered_sn,
+                sn: buffered.request.sequence_no(),
+                msg_type: buffered.request.msg_type(),
+                data: buffered
+                    .request
+                    .data()
+                    .map(|d| d.to_vec())
+                    .unwrap_or_default(),
+                protocols: buffered.request.protocols().clone(),
+            })
+            .collect();
+
+        Ok(OrderedChannelResponse {
+            next_sn: self.get_last_ordered_sn(deps.storage, &from, &to) + 1,
+            buffered,
+        })
+    }
+}
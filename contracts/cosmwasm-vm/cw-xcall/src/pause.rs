@@ -0,0 +1,261 @@
+use cw_xcall_lib::{network_address::NetId, xcall_query_msg::PauseStateResponse};
+
+use crate::{
+    events::{
+        event_global_pause_updated, event_inbound_resumed, event_message_queued,
+        event_network_pause_updated, event_queued_message_dropped,
+    },
+    state::{DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT},
+    types::pause::{NetworkPause, PauseDirection, PausedMessage},
+};
+
+use super::*;
+
+impl<'a> CwCallService<'a> {
+    /// Makes `address` the only pauser next to the admin, revoking the pauser role from its
+    /// current members. Use `GrantRole` to have several pausers.
+    pub fn set_pauser(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender)?;
+        let pauser = CwCallService::validate_address(deps.api, address.as_str())?;
+        for member in self.get_role_members(deps.storage, &Role::Pauser)? {
+            self.remove_role(deps.storage, &Role::Pauser, &Addr::unchecked(member));
+        }
+        self.store_role(deps.storage, &Role::Pauser, &pauser)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_pauser")
+            .add_attribute("pauser", pauser.to_string()))
+    }
+
+    /// Updates the pause switches of a single remote network.
+    ///
+    /// Arguments:
+    ///
+    /// * `nid`: The network whose traffic is paused or unpaused.
+    /// * `pause`: The new switches. `outbound` blocks `SendCall` towards `nid`, `inbound` queues
+    /// messages delivered from `nid` and `execution` blocks `ExecuteCall` for requests from `nid`.
+    pub fn set_network_pause(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        nid: NetId,
+        pause: NetworkPause,
+    ) -> Result<Response, ContractError> {
+        self.ensure_pause_authority(deps.storage, &info.sender)?;
+        self.store_network_pause(deps.storage, nid.clone(), &pause)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_network_pause")
+            .add_event(event_network_pause_updated(&nid, &pause)))
+    }
+
+    /// Pauses or unpauses all traffic regardless of the per network switches.
+    pub fn set_global_pause(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        paused: bool,
+    ) -> Result<Response, ContractError> {
+        self.ensure_pause_authority(deps.storage, &info.sender)?;
+        self.store_global_pause(deps.storage, paused)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_global_pause")
+            .add_event(event_global_pause_updated(paused)))
+    }
+
+    /// Replays messages that were queued while inbound traffic from `nid` was paused, oldest first.
+    ///
+    /// Anyone can call this once the network is unpaused. The replay stops at the first message
+    /// that no longer passes `handle_message` and reverts, leaving the queue untouched. The error
+    /// names the failing message so that a pauser can discard it with `DropQueuedMessage`.
+    ///
+    /// Arguments:
+    ///
+    /// * `nid`: The network whose queued messages are replayed.
+    /// * `limit`: Maximum number of messages to replay, defaults to `DEFAULT_PAGE_LIMIT` and is
+    /// capped at `MAX_PAGE_LIMIT`.
+    pub fn resume_inbound(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        nid: NetId,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        self.ensure_not_paused(deps.storage, &nid, PauseDirection::Inbound)?;
+
+        let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+        let queued = self.get_paused_messages(deps.storage, &nid, limit)?;
+        let mut response = Response::new()
+            .add_attribute("action", "call_service")
+            .add_attribute("method", "resume_inbound");
+
+        for (id, message) in queued.iter() {
+            let info = MessageInfo {
+                sender: message.connection.clone(),
+                funds: vec![],
+            };
+            let res = self
+                .handle_message(
+                    deps.branch(),
+                    env.clone(),
+                    info,
+                    message.from_nid.clone(),
+                    message.msg.clone(),
+                )
+                .map_err(|error| ContractError::QueuedMessageFailed {
+                    id: *id,
+                    error: error.to_string(),
+                })?;
+            self.remove_paused_message(deps.storage, &nid, *id)?;
+            response = response
+                .add_submessages(res.messages)
+                .add_events(res.events);
+        }
+
+        Ok(response.add_event(event_inbound_resumed(&nid, queued.len())))
+    }
+
+    /// Discards a queued message that can no longer be replayed, so that it stops blocking
+    /// `ResumeInbound` for the rest of the queue.
+    pub fn drop_queued_message(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        nid: NetId,
+        id: u128,
+    ) -> Result<Response, ContractError> {
+        self.ensure_pause_authority(deps.storage, &info.sender)?;
+        ensure!(
+            self.has_paused_message(deps.storage, &nid, id),
+            ContractError::QueuedMessageNotFound { id }
+        );
+        self.remove_paused_message(deps.storage, &nid, id)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "drop_queued_message")
+            .add_event(event_queued_message_dropped(id, &nid, &info.sender)))
+    }
+
+    /// Stores a message delivered while inbound traffic from `from_nid` is paused so that it can be
+    /// replayed with `ResumeInbound` later. Only messages that decode and come from a connection
+    /// allowed to deliver them are queued.
+    pub fn queue_paused_message(
+        &self,
+        store: &mut dyn Storage,
+        info: MessageInfo,
+        from_nid: NetId,
+        msg: Vec<u8>,
+    ) -> Result<Response, ContractError> {
+        self.ensure_queueable_message(store, &from_nid, &info.sender, &msg)?;
+        let id = self.save_paused_message(
+            store,
+            &PausedMessage {
+                from_nid: from_nid.clone(),
+                connection: info.sender,
+                msg,
+            },
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "call_service")
+            .add_attribute("method", "queue_paused_message")
+            .add_event(event_message_queued(id, &from_nid)))
+    }
+
+    /// Runs the checks `handle_message` makes on the sender of a message, so that a connection
+    /// that could not deliver it cannot fill the queue either.
+    fn ensure_queueable_message(
+        &self,
+        store: &dyn Storage,
+        from_nid: &NetId,
+        sender: &Addr,
+        msg: &[u8],
+    ) -> Result<(), ContractError> {
+        let message = CSMessage::try_from(msg.to_vec())?;
+        let source = sender.to_string();
+        let source_valid = match message.message_type() {
+            CSMessageType::CSMessageRequest | CSMessageType::CSMessageRequestV2 => {
+                let request = CSMessageRequest::try_from(message.payload())?;
+                ensure!(
+                    request.from().nid() == *from_nid,
+                    ContractError::ProtocolsMismatch
+                );
+                self.is_valid_source(store, from_nid.clone(), &source, request.protocols())?
+            }
+            CSMessageType::CSMessageResult => {
+                let result = CSMessageResult::try_from(message.payload())?;
+                let sn = result.sequence_no();
+                let call_request = self
+                    .get_call_request(store, sn)
+                    .map_err(|_| ContractError::CallRequestNotFound { sn })?;
+                self.is_valid_source(
+                    store,
+                    call_request.to().nid(),
+                    &source,
+                    call_request.protocols(),
+                )?
+            }
+        };
+        ensure!(source_valid, ContractError::ProtocolsMismatch);
+        Ok(())
+    }
+
+    pub fn is_paused(&self, store: &dyn Storage, nid: &NetId, direction: PauseDirection) -> bool {
+        self.is_globally_paused(store)
+            || self
+                .get_network_pause(store, nid.clone())
+                .is_paused(&direction)
+    }
+
+    pub fn ensure_not_paused(
+        &self,
+        store: &dyn Storage,
+        nid: &NetId,
+        direction: PauseDirection,
+    ) -> Result<(), ContractError> {
+        ensure!(
+            !self.is_globally_paused(store),
+            ContractError::ContractPaused
+        );
+        ensure!(
+            !self
+                .get_network_pause(store, nid.clone())
+                .is_paused(&direction),
+            ContractError::NetworkPaused {
+                nid: nid.to_string()
+            }
+        );
+        Ok(())
+    }
+
+    fn ensure_pause_authority(
+        &self,
+        store: &dyn Storage,
+        sender: &Addr,
+    ) -> Result<(), ContractError> {
+        self.ensure_role(store, Role::Pauser, sender)
+    }
+
+    pub fn query_pause_state(
+        &self,
+        store: &dyn Storage,
+        nid: NetId,
+    ) -> StdResult<PauseStateResponse> {
+        let pause = self.get_network_pause(store, nid.clone());
+        let queued_messages = self.get_paused_message_count(store, &nid);
+
+        Ok(PauseStateResponse {
+            global: self.is_globally_paused(store),
+            outbound: pause.outbound,
+            inbound: pause.inbound,
+            execution: pause.execution,
+            queued_messages,
+        })
+    }
+}
@@ -0,0 +1,58 @@
+use cw_xcall_lib::{network_address::NetId, xcall_query_msg::PayloadLimits};
+
+use crate::state::PAYLOAD_LIMIT_CEILING;
+
+use super::*;
+
+impl<'a> CwCallService<'a> {
+    /// Sets the payload limits applied to outgoing messages.
+    ///
+    /// Arguments:
+    ///
+    /// * `nid`: The destination network to override, or `None` to update the default limits used by
+    /// every network without an override.
+    /// * `limits`: The maximum encoded message size and rollback size in bytes. Both must be non-zero
+    /// and at most `PAYLOAD_LIMIT_CEILING`.
+    pub fn set_payload_limits(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        nid: Option<NetId>,
+        limits: PayloadLimits,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender)?;
+        let valid = |limit: u64| limit > 0 && limit <= PAYLOAD_LIMIT_CEILING;
+        ensure!(
+            valid(limits.max_data_size) && valid(limits.max_rollback_size),
+            ContractError::InvalidPayloadLimits
+        );
+        let mut res = Response::new()
+            .add_attribute("method", "set_payload_limits")
+            .add_attribute("max_data_size", limits.max_data_size.to_string())
+            .add_attribute("max_rollback_size", limits.max_rollback_size.to_string());
+        match nid {
+            Some(nid) => {
+                res = res.add_attribute("nid", nid.to_string());
+                self.store_network_payload_limits(deps.storage, nid, &limits)?;
+            }
+            None => self.store_default_payload_limits(deps.storage, &limits)?,
+        }
+
+        Ok(res)
+    }
+
+    /// Removes the override of `nid` so that the default limits apply to it again.
+    pub fn remove_payload_limits(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        nid: NetId,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender)?;
+        self.remove_network_payload_limits(deps.storage, nid.clone());
+
+        Ok(Response::new()
+            .add_attribute("method", "remove_payload_limits")
+            .add_attribute("nid", nid.to_string()))
+    }
+}
@@ -0,0 +1,156 @@
+use cw_xcall_lib::xcall_query_msg::{PendingDeliveriesResponse, PendingDeliveryResponse};
+
+use crate::{
+    events::event_pending_delivery_purged,
+    state::{DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT},
+    types::pending::DeliveryKind,
+};
+
+use super::*;
+
+impl<'a> CwCallService<'a> {
+    /// Sets how long, in seconds, a message sent over several protocols may wait for its remaining
+    /// deliveries before anyone can purge it with `PurgeExpiredDeliveries`. `None` disables the
+    /// expiry, leaving only the admin purge.
+    pub fn set_pending_delivery_ttl(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        ttl: Option<u64>,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender)?;
+        self.store_pending_delivery_ttl(deps.storage, ttl)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "set_pending_delivery_ttl")
+            .add_attribute("ttl", ttl.map_or("none".to_string(), |ttl| ttl.to_string())))
+    }
+
+    /// Removes the pending requests and responses whose first delivery is older than the
+    /// configured ttl, requests first. Callable by anyone.
+    ///
+    /// Messages already accepted are kept until their remaining protocols deliver, since removing
+    /// them would let a late delivery accept and execute them again.
+    ///
+    /// Arguments:
+    ///
+    /// * `limit`: Maximum number of messages removed, defaults to `DEFAULT_PAGE_LIMIT` and is
+    /// capped at `MAX_PAGE_LIMIT`.
+    pub fn purge_expired_deliveries(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        let ttl = self
+            .get_pending_delivery_ttl(deps.storage)
+            .ok_or(ContractError::PendingDeliveryTtlNotSet)?;
+        let mut remaining = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+
+        let mut res = Response::new()
+            .add_attribute("action", "call_service")
+            .add_attribute("method", "purge_expired_deliveries");
+        for kind in [DeliveryKind::Request, DeliveryKind::Response] {
+            let expired = self.get_expired_pending_deliveries(
+                deps.storage,
+                kind,
+                env.block.time,
+                ttl,
+                remaining,
+            )?;
+            remaining -= expired.len();
+            for hash in expired {
+                res = res.add_event(self.purge_pending_delivery(deps.storage, kind, hash)?);
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Removes the pending request and response hashed to `hash` regardless of their age.
+    /// Messages already accepted can not be removed, for the same reason as in
+    /// `purge_expired_deliveries`.
+    pub fn purge_pending_delivery_by_admin(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        hash: Vec<u8>,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender)?;
+
+        let mut res = Response::new()
+            .add_attribute("action", "call_service")
+            .add_attribute("method", "purge_pending_delivery");
+        let kinds = [DeliveryKind::Request, DeliveryKind::Response]
+            .into_iter()
+            .filter_map(|kind| {
+                self.get_pending_delivery(deps.storage, kind, &hash)
+                    .map(|pending| (kind, pending))
+            })
+            .collect::<Vec<_>>();
+        ensure!(
+            kinds.iter().all(|(_, pending)| !pending.accepted),
+            ContractError::PendingDeliveryAccepted
+        );
+        for (kind, _) in kinds {
+            let event = self.purge_pending_delivery(deps.storage, kind, hash.clone())?;
+            res = res.add_event(event);
+        }
+
+        Ok(res)
+    }
+
+    fn purge_pending_delivery(
+        &self,
+        store: &mut dyn Storage,
+        kind: DeliveryKind,
+        hash: Vec<u8>,
+    ) -> Result<Event, ContractError> {
+        let delivered = self.get_delivered_protocols(store, kind, hash.clone())?;
+        self.remove_pending_delivery(store, kind, hash.clone())?;
+        Ok(event_pending_delivery_purged(kind, &hash, &delivered))
+    }
+
+    /// Lists the messages of `kind` that some of their protocols have not delivered yet, ordered by
+    /// hash.
+    ///
+    /// Arguments:
+    ///
+    /// * `store`: The contract storage.
+    /// * `kind`: Whether to list requests received from or responses to messages sent to other
+    /// chains.
+    /// * `start_after`: The last hash of the previous page, if any.
+    /// * `limit`: Page size, defaults to `DEFAULT_PAGE_LIMIT` and is capped at `MAX_PAGE_LIMIT`.
+    pub fn query_pending_deliveries(
+        &self,
+        store: &dyn Storage,
+        kind: DeliveryKind,
+        start_after: Option<Vec<u8>>,
+        limit: Option<u32>,
+    ) -> StdResult<PendingDeliveriesResponse> {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+        let deliveries = self
+            .get_pending_deliveries(store, kind, start_after, limit)?
+            .into_iter()
+            .map(|(hash, pending)| {
+                let delivered = self
+                    .get_delivered_protocols(store, kind, hash.clone())
+                    .map_err(|e| StdError::generic_err(e.to_string()))?;
+                let missing = pending
+                    .protocols
+                    .into_iter()
+                    .filter(|protocol| !delivered.contains(protocol))
+                    .collect();
+                Ok(PendingDeliveryResponse {
+                    hash,
+                    delivered,
+                    missing,
+                    received_at: pending.received_at.seconds(),
+                    accepted: pending.accepted,
+                })
+            })
+            .collect::<StdResult<Vec<PendingDeliveryResponse>>>()?;
+
+        Ok(PendingDeliveriesResponse { deliveries })
+    }
+}
@@ -0,0 +1,245 @@
+use cosmwasm_std::{Order, StdError, StdResult, Storage};
+use cw_storage_plus::Bound;
+use cw_xcall_lib::{
+    network_address::NetId,
+    xcall_query_msg::{
+        DecodedCsMessage, DecodedMessageType, DecodedRequest, DecodedResult, MessageStatus,
+        ProxyRequestResponse, ProxyRequestsResponse, RollbackResponse, RollbacksResponse,
+    },
+};
+
+use crate::{
+    error::ContractError,
+    state::{CwCallService, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT},
+    types::{
+        message::{CSMessage, CSMessageType},
+        request::CSMessageRequest,
+        result::CSMessageResult,
+        rollback::Rollback,
+    },
+};
+
+impl<'a> CwCallService<'a> {
+    /// Lists the two-way messages sent from this chain that have not been resolved yet, ordered by
+    /// sequence number.
+    ///
+    /// Arguments:
+    ///
+    /// * `store`: The contract storage.
+    /// * `start_after`: The last sequence number of the previous page, if any.
+    /// * `limit`: Page size, defaults to `DEFAULT_PAGE_LIMIT` and is capped at `MAX_PAGE_LIMIT`.
+    /// * `dapp`: Only return messages sent by this dapp address.
+    /// * `nid`: Only return messages sent to this network.
+    pub fn query_pending_rollbacks(
+        &self,
+        store: &dyn Storage,
+        start_after: Option<u128>,
+        limit: Option<u32>,
+        dapp: Option<String>,
+        nid: Option<NetId>,
+    ) -> StdResult<RollbacksResponse> {
+        let rollbacks = self
+            .call_requests()
+            .range(
+                store,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .filter(|item| match item {
+                Ok((_, rollback)) => {
+                    dapp.as_ref()
+                        .map_or(true, |d| rollback.from().as_str() == d)
+                        && nid.as_ref().map_or(true, |n| &rollback.to().nid() == n)
+                }
+                Err(_) => true,
+            })
+            .take(page_limit(limit))
+            .map(|item| item.map(|(sn, rollback)| to_rollback_response(sn, rollback)))
+            .collect::<StdResult<Vec<RollbackResponse>>>()?;
+
+        Ok(RollbacksResponse { rollbacks })
+    }
+
+    pub fn query_rollback(&self, store: &dyn Storage, sn: u128) -> StdResult<RollbackResponse> {
+        let rollback = self.call_requests().load(store, sn)?;
+        Ok(to_rollback_response(sn, rollback))
+    }
+
+    /// Lists the requests received from other chains that are waiting for `ExecuteCall`, ordered by
+    /// request id.
+    ///
+    /// Arguments:
+    ///
+    /// * `store`: The contract storage.
+    /// * `start_after`: The last request id of the previous page, if any.
+    /// * `limit`: Page size, defaults to `DEFAULT_PAGE_LIMIT` and is capped at `MAX_PAGE_LIMIT`.
+    /// * `dapp`: Only return requests addressed to this dapp address.
+    /// * `nid`: Only return requests coming from this network.
+    pub fn query_proxy_requests(
+        &self,
+        store: &dyn Storage,
+        start_after: Option<u128>,
+        limit: Option<u32>,
+        dapp: Option<String>,
+        nid: Option<NetId>,
+    ) -> StdResult<ProxyRequestsResponse> {
+        let requests = self
+            .proxy_requests()
+            .range(
+                store,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .filter(|item| match item {
+                Ok((_, request)) => {
+                    dapp.as_ref().map_or(true, |d| request.to().as_str() == d)
+                        && nid.as_ref().map_or(true, |n| &request.from().nid() == n)
+                }
+                Err(_) => true,
+            })
+            .take(page_limit(limit))
+            .map(|item| item.map(|(req_id, request)| to_proxy_request_response(req_id, request)))
+            .collect::<StdResult<Vec<ProxyRequestResponse>>>()?;
+
+        Ok(ProxyRequestsResponse { requests })
+    }
+
+    pub fn query_proxy_request(
+        &self,
+        store: &dyn Storage,
+        req_id: u128,
+    ) -> StdResult<ProxyRequestResponse> {
+        let request = self.proxy_requests().load(store, req_id)?;
+        Ok(to_proxy_request_response(req_id, request))
+    }
+
+    /// Returns the lifecycle status of a message sent from this chain.
+    ///
+    /// Two-way messages have their status recorded on every transition. One-way messages keep no
+    /// entry, so any other sequence number that has been handed out is reported as `Sent`.
+    /// Messages sent before status tracking was introduced fall back to the rollback and
+    /// successful response state.
+    pub fn query_message_status(&self, store: &dyn Storage, sn: u128) -> StdResult<MessageStatus> {
+        if let Some(status) = self.get_message_status(store, sn) {
+            return Ok(status);
+        }
+        if let Ok(rollback) = self.call_requests().load(store, sn) {
+            return match rollback.enabled() {
+                true => Ok(MessageStatus::RollbackEnabled),
+                false => Ok(MessageStatus::AwaitingResponse),
+            };
+        }
+        if self.get_successful_response(store, sn) {
+            return Ok(MessageStatus::Succeeded);
+        }
+        let current_sn = self.sn().load(store).unwrap_or(0);
+        if sn == 0 || sn > current_sn {
+            return Err(StdError::not_found(format!("MessageStatus {sn}")));
+        }
+        Ok(MessageStatus::Sent)
+    }
+
+    /// Decodes the RLP encoding of a `CSMessage`, as relayed between xcall contracts, into its
+    /// request or result fields. Errors name the part of the message that failed to decode.
+    pub fn decode_cs_message(&self, msg: &[u8]) -> Result<DecodedCsMessage, ContractError> {
+        let message = CSMessage::try_from(msg.to_vec()).map_err(|e| decode_error("message", e))?;
+        match message.message_type() {
+            CSMessageType::CSMessageRequest | CSMessageType::CSMessageRequestV2 => {
+                let request = CSMessageRequest::try_from(message.payload())
+                    .map_err(|e| decode_error("request", e))?;
+                let message_type = match message.message_type() {
+                    CSMessageType::CSMessageRequestV2 => DecodedMessageType::RequestV2,
+                    _ => DecodedMessageType::Request,
+                };
+                Ok(DecodedCsMessage {
+                    message_type,
+                    request: Some(to_decoded_request(request)),
+                    result: None,
+                })
+            }
+            CSMessageType::CSMessageResult => {
+                let result = CSMessageResult::try_from(message.payload())
+                    .map_err(|e| decode_error("result", e))?;
+                let reply = match result.message().is_empty() {
+                    true => None,
+                    false => Some(
+                        CSMessageRequest::try_from(result.message())
+                            .map_err(|e| decode_error("reply", e))?,
+                    ),
+                };
+                let additional_replies = result
+                    .additional_replies()
+                    .iter()
+                    .map(|reply| {
+                        CSMessageRequest::try_from(reply)
+                            .map(to_decoded_request)
+                            .map_err(|e| decode_error("reply", e))
+                    })
+                    .collect::<Result<Vec<DecodedRequest>, ContractError>>()?;
+                Ok(DecodedCsMessage {
+                    message_type: DecodedMessageType::Result,
+                    request: None,
+                    result: Some(DecodedResult {
+                        sn: result.sequence_no(),
+                        code: result.response_code().clone().into(),
+                        reply: reply.map(to_decoded_request),
+                        additional_replies,
+                    }),
+                })
+            }
+        }
+    }
+}
+
+fn decode_error(part: &str, error: ContractError) -> ContractError {
+    match error {
+        ContractError::DecodeFailed { error } => ContractError::DecodeFailed {
+            error: format!("{part}: {error}"),
+        },
+        error => error,
+    }
+}
+
+fn to_decoded_request(request: CSMessageRequest) -> DecodedRequest {
+    DecodedRequest {
+        from: request.from().clone(),
+        to: request.to().to_string(),
+        sn: request.sequence_no(),
+        msg_type: request.msg_type(),
+        data: request.data().map(|d| d.to_vec()).unwrap_or_default(),
+        protocols: request.protocols().clone(),
+        expiry: request.expiry().clone(),
+        gas_limit: request.gas_limit(),
+        threshold: request.threshold(),
+        ordered_sn: request.ordered_sn(),
+    }
+}
+
+fn page_limit(limit: Option<u32>) -> usize {
+    limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize
+}
+
+fn to_rollback_response(sn: u128, rollback: Rollback) -> RollbackResponse {
+    RollbackResponse {
+        sn,
+        from: rollback.from().clone(),
+        to: rollback.to().clone(),
+        protocols: rollback.protocols().clone(),
+        rollback: rollback.rollback().to_vec(),
+        enabled: rollback.enabled(),
+    }
+}
+
+fn to_proxy_request_response(req_id: u128, request: CSMessageRequest) -> ProxyRequestResponse {
+    ProxyRequestResponse {
+        req_id,
+        from: request.from().clone(),
+        to: request.to().clone(),
+        sn: request.sequence_no(),
+        msg_type: request.msg_type(),
+        data_hash: request.data().map(|d| d.to_vec()).unwrap_or_default(),
+        protocols: request.protocols().clone(),
+    }
+}
@@ -0,0 +1,112 @@
+use super::*;
+
+impl<'a> CwCallService<'a> {
+    /// Ensures `address` can act with `role`, either as a member of the role or as the admin.
+    pub fn ensure_role(
+        &self,
+        store: &dyn Storage,
+        role: Role,
+        address: &Addr,
+    ) -> Result<(), ContractError> {
+        if self.has_role(store, &role, address) {
+            return Ok(());
+        }
+        self.ensure_admin(store, address.clone())
+            .map_err(|_| ContractError::MissingRole {
+                role: role.as_str().to_string(),
+            })
+    }
+
+    /// Whether `address` can act with `role`, either as a member of the role or as the admin.
+    pub fn can_act_as(&self, store: &dyn Storage, role: Role, address: &Addr) -> bool {
+        self.ensure_role(store, role, address).is_ok()
+    }
+
+    /// Adds `address` to the members of `role`. Only the admin can grant roles.
+    pub fn grant_role(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        role: Role,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender)?;
+        let address = CwCallService::validate_address(deps.api, address.as_str())?;
+        self.store_role(deps.storage, &role, &address)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "grant_role")
+            .add_attribute("role", role.as_str())
+            .add_attribute("address", address.to_string()))
+    }
+
+    /// Removes `address` from the members of `role`. Only the admin can revoke roles.
+    pub fn revoke_role(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        role: Role,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        self.ensure_admin(deps.storage, info.sender)?;
+        let address = deps.api.addr_validate(&address)?;
+        self.remove_role(deps.storage, &role, &address);
+
+        Ok(Response::new()
+            .add_attribute("method", "revoke_role")
+            .add_attribute("role", role.as_str())
+            .add_attribute("address", address.to_string()))
+    }
+
+    /// Removes the sender from the members of `role`.
+    pub fn renounce_role(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        role: Role,
+    ) -> Result<Response, ContractError> {
+        ensure!(
+            self.has_role(deps.storage, &role, &info.sender),
+            ContractError::MissingRole {
+                role: role.as_str().to_string()
+            }
+        );
+        self.remove_role(deps.storage, &role, &info.sender);
+
+        Ok(Response::new()
+            .add_attribute("method", "renounce_role")
+            .add_attribute("role", role.as_str())
+            .add_attribute("address", info.sender.to_string()))
+    }
+
+    /// Proposes `address` as the next admin, replacing any previous proposal. The current admin
+    /// stays in charge until `address` accepts.
+    pub fn propose_admin(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        let address = CwCallService::validate_address(deps.api, address.as_str())?;
+        self.ensure_admin(deps.storage, info.sender)?;
+        self.store_pending_admin(deps.storage, &address)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "propose_admin")
+            .add_attribute("pending_admin", address.to_string()))
+    }
+
+    /// Makes the proposed admin, which must be the sender, the admin.
+    pub fn accept_admin(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        ensure!(
+            self.get_pending_admin(deps.storage).as_ref() == Some(&info.sender),
+            ContractError::OnlyPendingAdmin
+        );
+        self.remove_pending_admin(deps.storage);
+        self.set_admin(deps.storage, info.sender)
+    }
+}
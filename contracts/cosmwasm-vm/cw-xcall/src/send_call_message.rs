@@ -1,12 +1,19 @@
-use cosmwasm_std::{coins, BankMsg};
+use cosmwasm_std::{coins, BankMsg, Decimal};
 use cw_xcall_lib::message::call_message::CallMessage;
 use cw_xcall_lib::message::msg_trait::IMessage;
 
+use cw_xcall_lib::message::expiry::MessageExpiry;
 use cw_xcall_lib::message::AnyMessage;
 use cw_xcall_lib::message::{call_message_rollback::CallMessageWithRollback, envelope::Envelope};
 use cw_xcall_lib::network_address::{NetId, NetworkAddress};
+use cw_xcall_lib::xcall_query_msg::MessageStatus;
 
-use crate::{assertion::is_contract, types::LOG_PREFIX};
+use crate::{
+    assertion::is_contract,
+    fees::convert_fee,
+    state::MAX_CALL_REPLIES,
+    types::{pause::PauseDirection, LOG_PREFIX},
+};
 
 use super::*;
 
@@ -15,12 +22,13 @@ impl<'a> CwCallService<'a> {
         &self,
         deps: DepsMut,
         info: MessageInfo,
-        _env: Env,
+        env: Env,
         to: NetworkAddress,
         data: Vec<u8>,
         rollback: Option<Vec<u8>>,
         sources: Vec<String>,
         destinations: Vec<String>,
+        max_fee: Option<u128>,
     ) -> Result<Response, ContractError> {
         let msg = if rollback.is_some() {
             AnyMessage::CallMessageWithRollback(CallMessageWithRollback {
@@ -31,22 +39,24 @@ impl<'a> CwCallService<'a> {
             AnyMessage::CallMessage(CallMessage { data })
         };
         let envelope = Envelope::new(msg, sources, destinations);
-        self.send_call(deps, info, to, envelope)
+        self.send_call(deps, info, env, to, envelope, max_fee)
     }
 
     pub fn validate_payload(
         &self,
         deps: Deps,
         caller: &Addr,
+        to: &NetId,
         envelope: &Envelope,
     ) -> Result<(), ContractError> {
+        self.ensure_data_length(deps.storage, to, envelope.message.data().len())?;
         match &envelope.message {
             AnyMessage::CallMessage(_m) => Ok(()),
             AnyMessage::CallMessageWithRollback(m) => {
                 if !is_contract(deps.querier, caller) {
                     return Err(ContractError::RollbackNotPossible);
                 }
-                self.ensure_rollback_length(&m.rollback().unwrap())?;
+                self.ensure_rollback_length(deps.storage, to, &m.rollback().unwrap())?;
                 Ok(())
             }
             AnyMessage::CallMessagePersisted(_) => Ok(()),
@@ -57,13 +67,22 @@ impl<'a> CwCallService<'a> {
         &self,
         deps: DepsMut,
         info: MessageInfo,
+        env: Env,
         to: NetworkAddress,
         envelope: Envelope,
+        max_fee: Option<u128>,
     ) -> Result<Response, ContractError> {
         let caller = info.sender.clone();
         let config = self.get_config(deps.as_ref().storage)?;
         let nid = config.network_id;
-        self.validate_payload(deps.as_ref(), &caller, &envelope)?;
+        self.ensure_not_paused(deps.storage, &to.nid(), PauseDirection::Outbound)?;
+        // a reply travels back over the protocols of the request being executed, so it is matched
+        // against the sources named by the dapp
+        let requested_sources = envelope.sources.clone();
+        let envelope = self.with_dapp_connections(deps.storage, &caller, &to.nid(), envelope);
+        self.validate_payload(deps.as_ref(), &caller, &to.nid(), &envelope)?;
+        self.ensure_valid_threshold(&envelope)?;
+        self.ensure_extended_messages_supported(deps.storage, &to.nid(), &envelope)?;
 
         let sequence_no = self.get_next_sn(deps.storage)?;
 
@@ -77,9 +96,13 @@ impl<'a> CwCallService<'a> {
                 envelope.sources.clone(),
                 rollback_data,
                 false,
-            );
+            )
+            .with_result_callback(envelope.result_callback)
+            .with_threshold(envelope.threshold)
+            .with_sent_at(Some(env.block.time));
 
             self.store_call_request(deps.storage, sequence_no, &request)?;
+            self.store_message_status(deps.storage, sequence_no, MessageStatus::AwaitingResponse)?;
         }
         let call_request = CSMessageRequest::new(
             from,
@@ -88,66 +111,136 @@ impl<'a> CwCallService<'a> {
             envelope.message.msg_type().clone(),
             envelope.message.data(),
             envelope.destinations,
-        );
+        )
+        .with_sent_height(sent_height(&envelope.expiry, &env))
+        .with_expiry(envelope.expiry)
+        .with_gas_limit(envelope.gas_limit)
+        .with_threshold(envelope.threshold);
         let need_response = call_request.need_response();
 
-        let event = event_xcall_message_sent(caller.to_string(), to.to_string(), sequence_no);
         // if contract is in reply state
         if envelope.message.rollback().is_none()
-            && self.is_reply(deps.as_ref(), to.nid(), &envelope.sources)
+            && self.is_reply(deps.as_ref(), to.nid(), &requested_sources)
         {
-            self.save_call_reply(deps.storage, &call_request)?;
+            self.push_call_reply(deps.storage, &call_request)?;
+            let event = event_xcall_message_sent(
+                caller.to_string(),
+                to.to_string(),
+                sequence_no,
+                &requested_sources,
+            );
             let res = self.send_call_response(event, sequence_no);
             return Ok(res);
         }
 
+        let ordered_sn = match envelope.ordered {
+            true => Some(self.get_next_ordered_sn(deps.storage, &caller, &to)?),
+            false => None,
+        };
+        let call_request = call_request.with_ordered_sn(ordered_sn);
+
         let mut confirmed_sources = envelope.sources;
         if confirmed_sources.is_empty() {
             let default = self.get_default_connection(deps.as_ref().storage, to.nid())?;
             confirmed_sources = vec![default.to_string()]
         }
+        let event = event_xcall_message_sent(
+            caller.to_string(),
+            to.to_string(),
+            sequence_no,
+            &confirmed_sources,
+        );
         let message: CSMessage = call_request.into();
         let sn: i64 = if need_response { sequence_no as i64 } else { 0 };
-        let mut total_spent = 0_u128;
+        let (paid_denom, paid) = self.get_total_paid(deps.as_ref(), &info.funds)?;
+        let connection_fees = confirmed_sources
+            .iter()
+            .map(|r| self.query_connection_fee(deps.as_ref(), to.nid(), need_response, r))
+            .collect::<Result<Vec<u128>, ContractError>>()?;
+        let protocol_fee = self.get_protocol_fee(deps.as_ref().storage);
+        let total_fee = |rate: Decimal| {
+            connection_fees
+                .iter()
+                .chain([protocol_fee].iter())
+                .try_fold(0_u128, |total, fee| {
+                    total
+                        .checked_add(convert_fee(*fee, rate)?)
+                        .ok_or(ContractError::InsufficientFunds)
+                })
+        };
+        let (fee_denom, drawn) =
+            self.select_fee_denom(deps.storage, &caller, &paid_denom, paid, &total_fee)?;
+        let rate = self.get_fee_denom_rate(deps.storage, &fee_denom)?;
+        let total_fee_required = total_fee(rate)?;
 
         let submessages = confirmed_sources
             .iter()
-            .map(|r| {
-                return self
-                    .query_connection_fee(deps.as_ref(), to.nid(), need_response, r)
-                    .and_then(|fee| {
-                        let fund = if fee > 0 {
-                            total_spent = total_spent.checked_add(fee).unwrap();
-                            coins(fee, config.denom.clone())
-                        } else {
-                            vec![]
-                        };
-                        let address = deps.api.addr_validate(r)?;
-
-                        self.call_connection_send_message(&address, fund, to.nid(), sn, &message)
-                    });
+            .zip(connection_fees.iter())
+            .map(|(r, fee)| {
+                let fee = convert_fee(*fee, rate)?;
+                let fund = if fee > 0 {
+                    coins(fee, fee_denom.clone())
+                } else {
+                    vec![]
+                };
+                let address = deps.api.addr_validate(r)?;
+
+                self.call_connection_send_message(
+                    deps.storage,
+                    &address,
+                    fund,
+                    to.nid(),
+                    sn,
+                    &message,
+                )
             })
             .collect::<Result<Vec<SubMsg>, ContractError>>()?;
 
-        let total_paid = self.get_total_paid(deps.as_ref(), &info.funds)?;
         let fee_handler = self.fee_handler().load(deps.storage)?;
-        let protocol_fee = self.get_protocol_fee(deps.as_ref().storage);
-        let total_fee_required = protocol_fee + total_spent;
+        let protocol_fee = convert_fee(protocol_fee, rate)?;
 
-        if total_paid < total_fee_required {
-            return Err(ContractError::InsufficientFunds);
+        if let Some(max_fee) = max_fee {
+            ensure!(
+                total_fee_required <= max_fee,
+                ContractError::MaxFeeExceeded {
+                    required: total_fee_required,
+                    max_fee
+                }
+            );
+        }
+        if drawn > 0 {
+            self.charge_fee_balance(
+                deps.storage,
+                &caller,
+                &fee_denom,
+                drawn,
+                sequence_no,
+                to.nid(),
+            )?;
         }
-        let remaining = total_paid - total_spent;
+        self.store_sending_sn(deps.storage, sequence_no)?;
+        // attached funds in another denom than the one the fee was drawn in are returned in full
+        let refund = match fee_denom == paid_denom {
+            true => paid + drawn - total_fee_required,
+            false => paid,
+        };
 
         println!("{LOG_PREFIX} Sent Bank Message");
         let mut res = self
             .send_call_response(event, sequence_no)
             .add_submessages(submessages);
 
-        if remaining > 0 {
+        if protocol_fee > 0 {
             let msg = BankMsg::Send {
                 to_address: fee_handler,
-                amount: coins(remaining, config.denom),
+                amount: coins(protocol_fee, fee_denom.clone()),
+            };
+            res = res.add_message(msg);
+        }
+        if refund > 0 {
+            let msg = BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(refund, paid_denom),
             };
             res = res.add_message(msg);
         }
@@ -164,15 +257,25 @@ impl<'a> CwCallService<'a> {
     }
 
     pub fn is_reply(&self, deps: Deps, to: NetId, sources: &Vec<String>) -> bool {
-        if self.get_call_reply(deps.storage).is_some() {
+        if self.get_call_replies(deps.storage).len() >= MAX_CALL_REPLIES {
             return false;
         }
 
         if let Ok(reqid) = self.get_execute_request_id(deps.storage) {
-            let request = self.get_proxy_request(deps.storage, reqid).unwrap();
+            let request = match self.get_proxy_request(deps.storage, reqid) {
+                Ok(request) => request,
+                Err(_) => return false,
+            };
             if request.from().nid() != to {
                 return false;
             }
+            // Results carrying more than one reply can only be decoded by networks with
+            // extended messages enabled, so later messages to other networks are sent as new ones.
+            if !self.get_call_replies(deps.storage).is_empty()
+                && !self.supports_extended_messages(deps.storage, &to)
+            {
+                return false;
+            }
             return self.are_equal(request.protocols(), sources);
         }
         false
@@ -190,3 +293,12 @@ impl<'a> CwCallService<'a> {
         true
     }
 }
+
+/// The block height of this chain carried by a request expiring at a source chain height, so the
+/// destination learns how far this chain has progressed.
+fn sent_height(expiry: &Option<MessageExpiry>, env: &Env) -> Option<u64> {
+    match expiry {
+        Some(MessageExpiry::Height(_)) => Some(env.block.height),
+        _ => None,
+    }
+}
@@ -1,18 +1,48 @@
-use cosmwasm_std::{from_json, to_json_vec, Order};
-use cw_storage_plus::{KeyDeserialize, PrimaryKey};
-use cw_xcall_lib::network_address::NetId;
+use cosmwasm_std::{from_json, to_json_vec, Decimal, Order, Timestamp};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use cw_xcall_lib::{
+    network_address::{NetId, NetworkAddress},
+    xcall_msg::Role,
+    xcall_query_msg::{DappConnections, FeeUsage, InboundPolicy, MessageStatus, PayloadLimits},
+};
 use serde::de::DeserializeOwned;
 
-use crate::types::config::Config;
+use crate::types::{
+    config::Config,
+    ordered::BufferedRequest,
+    pause::{NetworkPause, PausedMessage},
+    pending::{DeliveryKind, PendingDelivery},
+};
 
 use super::*;
 
 /// These are constants defined in the `CwCallService` struct that are used throughout the codebase.
+/// `MAX_DATA_SIZE` and `MAX_ROLLBACK_SIZE` are the payload limits used until the admin configures
+/// others.
 pub const MAX_DATA_SIZE: u64 = 2048;
 pub const MAX_ROLLBACK_SIZE: u64 = 1024;
+/// Upper bound for the payload limits the admin can configure.
+pub const PAYLOAD_LIMIT_CEILING: u64 = 65_536;
 pub const EXECUTE_CALL_ID: u64 = 0;
 pub const EXECUTE_ROLLBACK_ID: u64 = 1;
 pub const SEND_CALL_MESSAGE_REPLY_ID: u64 = 2;
+pub const CALL_RESULT_REPLY_ID: u64 = 3;
+/// Item `i` of an `ExecuteCalls` batch replies on `EXECUTE_CALLS_REPLY_ID + i`, so a batch holds at
+/// most `MAX_EXECUTE_CALLS` items.
+pub const EXECUTE_CALLS_REPLY_ID: u64 = 1 << 32;
+pub const MAX_EXECUTE_CALLS: u64 = 32;
+pub const DEFAULT_PAGE_LIMIT: u32 = 10;
+pub const MAX_PAGE_LIMIT: u32 = 30;
+/// Buffered ordered requests released by a single transaction. The rest are released by the
+/// next delivery on the channel or with `ReleaseBuffered`.
+pub const MAX_ORDERED_RELEASES: usize = 10;
+/// How far ahead of the next expected position of its channel an ordered request can be
+/// buffered.
+pub const MAX_ORDERED_LOOKAHEAD: u128 = 100;
+/// Replies a dapp can send back in the result of a single execution to a network with extended
+/// messages enabled. Other networks get a single reply. Messages sent after that are sent as new
+/// messages.
+pub const MAX_CALL_REPLIES: usize = 10;
 
 pub struct CwCallService<'a> {
     sn: Item<'a, u128>,
@@ -26,9 +56,38 @@ pub struct CwCallService<'a> {
     default_connections: Map<'a, NetId, Addr>,
     pending_requests: Map<'a, (Vec<u8>, String), bool>,
     pending_responses: Map<'a, (Vec<u8>, String), bool>,
+    pending_request_deliveries: Map<'a, Vec<u8>, PendingDelivery>,
+    pending_response_deliveries: Map<'a, Vec<u8>, PendingDelivery>,
+    pending_delivery_ttl: Item<'a, u64>,
+    rollback_grace_period: Item<'a, u64>,
     successful_responses: Map<'a, u128, bool>,
     callback_data: Map<'a, u64, Vec<u8>>,
-    call_reply: Item<'a, CSMessageRequest>,
+    call_replies: Item<'a, Vec<CSMessageRequest>>,
+    message_status: Map<'a, u128, MessageStatus>,
+    global_pause: Item<'a, bool>,
+    network_pause: Map<'a, NetId, NetworkPause>,
+    paused_messages: Map<'a, (String, u128), PausedMessage>,
+    paused_message_id: Item<'a, u128>,
+    paused_message_counts: Map<'a, NetId, u64>,
+    payload_limits: Item<'a, PayloadLimits>,
+    network_payload_limits: Map<'a, NetId, PayloadLimits>,
+    fee_denoms: Map<'a, String, Decimal>,
+    fee_balances: Map<'a, (String, String), u128>,
+    fee_sponsors: Map<'a, (String, String), bool>,
+    fee_usage: Map<'a, (String, u64), FeeUsage>,
+    fee_usage_count: Map<'a, String, u64>,
+    ordered_sent_sequences: Map<'a, (String, String), u128>,
+    ordered_delivered_sequences: Map<'a, (String, String), u128>,
+    ordered_buffer: Map<'a, (String, String, u128), BufferedRequest>,
+    inbound_policies: Map<'a, String, InboundPolicy>,
+    dapp_connections: Map<'a, (String, NetId), DappConnections>,
+    roles: Map<'a, (String, String), bool>,
+    pending_admin: Item<'a, Addr>,
+    source_heights: Map<'a, NetId, u64>,
+    extended_message_networks: Map<'a, NetId, bool>,
+    sending_sn: Item<'a, u128>,
+    message_conn_sns: Map<'a, (u128, String), u128>,
+    backfill_cursor: Item<'a, u128>,
 }
 
 impl<'a> Default for CwCallService<'a> {
@@ -50,10 +109,39 @@ impl<'a> CwCallService<'a> {
             default_connections: Map::new(StorageKey::DefaultConnections.as_str()),
             pending_requests: Map::new(StorageKey::PendingRequests.as_str()),
             pending_responses: Map::new(StorageKey::PendingResponses.as_str()),
+            pending_request_deliveries: Map::new(StorageKey::PendingRequestDeliveries.as_str()),
+            pending_response_deliveries: Map::new(StorageKey::PendingResponseDeliveries.as_str()),
+            pending_delivery_ttl: Item::new(StorageKey::PendingDeliveryTtl.as_str()),
+            rollback_grace_period: Item::new(StorageKey::RollbackGracePeriod.as_str()),
             successful_responses: Map::new(StorageKey::SuccessfulResponses.as_str()),
             config: Item::new(StorageKey::Config.as_str()),
             callback_data: Map::new(StorageKey::Callbackdata.as_str()),
-            call_reply: Item::new(StorageKey::CallReply.as_str()),
+            call_replies: Item::new(StorageKey::CallReplies.as_str()),
+            message_status: Map::new(StorageKey::MessageStatus.as_str()),
+            global_pause: Item::new(StorageKey::GlobalPause.as_str()),
+            network_pause: Map::new(StorageKey::NetworkPause.as_str()),
+            paused_messages: Map::new(StorageKey::PausedMessages.as_str()),
+            paused_message_id: Item::new(StorageKey::PausedMessageId.as_str()),
+            paused_message_counts: Map::new(StorageKey::PausedMessageCounts.as_str()),
+            payload_limits: Item::new(StorageKey::PayloadLimits.as_str()),
+            network_payload_limits: Map::new(StorageKey::NetworkPayloadLimits.as_str()),
+            fee_denoms: Map::new(StorageKey::FeeDenoms.as_str()),
+            fee_balances: Map::new(StorageKey::FeeBalances.as_str()),
+            fee_sponsors: Map::new(StorageKey::FeeSponsors.as_str()),
+            fee_usage: Map::new(StorageKey::FeeUsage.as_str()),
+            fee_usage_count: Map::new(StorageKey::FeeUsageCount.as_str()),
+            ordered_sent_sequences: Map::new(StorageKey::OrderedSentSequences.as_str()),
+            ordered_delivered_sequences: Map::new(StorageKey::OrderedDeliveredSequences.as_str()),
+            ordered_buffer: Map::new(StorageKey::OrderedBuffer.as_str()),
+            inbound_policies: Map::new(StorageKey::InboundPolicies.as_str()),
+            dapp_connections: Map::new(StorageKey::DappConnections.as_str()),
+            roles: Map::new(StorageKey::Roles.as_str()),
+            pending_admin: Item::new(StorageKey::PendingAdmin.as_str()),
+            source_heights: Map::new(StorageKey::SourceHeights.as_str()),
+            extended_message_networks: Map::new(StorageKey::ExtendedMessageNetworks.as_str()),
+            sending_sn: Item::new(StorageKey::SendingSn.as_str()),
+            message_conn_sns: Map::new(StorageKey::MessageConnSns.as_str()),
+            backfill_cursor: Item::new(StorageKey::BackfillCursor.as_str()),
         }
     }
 
@@ -93,14 +181,40 @@ impl<'a> CwCallService<'a> {
         store: &mut dyn Storage,
         req_id: u128,
     ) -> Result<(), ContractError> {
-        self.store_callback_data(store, EXECUTE_CALL_ID, &req_id)
+        self.store_execute_item_request_id(store, EXECUTE_CALL_ID, req_id)
     }
-    pub fn remove_execute_request_id(&self, store: &mut dyn Storage) {
-        self.clear_callback_data(store, EXECUTE_CALL_ID)
+
+    pub fn store_execute_item_request_id(
+        &self,
+        store: &mut dyn Storage,
+        reply_id: u64,
+        req_id: u128,
+    ) -> Result<(), ContractError> {
+        self.store_callback_data(store, reply_id, &req_id)
+    }
+
+    pub fn remove_execute_request_id(&self, store: &mut dyn Storage, reply_id: u64) {
+        self.clear_callback_data(store, reply_id)
+    }
+
+    pub fn get_execute_item_request_id(
+        &self,
+        store: &dyn Storage,
+        reply_id: u64,
+    ) -> Result<u128, ContractError> {
+        self.get_callback_data(store, reply_id)
     }
 
+    /// Returns the request whose dapp is being called right now. Replies clear their entry before
+    /// the next item of a batch runs, so that is the pending execution with the lowest reply id.
     pub fn get_execute_request_id(&self, store: &dyn Storage) -> Result<u128, ContractError> {
-        self.get_callback_data(store, EXECUTE_CALL_ID)
+        let reply_id = self
+            .callback_data
+            .keys(store, None, None, Order::Ascending)
+            .filter_map(Result::ok)
+            .find(|id| is_execute_call_reply(*id))
+            .ok_or(ContractError::Std(StdError::not_found("ExecuteRequestId")))?;
+        self.get_callback_data(store, reply_id)
     }
 
     pub fn admin(&self) -> &Item<'a, Addr> {
@@ -128,6 +242,10 @@ impl<'a> CwCallService<'a> {
             .map_err(ContractError::Std)
     }
 
+    pub fn proxy_requests(&self) -> &Map<'a, u128, CSMessageRequest> {
+        &self.proxy_request
+    }
+
     pub fn remove_proxy_request(&self, store: &mut dyn Storage, id: u128) {
         self.proxy_request.remove(store, id)
     }
@@ -153,6 +271,10 @@ impl<'a> CwCallService<'a> {
             .map_err(ContractError::Std)
     }
 
+    pub fn call_requests(&self) -> &Map<'a, u128, Rollback> {
+        &self.call_requests
+    }
+
     pub fn remove_call_request(&self, store: &mut dyn Storage, id: u128) {
         self.call_requests.remove(store, id)
     }
@@ -172,6 +294,10 @@ impl<'a> CwCallService<'a> {
         &self.fee_handler
     }
 
+    pub fn fee_denoms(&self) -> &Map<'a, String, Decimal> {
+        &self.fee_denoms
+    }
+
     pub fn store_default_connection(
         &self,
         store: &mut dyn Storage,
@@ -188,8 +314,10 @@ impl<'a> CwCallService<'a> {
         nid: NetId,
     ) -> Result<Addr, ContractError> {
         self.default_connections
-            .load(store, nid)
-            .map_err(ContractError::Std)
+            .load(store, nid.clone())
+            .map_err(|_| ContractError::DefaultConnectionNotFound {
+                nid: nid.to_string(),
+            })
     }
 
     pub fn get_pending_requests_by_hash(
@@ -205,7 +333,7 @@ impl<'a> CwCallService<'a> {
         store: &mut dyn Storage,
         hash: Vec<u8>,
     ) -> Result<(), ContractError> {
-        self.remove_by_prefix(store, &self.pending_requests, hash)
+        self.remove_pending_delivery(store, DeliveryKind::Request, hash)
     }
 
     pub fn save_pending_requests(
@@ -232,7 +360,7 @@ impl<'a> CwCallService<'a> {
         store: &mut dyn Storage,
         hash: Vec<u8>,
     ) -> Result<(), ContractError> {
-        self.remove_by_prefix(store, &self.pending_responses, hash)
+        self.remove_pending_delivery(store, DeliveryKind::Response, hash)
     }
 
     pub fn save_pending_responses(
@@ -246,6 +374,220 @@ impl<'a> CwCallService<'a> {
             .map_err(ContractError::Std)
     }
 
+    /// Records that `caller` delivered the message hashed to `hash` and returns whether the
+    /// message is accepted with this delivery.
+    ///
+    /// A message is accepted once `threshold` of its `protocols`, or all of them when there is no
+    /// threshold, delivered identical bytes. Deliveries arriving after that are only recorded, to
+    /// clear the pending entries once every protocol delivered, and are never accepted again.
+    /// `protocols` and `threshold` are not used for a message that is already accepted.
+    #[allow(clippy::too_many_arguments)]
+    pub fn register_delivery(
+        &self,
+        store: &mut dyn Storage,
+        kind: DeliveryKind,
+        hash: Vec<u8>,
+        caller: String,
+        protocols: &[String],
+        threshold: Option<u32>,
+        now: Timestamp,
+    ) -> Result<bool, ContractError> {
+        let (map, deliveries) = self.pending_maps(kind);
+        let pending = deliveries
+            .may_load(store, hash.clone())
+            .map_err(ContractError::Std)?;
+        if let Some(pending) = pending.as_ref().filter(|pending| pending.accepted) {
+            ensure!(
+                pending.protocols.contains(&caller),
+                ContractError::ProtocolsMismatch
+            );
+        }
+        map.save(store, (hash.clone(), caller), &true)
+            .map_err(ContractError::Std)?;
+        let registered = self.get_by_prefix(store, map, hash.clone())?.len();
+
+        let mut pending = pending.unwrap_or_else(|| PendingDelivery::new(protocols.to_vec(), now));
+        if pending.accepted {
+            if registered >= pending.protocols.len() {
+                self.remove_pending_delivery(store, kind, hash)?;
+            }
+            return Ok(false);
+        }
+
+        let quorum = threshold.map_or(protocols.len(), |threshold| {
+            (threshold as usize).clamp(1, protocols.len())
+        });
+        if registered >= protocols.len() {
+            self.remove_pending_delivery(store, kind, hash)?;
+            return Ok(true);
+        }
+        pending.accepted = registered >= quorum;
+        deliveries
+            .save(store, hash, &pending)
+            .map_err(ContractError::Std)?;
+        Ok(pending.accepted)
+    }
+
+    pub fn get_pending_delivery(
+        &self,
+        store: &dyn Storage,
+        kind: DeliveryKind,
+        hash: &[u8],
+    ) -> Option<PendingDelivery> {
+        let (_, deliveries) = self.pending_maps(kind);
+        deliveries.load(store, hash.to_vec()).ok()
+    }
+
+    pub fn get_pending_deliveries(
+        &self,
+        store: &dyn Storage,
+        kind: DeliveryKind,
+        start_after: Option<Vec<u8>>,
+        limit: usize,
+    ) -> StdResult<Vec<(Vec<u8>, PendingDelivery)>> {
+        let (_, deliveries) = self.pending_maps(kind);
+        deliveries
+            .range(
+                store,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect()
+    }
+
+    /// Returns the hashes of up to `limit` messages of `kind` whose first delivery is older than
+    /// `ttl` and that were not accepted yet, ordered by hash. Iteration stops at the `limit`th
+    /// match.
+    pub fn get_expired_pending_deliveries(
+        &self,
+        store: &dyn Storage,
+        kind: DeliveryKind,
+        now: Timestamp,
+        ttl: u64,
+        limit: usize,
+    ) -> StdResult<Vec<Vec<u8>>> {
+        let (_, deliveries) = self.pending_maps(kind);
+        deliveries
+            .range(store, None, None, Order::Ascending)
+            .filter(|entry| match entry {
+                Ok((_, pending)) => !pending.accepted && pending.is_expired(now, ttl),
+                Err(_) => true,
+            })
+            .map(|entry| entry.map(|(hash, _)| hash))
+            .take(limit)
+            .collect()
+    }
+
+    /// Returns the protocols that delivered the message hashed to `hash` so far.
+    pub fn get_delivered_protocols(
+        &self,
+        store: &dyn Storage,
+        kind: DeliveryKind,
+        hash: Vec<u8>,
+    ) -> Result<Vec<String>, ContractError> {
+        let (map, _) = self.pending_maps(kind);
+        let delivered = self.get_by_prefix(store, map, hash)?;
+        Ok(delivered.into_iter().map(|(caller, _)| caller).collect())
+    }
+
+    pub fn remove_pending_delivery(
+        &self,
+        store: &mut dyn Storage,
+        kind: DeliveryKind,
+        hash: Vec<u8>,
+    ) -> Result<(), ContractError> {
+        let (map, deliveries) = self.pending_maps(kind);
+        deliveries.remove(store, hash.clone());
+        self.remove_by_prefix(store, map, hash)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn pending_maps(
+        &self,
+        kind: DeliveryKind,
+    ) -> (
+        &Map<'a, (Vec<u8>, String), bool>,
+        &Map<'a, Vec<u8>, PendingDelivery>,
+    ) {
+        match kind {
+            DeliveryKind::Request => (&self.pending_requests, &self.pending_request_deliveries),
+            DeliveryKind::Response => (&self.pending_responses, &self.pending_response_deliveries),
+        }
+    }
+
+    /// Creates the missing delivery record of every message with deliveries in the pending map of
+    /// `kind`. The protocols the message was sent over are not known from the pending map, so the
+    /// record lists the ones that delivered it so far.
+    pub fn backfill_pending_deliveries(
+        &self,
+        store: &mut dyn Storage,
+        kind: DeliveryKind,
+        now: Timestamp,
+    ) -> Result<(), ContractError> {
+        let (map, deliveries) = self.pending_maps(kind);
+        let keys = map
+            .keys(store, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<(Vec<u8>, String)>>>()?;
+        let mut missing: Vec<(Vec<u8>, Vec<String>)> = vec![];
+        for (hash, caller) in keys {
+            if deliveries.has(store, hash.clone()) {
+                continue;
+            }
+            match missing.last_mut() {
+                Some((last, callers)) if *last == hash => callers.push(caller),
+                _ => missing.push((hash, vec![caller])),
+            }
+        }
+        for (hash, callers) in missing {
+            deliveries.save(store, hash, &PendingDelivery::new(callers, now))?;
+        }
+        Ok(())
+    }
+
+    pub fn get_pending_delivery_ttl(&self, store: &dyn Storage) -> Option<u64> {
+        self.pending_delivery_ttl.load(store).ok()
+    }
+
+    pub fn store_pending_delivery_ttl(
+        &self,
+        store: &mut dyn Storage,
+        ttl: Option<u64>,
+    ) -> Result<(), ContractError> {
+        match ttl {
+            Some(ttl) => self
+                .pending_delivery_ttl
+                .save(store, &ttl)
+                .map_err(ContractError::Std),
+            None => {
+                self.pending_delivery_ttl.remove(store);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn get_rollback_grace_period(&self, store: &dyn Storage) -> Option<u64> {
+        self.rollback_grace_period.load(store).ok()
+    }
+
+    pub fn store_rollback_grace_period(
+        &self,
+        store: &mut dyn Storage,
+        period: Option<u64>,
+    ) -> Result<(), ContractError> {
+        match period {
+            Some(period) => self
+                .rollback_grace_period
+                .save(store, &period)
+                .map_err(ContractError::Std),
+            None => {
+                self.rollback_grace_period.remove(store);
+                Ok(())
+            }
+        }
+    }
+
     pub fn get_all_connections(&self, store: &dyn Storage) -> Result<Vec<String>, ContractError> {
         let res = self.get_all_values::<NetId, Addr>(store, &self.default_connections)?;
         let addresses: Vec<String> = res.into_iter().map(|a| a.to_string()).collect();
@@ -277,7 +619,7 @@ impl<'a> CwCallService<'a> {
             .collect();
         let keys = keys.map_err(ContractError::Std)?;
         for key in keys {
-            self.pending_requests.remove(store, (hash.clone(), key))
+            map.remove(store, (hash.clone(), key))
         }
         Ok(())
     }
@@ -334,6 +676,21 @@ impl<'a> CwCallService<'a> {
             .map_err(ContractError::Std)
     }
 
+    pub fn get_message_status(&self, store: &dyn Storage, sn: u128) -> Option<MessageStatus> {
+        self.message_status.load(store, sn).ok()
+    }
+
+    pub fn store_message_status(
+        &self,
+        store: &mut dyn Storage,
+        sn: u128,
+        status: MessageStatus,
+    ) -> Result<(), ContractError> {
+        self.message_status
+            .save(store, sn, &status)
+            .map_err(ContractError::Std)
+    }
+
     pub fn store_callback_data<T>(
         &self,
         store: &mut dyn Storage,
@@ -374,21 +731,540 @@ impl<'a> CwCallService<'a> {
         Ok(data)
     }
 
-    pub fn get_call_reply(&self, store: &dyn Storage) -> Option<CSMessageRequest> {
-        self.call_reply.load(store).ok()
+    /// Returns the replies sent so far by the dapp being executed, in the order they were sent.
+    pub fn get_call_replies(&self, store: &dyn Storage) -> Vec<CSMessageRequest> {
+        self.call_replies.load(store).unwrap_or_default()
     }
 
-    pub fn save_call_reply(
+    pub fn push_call_reply(
         &self,
         store: &mut dyn Storage,
         msg: &CSMessageRequest,
     ) -> Result<(), ContractError> {
-        self.call_reply.save(store, msg).map_err(ContractError::Std)
+        let mut replies = self.get_call_replies(store);
+        replies.push(msg.clone());
+        self.call_replies
+            .save(store, &replies)
+            .map_err(ContractError::Std)
+    }
+
+    pub fn pop_call_replies(&self, store: &mut dyn Storage) -> Vec<CSMessageRequest> {
+        let replies = self.get_call_replies(store);
+        self.call_replies.remove(store);
+        replies
+    }
+
+    pub fn is_globally_paused(&self, store: &dyn Storage) -> bool {
+        self.global_pause.load(store).unwrap_or(false)
+    }
+
+    pub fn store_global_pause(
+        &self,
+        store: &mut dyn Storage,
+        paused: bool,
+    ) -> Result<(), ContractError> {
+        self.global_pause
+            .save(store, &paused)
+            .map_err(ContractError::Std)
+    }
+
+    pub fn get_network_pause(&self, store: &dyn Storage, nid: NetId) -> NetworkPause {
+        self.network_pause.load(store, nid).unwrap_or_default()
+    }
+
+    pub fn store_network_pause(
+        &self,
+        store: &mut dyn Storage,
+        nid: NetId,
+        pause: &NetworkPause,
+    ) -> Result<(), ContractError> {
+        self.network_pause
+            .save(store, nid, pause)
+            .map_err(ContractError::Std)
+    }
+
+    pub fn save_paused_message(
+        &self,
+        store: &mut dyn Storage,
+        message: &PausedMessage,
+    ) -> Result<u128, ContractError> {
+        let id = self.paused_message_id.load(store).unwrap_or(0) + 1;
+        self.paused_message_id.save(store, &id)?;
+        self.paused_messages
+            .save(store, (message.from_nid.to_string(), id), message)?;
+        let count = self.get_paused_message_count(store, &message.from_nid);
+        self.paused_message_counts
+            .save(store, message.from_nid.clone(), &(count + 1))?;
+        Ok(id)
+    }
+
+    pub fn remove_paused_message(
+        &self,
+        store: &mut dyn Storage,
+        nid: &NetId,
+        id: u128,
+    ) -> Result<(), ContractError> {
+        if !self.has_paused_message(store, nid, id) {
+            return Ok(());
+        }
+        self.paused_messages.remove(store, (nid.to_string(), id));
+        let count = self.get_paused_message_count(store, nid);
+        self.paused_message_counts
+            .save(store, nid.clone(), &count.saturating_sub(1))
+            .map_err(ContractError::Std)
+    }
+
+    pub fn has_paused_message(&self, store: &dyn Storage, nid: &NetId, id: u128) -> bool {
+        self.paused_messages.has(store, (nid.to_string(), id))
+    }
+
+    /// Number of messages queued from `nid`, kept next to the queue so that it can be read without
+    /// ranging over it.
+    pub fn get_paused_message_count(&self, store: &dyn Storage, nid: &NetId) -> u64 {
+        self.paused_message_counts
+            .load(store, nid.clone())
+            .unwrap_or(0)
+    }
+
+    /// Lists the messages queued from `nid`, oldest first.
+    pub fn get_paused_messages(
+        &self,
+        store: &dyn Storage,
+        nid: &NetId,
+        limit: usize,
+    ) -> Result<Vec<(u128, PausedMessage)>, ContractError> {
+        self.paused_messages
+            .prefix(nid.to_string())
+            .range(store, None, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<Vec<(u128, PausedMessage)>>>()
+            .map_err(ContractError::Std)
+    }
+
+    pub fn get_default_payload_limits(&self, store: &dyn Storage) -> PayloadLimits {
+        self.payload_limits.load(store).unwrap_or(PayloadLimits {
+            max_data_size: MAX_DATA_SIZE,
+            max_rollback_size: MAX_ROLLBACK_SIZE,
+        })
+    }
+
+    pub fn store_default_payload_limits(
+        &self,
+        store: &mut dyn Storage,
+        limits: &PayloadLimits,
+    ) -> Result<(), ContractError> {
+        self.payload_limits
+            .save(store, limits)
+            .map_err(ContractError::Std)
+    }
+
+    /// Returns the limits that apply to messages sent to `nid`, falling back to the default limits
+    /// when the network has no override.
+    pub fn get_payload_limits(&self, store: &dyn Storage, nid: &NetId) -> PayloadLimits {
+        match self.network_payload_limits.load(store, nid.clone()) {
+            Ok(limits) => limits,
+            Err(_) => self.get_default_payload_limits(store),
+        }
+    }
+
+    pub fn store_network_payload_limits(
+        &self,
+        store: &mut dyn Storage,
+        nid: NetId,
+        limits: &PayloadLimits,
+    ) -> Result<(), ContractError> {
+        self.network_payload_limits
+            .save(store, nid, limits)
+            .map_err(ContractError::Std)
+    }
+
+    pub fn remove_network_payload_limits(&self, store: &mut dyn Storage, nid: NetId) {
+        self.network_payload_limits.remove(store, nid)
+    }
+
+    pub fn get_fee_balance(&self, store: &dyn Storage, dapp: &Addr, denom: &str) -> u128 {
+        self.fee_balances
+            .load(store, (dapp.to_string(), denom.to_string()))
+            .unwrap_or(0)
+    }
+
+    /// Lists the prepaid fee balances of `dapp` by denom.
+    pub fn get_fee_balances(
+        &self,
+        store: &dyn Storage,
+        dapp: &Addr,
+    ) -> Result<Vec<(String, u128)>, ContractError> {
+        self.fee_balances
+            .prefix(dapp.to_string())
+            .range(store, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<(String, u128)>>>()
+            .map_err(ContractError::Std)
+    }
+
+    pub fn store_fee_balance(
+        &self,
+        store: &mut dyn Storage,
+        dapp: &Addr,
+        denom: &str,
+        balance: u128,
+    ) -> Result<(), ContractError> {
+        let key = (dapp.to_string(), denom.to_string());
+        if balance == 0 {
+            self.fee_balances.remove(store, key);
+            return Ok(());
+        }
+        self.fee_balances
+            .save(store, key, &balance)
+            .map_err(ContractError::Std)
+    }
+
+    pub fn is_fee_sponsor(&self, store: &dyn Storage, dapp: &Addr, sponsor: &Addr) -> bool {
+        self.fee_sponsors
+            .has(store, (dapp.to_string(), sponsor.to_string()))
+    }
+
+    pub fn store_fee_sponsor(
+        &self,
+        store: &mut dyn Storage,
+        dapp: &Addr,
+        sponsor: &Addr,
+    ) -> Result<(), ContractError> {
+        self.fee_sponsors
+            .save(store, (dapp.to_string(), sponsor.to_string()), &true)
+            .map_err(ContractError::Std)
+    }
+
+    pub fn delete_fee_sponsor(&self, store: &mut dyn Storage, dapp: &Addr, sponsor: &Addr) {
+        self.fee_sponsors
+            .remove(store, (dapp.to_string(), sponsor.to_string()))
+    }
+
+    pub fn save_fee_usage(
+        &self,
+        store: &mut dyn Storage,
+        dapp: &Addr,
+        usage: &FeeUsage,
+    ) -> Result<(), ContractError> {
+        let index = self
+            .fee_usage_count
+            .load(store, dapp.to_string())
+            .unwrap_or(0);
+        self.fee_usage
+            .save(store, (dapp.to_string(), index), usage)?;
+        self.fee_usage_count
+            .save(store, dapp.to_string(), &(index + 1))
+            .map_err(ContractError::Std)
+    }
+
+    pub fn fee_usage(&self) -> &Map<'a, (String, u64), FeeUsage> {
+        &self.fee_usage
+    }
+
+    /// Assigns the next position in the ordered channel from the dapp `from` on this chain to `to`.
+    pub fn get_next_ordered_sn(
+        &self,
+        store: &mut dyn Storage,
+        from: &Addr,
+        to: &NetworkAddress,
+    ) -> Result<u128, ContractError> {
+        let key = (from.to_string(), to.to_string());
+        let ordered_sn = self
+            .ordered_sent_sequences
+            .load(store, key.clone())
+            .unwrap_or(0)
+            + 1;
+        self.ordered_sent_sequences.save(store, key, &ordered_sn)?;
+        Ok(ordered_sn)
+    }
+
+    /// The position of the last request delivered, or skipped, in the ordered channel from `from`
+    /// to the dapp `to` on this chain. Zero when nothing was delivered yet.
+    pub fn get_last_ordered_sn(
+        &self,
+        store: &dyn Storage,
+        from: &NetworkAddress,
+        to: &Addr,
+    ) -> u128 {
+        self.ordered_delivered_sequences
+            .load(store, (from.to_string(), to.to_string()))
+            .unwrap_or(0)
+    }
+
+    pub fn store_last_ordered_sn(
+        &self,
+        store: &mut dyn Storage,
+        from: &NetworkAddress,
+        to: &Addr,
+        ordered_sn: u128,
+    ) -> Result<(), ContractError> {
+        self.ordered_delivered_sequences
+            .save(store, (from.to_string(), to.to_string()), &ordered_sn)
+            .map_err(ContractError::Std)
+    }
+
+    pub fn get_buffered_request(
+        &self,
+        store: &dyn Storage,
+        from: &NetworkAddress,
+        to: &Addr,
+        ordered_sn: u128,
+    ) -> Option<BufferedRequest> {
+        self.ordered_buffer
+            .may_load(store, (from.to_string(), to.to_string(), ordered_sn))
+            .ok()
+            .flatten()
     }
 
-    pub fn pop_call_reply(&self, store: &mut dyn Storage) -> Option<CSMessageRequest> {
-        let reply = self.get_call_reply(store);
-        self.call_reply.remove(store);
-        reply
+    pub fn store_buffered_request(
+        &self,
+        store: &mut dyn Storage,
+        ordered_sn: u128,
+        buffered: &BufferedRequest,
+    ) -> Result<(), ContractError> {
+        let request = &buffered.request;
+        let key = (
+            request.from().to_string(),
+            request.to().to_string(),
+            ordered_sn,
+        );
+        self.ordered_buffer
+            .save(store, key, buffered)
+            .map_err(ContractError::Std)
     }
+
+    pub fn remove_buffered_request(
+        &self,
+        store: &mut dyn Storage,
+        from: &NetworkAddress,
+        to: &Addr,
+        ordered_sn: u128,
+    ) {
+        self.ordered_buffer
+            .remove(store, (from.to_string(), to.to_string(), ordered_sn))
+    }
+
+    /// Lists the requests of the ordered channel from `from` to `to` that arrived ahead of their
+    /// turn, ordered by position.
+    pub fn get_buffered_requests(
+        &self,
+        store: &dyn Storage,
+        from: &NetworkAddress,
+        to: &Addr,
+        start_after: Option<u128>,
+        limit: usize,
+    ) -> Result<Vec<(u128, BufferedRequest)>, ContractError> {
+        self.ordered_buffer
+            .prefix((from.to_string(), to.to_string()))
+            .range(
+                store,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect::<StdResult<Vec<(u128, BufferedRequest)>>>()
+            .map_err(ContractError::Std)
+    }
+
+    pub fn get_inbound_policy(&self, store: &dyn Storage, dapp: &Addr) -> Option<InboundPolicy> {
+        self.inbound_policies.load(store, dapp.to_string()).ok()
+    }
+
+    pub fn store_inbound_policy(
+        &self,
+        store: &mut dyn Storage,
+        dapp: &Addr,
+        policy: &InboundPolicy,
+    ) -> Result<(), ContractError> {
+        self.inbound_policies
+            .save(store, dapp.to_string(), policy)
+            .map_err(ContractError::Std)
+    }
+
+    pub fn remove_inbound_policy(&self, store: &mut dyn Storage, dapp: &Addr) {
+        self.inbound_policies.remove(store, dapp.to_string())
+    }
+
+    pub fn get_dapp_connections(
+        &self,
+        store: &dyn Storage,
+        dapp: &Addr,
+        nid: &NetId,
+    ) -> Option<DappConnections> {
+        self.dapp_connections
+            .load(store, (dapp.to_string(), nid.clone()))
+            .ok()
+    }
+
+    pub fn store_dapp_connections(
+        &self,
+        store: &mut dyn Storage,
+        dapp: &Addr,
+        nid: NetId,
+        connections: &DappConnections,
+    ) -> Result<(), ContractError> {
+        self.dapp_connections
+            .save(store, (dapp.to_string(), nid), connections)
+            .map_err(ContractError::Std)
+    }
+
+    pub fn remove_dapp_connections(&self, store: &mut dyn Storage, dapp: &Addr, nid: NetId) {
+        self.dapp_connections.remove(store, (dapp.to_string(), nid))
+    }
+
+    pub fn has_role(&self, store: &dyn Storage, role: &Role, address: &Addr) -> bool {
+        self.roles
+            .has(store, (role.as_str().to_string(), address.to_string()))
+    }
+
+    pub fn store_role(
+        &self,
+        store: &mut dyn Storage,
+        role: &Role,
+        address: &Addr,
+    ) -> Result<(), ContractError> {
+        self.roles
+            .save(
+                store,
+                (role.as_str().to_string(), address.to_string()),
+                &true,
+            )
+            .map_err(ContractError::Std)
+    }
+
+    pub fn remove_role(&self, store: &mut dyn Storage, role: &Role, address: &Addr) {
+        self.roles
+            .remove(store, (role.as_str().to_string(), address.to_string()))
+    }
+
+    pub fn get_role_members(&self, store: &dyn Storage, role: &Role) -> StdResult<Vec<String>> {
+        self.roles
+            .prefix(role.as_str().to_string())
+            .keys(store, None, None, Order::Ascending)
+            .collect()
+    }
+
+    pub fn get_pending_admin(&self, store: &dyn Storage) -> Option<Addr> {
+        self.pending_admin.load(store).ok()
+    }
+
+    pub fn store_pending_admin(
+        &self,
+        store: &mut dyn Storage,
+        address: &Addr,
+    ) -> Result<(), ContractError> {
+        self.pending_admin
+            .save(store, address)
+            .map_err(ContractError::Std)
+    }
+
+    pub fn remove_pending_admin(&self, store: &mut dyn Storage) {
+        self.pending_admin.remove(store)
+    }
+
+    /// The highest block height of `nid` known on this chain, learned from the requests it sent.
+    pub fn get_source_height(&self, store: &dyn Storage, nid: &NetId) -> Option<u64> {
+        self.source_heights.load(store, nid.clone()).ok()
+    }
+
+    /// Records that `nid` reached `height`, keeping the highest height known.
+    pub fn store_source_height(
+        &self,
+        store: &mut dyn Storage,
+        nid: &NetId,
+        height: u64,
+    ) -> Result<(), ContractError> {
+        if self.get_source_height(store, nid).unwrap_or(0) >= height {
+            return Ok(());
+        }
+        self.source_heights
+            .save(store, nid.clone(), &height)
+            .map_err(ContractError::Std)
+    }
+
+    /// Whether xcall on `nid` decodes extended requests and results with several replies.
+    pub fn supports_extended_messages(&self, store: &dyn Storage, nid: &NetId) -> bool {
+        self.extended_message_networks
+            .load(store, nid.clone())
+            .unwrap_or(false)
+    }
+
+    pub fn store_extended_messages(
+        &self,
+        store: &mut dyn Storage,
+        nid: NetId,
+        enabled: bool,
+    ) -> Result<(), ContractError> {
+        if !enabled {
+            self.extended_message_networks.remove(store, nid);
+            return Ok(());
+        }
+        self.extended_message_networks
+            .save(store, nid, &true)
+            .map_err(ContractError::Std)
+    }
+
+    /// Records the sequence number of the message whose connection submessages are dispatched
+    /// next, so their replies can be matched to it.
+    pub fn store_sending_sn(&self, store: &mut dyn Storage, sn: u128) -> Result<(), ContractError> {
+        self.sending_sn.save(store, &sn).map_err(ContractError::Std)
+    }
+
+    pub fn get_sending_sn(&self, store: &dyn Storage) -> Result<u128, ContractError> {
+        self.sending_sn.load(store).map_err(ContractError::Std)
+    }
+
+    pub fn store_message_conn_sn(
+        &self,
+        store: &mut dyn Storage,
+        sn: u128,
+        connection: &str,
+        conn_sn: u128,
+    ) -> Result<(), ContractError> {
+        self.message_conn_sns
+            .save(store, (sn, connection.to_string()), &conn_sn)
+            .map_err(ContractError::Std)
+    }
+
+    /// The sequence number `connection` assigned to the message sent with `sn`, if it reported
+    /// one.
+    pub fn get_message_conn_sn(
+        &self,
+        store: &dyn Storage,
+        sn: u128,
+        connection: &str,
+    ) -> Option<u128> {
+        self.message_conn_sns
+            .load(store, (sn, connection.to_string()))
+            .ok()
+    }
+
+    /// The sequence number of the last call request backfilled by a migration that has not
+    /// reached the end of the call requests yet.
+    pub fn get_backfill_cursor(&self, store: &dyn Storage) -> Option<u128> {
+        self.backfill_cursor.load(store).ok()
+    }
+
+    pub fn store_backfill_cursor(
+        &self,
+        store: &mut dyn Storage,
+        cursor: Option<u128>,
+    ) -> Result<(), ContractError> {
+        match cursor {
+            Some(cursor) => self
+                .backfill_cursor
+                .save(store, &cursor)
+                .map_err(ContractError::Std),
+            None => {
+                self.backfill_cursor.remove(store);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Whether `id` is the reply id of a single `ExecuteCall` or of an item of an `ExecuteCalls` batch.
+pub fn is_execute_call_reply(id: u64) -> bool {
+    id == EXECUTE_CALL_ID
+        || (EXECUTE_CALLS_REPLY_ID..EXECUTE_CALLS_REPLY_ID + MAX_EXECUTE_CALLS).contains(&id)
 }
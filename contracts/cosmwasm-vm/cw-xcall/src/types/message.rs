@@ -4,6 +4,8 @@ use super::*;
 pub enum CSMessageType {
     CSMessageRequest = 1,
     CSMessageResult,
+    /// A request carrying optional fields, such as an expiry, after the legacy ones.
+    CSMessageRequestV2,
 }
 
 #[cw_serde]
@@ -37,6 +39,7 @@ impl Encodable for CSMessage {
         let msg_type: u8 = match self.message_type {
             CSMessageType::CSMessageRequest => 1,
             CSMessageType::CSMessageResult => 2,
+            CSMessageType::CSMessageRequestV2 => 3,
         };
         stream.begin_list(2).append(&msg_type).append(&self.payload);
     }
@@ -50,6 +53,7 @@ impl Decodable for CSMessage {
             message_type: match msg_type {
                 1 => Ok(CSMessageType::CSMessageRequest),
                 2 => Ok(CSMessageType::CSMessageResult),
+                3 => Ok(CSMessageType::CSMessageRequestV2),
                 _ => Err(rlp::DecoderError::Custom("Invalid type")),
             }?,
             payload: rlp.val_at(1)?,
@@ -59,8 +63,12 @@ impl Decodable for CSMessage {
 
 impl From<CSMessageRequest> for CSMessage {
     fn from(value: CSMessageRequest) -> Self {
+        let message_type = match value.is_extended() {
+            true => CSMessageType::CSMessageRequestV2,
+            false => CSMessageType::CSMessageRequest,
+        };
         Self {
-            message_type: CSMessageType::CSMessageRequest,
+            message_type,
             payload: rlp::encode(&value).to_vec(),
         }
     }
@@ -1,5 +1,8 @@
 pub mod config;
 pub mod message;
+pub mod ordered;
+pub mod pause;
+pub mod pending;
 pub mod request;
 pub mod result;
 pub mod rollback;
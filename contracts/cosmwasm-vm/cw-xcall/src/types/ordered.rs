@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+/// An ordered request that arrived ahead of its turn, kept with the connection whose delivery made
+/// it accepted.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BufferedRequest {
+    pub request: CSMessageRequest,
+    pub connection: String,
+}
@@ -0,0 +1,37 @@
+use cosmwasm_std::Addr;
+use cw_xcall_lib::network_address::NetId;
+
+use super::*;
+
+/// Pause switches for a single remote network, one per message direction.
+#[cw_serde]
+#[derive(Default)]
+pub struct NetworkPause {
+    pub outbound: bool,
+    pub inbound: bool,
+    pub execution: bool,
+}
+
+pub enum PauseDirection {
+    Outbound,
+    Inbound,
+    Execution,
+}
+
+impl NetworkPause {
+    pub fn is_paused(&self, direction: &PauseDirection) -> bool {
+        match direction {
+            PauseDirection::Outbound => self.outbound,
+            PauseDirection::Inbound => self.inbound,
+            PauseDirection::Execution => self.execution,
+        }
+    }
+}
+
+/// A message delivered by a connection while inbound traffic from its network was paused.
+#[cw_serde]
+pub struct PausedMessage {
+    pub from_nid: NetId,
+    pub connection: Addr,
+    pub msg: Vec<u8>,
+}
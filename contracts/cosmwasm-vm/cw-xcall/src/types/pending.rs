@@ -0,0 +1,40 @@
+use cosmwasm_std::Timestamp;
+
+use super::*;
+
+/// Tracks a message sent over several protocols until all of them have delivered it.
+#[cw_serde]
+pub struct PendingDelivery {
+    pub protocols: Vec<String>,
+    pub received_at: Timestamp,
+    pub accepted: bool,
+}
+
+impl PendingDelivery {
+    pub fn new(protocols: Vec<String>, received_at: Timestamp) -> Self {
+        Self {
+            protocols,
+            received_at,
+            accepted: false,
+        }
+    }
+
+    pub fn is_expired(&self, now: Timestamp, ttl: u64) -> bool {
+        self.received_at.plus_seconds(ttl) <= now
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeliveryKind {
+    Request,
+    Response,
+}
+
+impl DeliveryKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryKind::Request => "request",
+            DeliveryKind::Response => "response",
+        }
+    }
+}
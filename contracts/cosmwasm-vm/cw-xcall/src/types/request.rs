@@ -1,11 +1,18 @@
 use super::*;
 use common::rlp::Nullable;
-use cosmwasm_std::Addr;
-use cw_xcall_lib::{message::msg_type::MessageType, network_address::NetworkAddress};
+use cosmwasm_std::{Addr, BlockInfo};
+use cw_xcall_lib::{
+    message::{expiry::MessageExpiry, msg_type::MessageType},
+    network_address::NetworkAddress,
+};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+/// Number of fields in the original request encoding. Requests carrying optional fields are
+/// encoded with extra trailing items and sent as `CSMessageType::CSMessageRequestV2`.
+const LEGACY_FIELD_COUNT: usize = 6;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct CSMessageRequest {
     from: NetworkAddress,
     to: Addr,
@@ -13,6 +20,16 @@ pub struct CSMessageRequest {
     protocols: Vec<String>,
     msg_type: MessageType,
     data: Nullable<Vec<u8>>,
+    #[serde(default)]
+    expiry: Option<MessageExpiry>,
+    #[serde(default)]
+    sent_height: Option<u64>,
+    #[serde(default)]
+    gas_limit: Option<u64>,
+    #[serde(default)]
+    threshold: Option<u32>,
+    #[serde(default)]
+    ordered_sn: Option<u128>,
 }
 
 impl CSMessageRequest {
@@ -35,9 +52,44 @@ impl CSMessageRequest {
             msg_type,
             data: Nullable::new(data_bytes),
             protocols,
+            expiry: None,
+            sent_height: None,
+            gas_limit: None,
+            threshold: None,
+            ordered_sn: None,
         }
     }
 
+    pub fn with_expiry(mut self, expiry: Option<MessageExpiry>) -> Self {
+        self.expiry = expiry;
+        self
+    }
+
+    pub fn with_sent_height(mut self, sent_height: Option<u64>) -> Self {
+        self.sent_height = sent_height;
+        self
+    }
+
+    pub fn with_gas_limit(mut self, gas_limit: Option<u64>) -> Self {
+        self.gas_limit = gas_limit;
+        self
+    }
+
+    pub fn with_threshold(mut self, threshold: Option<u32>) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn with_protocols(mut self, protocols: Vec<String>) -> Self {
+        self.protocols = protocols;
+        self
+    }
+
+    pub fn with_ordered_sn(mut self, ordered_sn: Option<u128>) -> Self {
+        self.ordered_sn = ordered_sn;
+        self
+    }
+
     pub fn from(&self) -> &NetworkAddress {
         &self.from
     }
@@ -75,6 +127,62 @@ impl CSMessageRequest {
         &self.protocols
     }
 
+    pub fn expiry(&self) -> &Option<MessageExpiry> {
+        &self.expiry
+    }
+
+    /// The block height of the source chain when the request was sent. Only carried by requests
+    /// expiring at a source chain height.
+    pub fn sent_height(&self) -> Option<u64> {
+        self.sent_height
+    }
+
+    /// The gas limit the sender set for the execution by the destination dapp.
+    pub fn gas_limit(&self) -> Option<u64> {
+        self.gas_limit
+    }
+
+    /// The number of protocols the sender asks to deliver the request before it is accepted. It
+    /// only applies when the inbound policy of the destination dapp sets a threshold too.
+    pub fn threshold(&self) -> Option<u32> {
+        self.threshold
+    }
+
+    /// The position of the request in the ordered channel from its sender to its destination dapp,
+    /// starting at 1. Unordered requests have none.
+    pub fn ordered_sn(&self) -> Option<u128> {
+        self.ordered_sn
+    }
+
+    /// Whether the expiry of the request has passed. `source_height` is the highest block height
+    /// of the source chain known on this chain.
+    pub fn is_expired(&self, block: &BlockInfo, source_height: Option<u64>) -> bool {
+        self.expiry
+            .as_ref()
+            .map_or(false, |expiry| expiry.is_expired(block, source_height))
+    }
+
+    /// Whether the request carries fields that the legacy encoding cannot represent.
+    pub fn is_extended(&self) -> bool {
+        self.extra_fields() > 0
+    }
+
+    /// The number of optional fields written after the legacy ones, up to the last one that is set.
+    fn extra_fields(&self) -> usize {
+        match (
+            &self.expiry,
+            self.gas_limit,
+            self.threshold,
+            self.ordered_sn,
+        ) {
+            (_, _, _, Some(_)) => 4,
+            (_, _, Some(_), None) => 3,
+            (_, Some(_), None, None) => 2,
+            (Some(_), None, None, None) => 1,
+            (None, None, None, None) => 0,
+        }
+    }
+
     pub fn as_bytes(&self) -> Vec<u8> {
         rlp::encode(self).to_vec()
     }
@@ -82,7 +190,8 @@ impl CSMessageRequest {
 
 impl Encodable for CSMessageRequest {
     fn rlp_append(&self, stream: &mut rlp::RlpStream) {
-        stream.begin_list(6);
+        let extra_fields = self.extra_fields();
+        stream.begin_list(LEGACY_FIELD_COUNT + extra_fields);
         stream.append(&self.from.to_string());
         stream.append(&self.to.to_string());
         stream.append(&self.sequence_no);
@@ -92,6 +201,33 @@ impl Encodable for CSMessageRequest {
         for protocol in self.protocols.iter() {
             stream.append(protocol);
         }
+        if extra_fields > 0 {
+            // a height expiry carries the source height at send time as a third item
+            match (&self.expiry, self.sent_height) {
+                (Some(MessageExpiry::Height(height)), Some(sent_height)) => stream
+                    .begin_list(3)
+                    .append(&1_u8)
+                    .append(height)
+                    .append(&sent_height),
+                (Some(expiry), _) => stream.append(expiry),
+                (None, _) => stream.begin_list(0),
+            };
+        }
+        if extra_fields > 1 {
+            match &self.gas_limit {
+                Some(gas_limit) => stream.append(gas_limit),
+                None => stream.begin_list(0),
+            };
+        }
+        if extra_fields > 2 {
+            match &self.threshold {
+                Some(threshold) => stream.append(threshold),
+                None => stream.begin_list(0),
+            };
+        }
+        if let Some(ordered_sn) = self.ordered_sn {
+            stream.append(&ordered_sn);
+        }
     }
 }
 
@@ -102,14 +238,43 @@ impl Decodable for CSMessageRequest {
         let str_from: String = rlp.val_at(0)?;
         let to_str: String = rlp.val_at(1)?;
         let msg_type_int: u8 = rlp.val_at(3)?;
+        let field_count = rlp.item_count()?;
+        let expiry = match field_count > LEGACY_FIELD_COUNT && !rlp.at(6)?.is_empty() {
+            true => Some(rlp.val_at(6)?),
+            false => None,
+        };
+        let sent_height = match matches!(expiry, Some(MessageExpiry::Height(_)))
+            && rlp.at(6)?.item_count()? > 2
+        {
+            true => Some(rlp.at(6)?.val_at(2)?),
+            false => None,
+        };
+        let gas_limit = match field_count > LEGACY_FIELD_COUNT + 1 && !rlp.at(7)?.is_empty() {
+            true => Some(rlp.val_at(7)?),
+            false => None,
+        };
+        let threshold = match field_count > LEGACY_FIELD_COUNT + 2 && !rlp.at(8)?.is_empty() {
+            true => Some(rlp.val_at(8)?),
+            false => None,
+        };
+        let ordered_sn = match field_count > LEGACY_FIELD_COUNT + 3 {
+            true => Some(rlp.val_at(9)?),
+            false => None,
+        };
         Ok(Self {
             from: NetworkAddress::from_str(&str_from)
                 .map_err(|_e| rlp::DecoderError::RlpInvalidLength)?,
             to: Addr::unchecked(to_str),
             sequence_no: rlp.val_at(2)?,
-            msg_type: MessageType::from_int(msg_type_int),
+            msg_type: MessageType::try_from_int(msg_type_int)
+                .ok_or(rlp::DecoderError::Custom("Invalid message type"))?,
             data: rlp.val_at(4)?,
             protocols: list,
+            expiry,
+            sent_height,
+            gas_limit,
+            threshold,
+            ordered_sn,
         })
     }
 }
@@ -175,7 +340,8 @@ mod tests {
     use cw_xcall_lib::network_address::NetworkAddress;
 
     use super::CSMessageRequest;
-    use cw_xcall_lib::message::msg_type::MessageType;
+    use crate::types::message::{CSMessage, CSMessageType};
+    use cw_xcall_lib::message::{expiry::MessageExpiry, msg_type::MessageType};
 
     #[test]
     fn test_csmessage_request_encoding() {
@@ -220,6 +386,108 @@ mod tests {
         assert_eq!("f84b8b3078312e4554482f307861aa63783030303030303030303030303030303030303030303030303030303030303030303030303031303215018474657374cc836162638363646583656667",hex::encode(encoded));
     }
 
+    #[test]
+    fn test_csmessage_request_with_expiry() {
+        let msg = CSMessageRequest::new(
+            NetworkAddress::from_str("0x1.ETH/0xa").unwrap(),
+            Addr::unchecked("cx0000000000000000000000000000000000000102"),
+            21,
+            MessageType::CallMessageWithRollback,
+            hex::decode("74657374").unwrap(),
+            vec![],
+        );
+        let legacy: CSMessage = rlp::decode::<CSMessageRequest>(&msg.as_bytes())
+            .unwrap()
+            .into();
+        assert_eq!(legacy.message_type, CSMessageType::CSMessageRequest);
+
+        let msg = msg.with_expiry(Some(MessageExpiry::Timestamp(1_700_000_000)));
+        let decoded: CSMessageRequest = rlp::decode(&msg.as_bytes()).unwrap();
+        assert_eq!(msg, decoded);
+
+        let message: CSMessage = decoded.into();
+        assert_eq!(message.message_type, CSMessageType::CSMessageRequestV2);
+    }
+
+    #[test]
+    fn test_csmessage_request_with_height_expiry() {
+        let msg = CSMessageRequest::new(
+            NetworkAddress::from_str("0x1.ETH/0xa").unwrap(),
+            Addr::unchecked("cx0000000000000000000000000000000000000102"),
+            21,
+            MessageType::CallMessage,
+            hex::decode("74657374").unwrap(),
+            vec![],
+        )
+        .with_expiry(Some(MessageExpiry::Height(120)))
+        .with_sent_height(Some(100));
+
+        let decoded: CSMessageRequest = rlp::decode(&msg.as_bytes()).unwrap();
+        assert_eq!(decoded.expiry(), &Some(MessageExpiry::Height(120)));
+        assert_eq!(decoded.sent_height(), Some(100));
+        assert_eq!(msg, decoded);
+    }
+
+    #[test]
+    fn test_csmessage_request_with_gas_limit() {
+        let msg = CSMessageRequest::new(
+            NetworkAddress::from_str("0x1.ETH/0xa").unwrap(),
+            Addr::unchecked("cx0000000000000000000000000000000000000102"),
+            21,
+            MessageType::CallMessageWithRollback,
+            hex::decode("74657374").unwrap(),
+            vec![],
+        )
+        .with_gas_limit(Some(200_000));
+
+        let decoded: CSMessageRequest = rlp::decode(&msg.as_bytes()).unwrap();
+        assert_eq!(decoded.expiry(), &None);
+        assert_eq!(decoded.gas_limit(), Some(200_000));
+        assert_eq!(msg, decoded);
+
+        let message: CSMessage = decoded.into();
+        assert_eq!(message.message_type, CSMessageType::CSMessageRequestV2);
+    }
+
+    #[test]
+    fn test_csmessage_request_with_threshold() {
+        let protocols = vec!["abc".to_string(), "cde".to_string(), "efg".to_string()];
+        let msg = CSMessageRequest::new(
+            NetworkAddress::from_str("0x1.ETH/0xa").unwrap(),
+            Addr::unchecked("cx0000000000000000000000000000000000000102"),
+            21,
+            MessageType::CallMessage,
+            hex::decode("74657374").unwrap(),
+            protocols,
+        )
+        .with_threshold(Some(2));
+
+        let decoded: CSMessageRequest = rlp::decode(&msg.as_bytes()).unwrap();
+        assert_eq!(decoded.expiry(), &None);
+        assert_eq!(decoded.gas_limit(), None);
+        assert_eq!(decoded.threshold(), Some(2));
+        assert_eq!(msg, decoded);
+    }
+
+    #[test]
+    fn test_csmessage_request_with_ordered_sn() {
+        let msg = CSMessageRequest::new(
+            NetworkAddress::from_str("0x1.ETH/0xa").unwrap(),
+            Addr::unchecked("cx0000000000000000000000000000000000000102"),
+            21,
+            MessageType::CallMessage,
+            hex::decode("74657374").unwrap(),
+            vec![],
+        )
+        .with_ordered_sn(Some(3));
+
+        let decoded: CSMessageRequest = rlp::decode(&msg.as_bytes()).unwrap();
+        assert_eq!(decoded.gas_limit(), None);
+        assert_eq!(decoded.threshold(), None);
+        assert_eq!(decoded.ordered_sn(), Some(3));
+        assert_eq!(msg, decoded);
+    }
+
     #[test]
     fn test_network_address() {
         let addr = NetworkAddress::from_str("0x1.ETH/0xa").unwrap();
@@ -24,11 +24,18 @@ impl TryFrom<u8> for CallServiceResponseType {
     }
 }
 
+/// Number of fields of a result carrying at most one reply, as understood by every chain.
+const LEGACY_FIELD_COUNT: usize = 3;
+
 #[cw_serde]
 pub struct CSMessageResult {
     sequence_no: u128,
     response_code: CallServiceResponseType,
     message: Vec<u8>,
+    /// The encoded replies that follow `message`, in the order they were sent. Encoded as an
+    /// extra field, only when there is more than one reply.
+    #[serde(default)]
+    additional_replies: Vec<Vec<u8>>,
 }
 
 impl CSMessageResult {
@@ -41,9 +48,18 @@ impl CSMessageResult {
             sequence_no,
             response_code,
             message: reply.unwrap_or_default(),
+            additional_replies: vec![],
         }
     }
 
+    /// Carries the encoded `replies` in the order they are given, replacing any reply set before.
+    pub fn with_replies(mut self, replies: Vec<Vec<u8>>) -> Self {
+        let mut replies = replies.into_iter();
+        self.message = replies.next().unwrap_or_default();
+        self.additional_replies = replies.collect();
+        self
+    }
+
     pub fn sequence_no(&self) -> u128 {
         self.sequence_no
     }
@@ -52,6 +68,16 @@ impl CSMessageResult {
         &self.response_code
     }
 
+    /// The encoded reply request, empty when the result carries none.
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    /// The encoded replies that follow the one returned by `message`.
+    pub fn additional_replies(&self) -> &[Vec<u8>] {
+        &self.additional_replies
+    }
+
     pub fn set_fields(&mut self, sequence_no: u128, response_code: CallServiceResponseType) {
         self.sequence_no.clone_from(&sequence_no);
         self.response_code = response_code;
@@ -64,6 +90,18 @@ impl CSMessageResult {
         rlp::decode(&self.message).ok()
     }
 
+    /// Returns every reply request carried by the result, in the order they were sent. Replies
+    /// that cannot be decoded are skipped, like in `get_message`.
+    pub fn get_messages(&self) -> Vec<CSMessageRequest> {
+        let mut replies: Vec<CSMessageRequest> = self.get_message().into_iter().collect();
+        replies.extend(
+            self.additional_replies
+                .iter()
+                .filter_map(|reply| rlp::decode(reply).ok()),
+        );
+        replies
+    }
+
     pub fn as_bytes(&self) -> Vec<u8> {
         rlp::encode(&self.clone()).to_vec()
     }
@@ -73,22 +111,37 @@ impl Encodable for CSMessageResult {
     fn rlp_append(&self, stream: &mut rlp::RlpStream) {
         let code: u8 = self.response_code.clone().into();
 
+        let field_count = match self.additional_replies.is_empty() {
+            true => LEGACY_FIELD_COUNT,
+            false => LEGACY_FIELD_COUNT + 1,
+        };
         stream
-            .begin_list(3)
+            .begin_list(field_count)
             .append(&self.sequence_no())
             .append(&code)
             .append(&self.message);
+        if !self.additional_replies.is_empty() {
+            stream.begin_list(self.additional_replies.len());
+            for reply in self.additional_replies.iter() {
+                stream.append(reply);
+            }
+        }
     }
 }
 
 impl Decodable for CSMessageResult {
     fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
         let code: u8 = rlp.val_at(1)?;
+        let additional_replies = match rlp.item_count()? > LEGACY_FIELD_COUNT {
+            true => rlp.at(LEGACY_FIELD_COUNT)?.as_list()?,
+            false => vec![],
+        };
 
         Ok(Self {
             sequence_no: rlp.val_at(0)?,
             response_code: CallServiceResponseType::try_from(code)?,
             message: rlp.val_at(2).unwrap_or_default(),
+            additional_replies,
         })
     }
 }
@@ -147,4 +200,25 @@ mod tests {
 
         assert_eq!("c3020080", hex::encode(encoded));
     }
+
+    #[test]
+    fn test_cs_message_result_with_replies() {
+        let single =
+            CSMessageResult::new(1, CallServiceResponseType::CallServiceResponseSuccess, None)
+                .with_replies(vec![vec![1, 2]]);
+        let legacy = CSMessageResult::new(
+            1,
+            CallServiceResponseType::CallServiceResponseSuccess,
+            Some(vec![1, 2]),
+        );
+        assert_eq!(rlp::encode(&single), rlp::encode(&legacy));
+
+        let result =
+            CSMessageResult::new(1, CallServiceResponseType::CallServiceResponseSuccess, None)
+                .with_replies(vec![vec![1, 2], vec![3], vec![4, 5]]);
+        let decoded: CSMessageResult = rlp::decode(&rlp::encode(&result)).unwrap();
+        assert_eq!(decoded.message(), &[1, 2]);
+        assert_eq!(decoded.additional_replies(), &[vec![3], vec![4, 5]]);
+        assert_eq!(decoded, result);
+    }
 }
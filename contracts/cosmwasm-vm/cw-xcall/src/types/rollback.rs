@@ -1,4 +1,4 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Timestamp};
 use cw_xcall_lib::network_address::NetworkAddress;
 
 use super::*;
@@ -11,6 +11,12 @@ pub struct Rollback {
     protocols: Vec<String>,
     rollback: Vec<u8>,
     enabled: bool,
+    #[serde(default)]
+    result_callback: bool,
+    #[serde(default)]
+    threshold: Option<u32>,
+    #[serde(default)]
+    sent_at: Option<Timestamp>,
 }
 
 impl Rollback {
@@ -27,9 +33,27 @@ impl Rollback {
             rollback,
             protocols,
             enabled,
+            result_callback: false,
+            threshold: None,
+            sent_at: None,
         }
     }
 
+    pub fn with_threshold(mut self, threshold: Option<u32>) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn with_sent_at(mut self, sent_at: Option<Timestamp>) -> Self {
+        self.sent_at = sent_at;
+        self
+    }
+
+    pub fn with_result_callback(mut self, result_callback: bool) -> Self {
+        self.result_callback = result_callback;
+        self
+    }
+
     pub fn from(&self) -> &Addr {
         &self.from
     }
@@ -46,6 +70,18 @@ impl Rollback {
         self.enabled
     }
 
+    pub fn result_callback(&self) -> bool {
+        self.result_callback
+    }
+
+    pub fn threshold(&self) -> Option<u32> {
+        self.threshold
+    }
+
+    pub fn sent_at(&self) -> Option<Timestamp> {
+        self.sent_at
+    }
+
     pub fn protocols(&self) -> &Vec<String> {
         &self.protocols
     }
@@ -14,10 +14,39 @@ pub enum StorageKey {
     Connections,
     PendingRequests,
     PendingResponses,
+    PendingRequestDeliveries,
+    PendingResponseDeliveries,
+    PendingDeliveryTtl,
     SuccessfulResponses,
     Config,
     Callbackdata,
-    CallReply,
+    CallReplies,
+    MessageStatus,
+    GlobalPause,
+    NetworkPause,
+    PausedMessages,
+    PausedMessageId,
+    PausedMessageCounts,
+    PayloadLimits,
+    NetworkPayloadLimits,
+    FeeDenoms,
+    FeeBalances,
+    FeeSponsors,
+    FeeUsage,
+    FeeUsageCount,
+    OrderedSentSequences,
+    OrderedDeliveredSequences,
+    OrderedBuffer,
+    InboundPolicies,
+    DappConnections,
+    Roles,
+    PendingAdmin,
+    RollbackGracePeriod,
+    SourceHeights,
+    ExtendedMessageNetworks,
+    SendingSn,
+    MessageConnSns,
+    BackfillCursor,
 }
 
 impl StorageKey {
@@ -35,10 +64,39 @@ impl StorageKey {
             StorageKey::Connections => "connections",
             StorageKey::PendingRequests => "pending_requests",
             StorageKey::PendingResponses => "pending_responses",
+            StorageKey::PendingRequestDeliveries => "pending_request_deliveries",
+            StorageKey::PendingResponseDeliveries => "pending_response_deliveries",
+            StorageKey::PendingDeliveryTtl => "pending_delivery_ttl",
             StorageKey::SuccessfulResponses => "successful_responses",
             StorageKey::Config => "config",
             StorageKey::Callbackdata => "callback_data",
-            StorageKey::CallReply => "call_reply",
+            StorageKey::CallReplies => "call_replies",
+            StorageKey::MessageStatus => "message_status",
+            StorageKey::GlobalPause => "global_pause",
+            StorageKey::NetworkPause => "network_pause",
+            StorageKey::PausedMessages => "paused_messages",
+            StorageKey::PausedMessageId => "paused_message_id",
+            StorageKey::PausedMessageCounts => "paused_message_counts",
+            StorageKey::PayloadLimits => "payload_limits",
+            StorageKey::NetworkPayloadLimits => "network_payload_limits",
+            StorageKey::FeeDenoms => "fee_denoms",
+            StorageKey::FeeBalances => "fee_balances",
+            StorageKey::FeeSponsors => "fee_sponsors",
+            StorageKey::FeeUsage => "fee_usage",
+            StorageKey::FeeUsageCount => "fee_usage_count",
+            StorageKey::OrderedSentSequences => "ordered_sent_sequences",
+            StorageKey::OrderedDeliveredSequences => "ordered_delivered_sequences",
+            StorageKey::OrderedBuffer => "ordered_buffer",
+            StorageKey::InboundPolicies => "inbound_policies",
+            StorageKey::DappConnections => "dapp_connections",
+            StorageKey::Roles => "roles",
+            StorageKey::PendingAdmin => "pending_admin",
+            StorageKey::RollbackGracePeriod => "rollback_grace_period",
+            StorageKey::SourceHeights => "source_heights",
+            StorageKey::ExtendedMessageNetworks => "extended_message_networks",
+            StorageKey::SendingSn => "sending_sn",
+            StorageKey::MessageConnSns => "message_conn_sns",
+            StorageKey::BackfillCursor => "backfill_cursor",
         }
     }
 }
@@ -4,8 +4,12 @@ use account::*;
 use cosmwasm_std::{testing::mock_env, Addr};
 
 use cw_xcall::state::CwCallService;
-use cw_xcall_lib::xcall_msg::ExecuteMsg;
+use cw_xcall_lib::{
+    network_address::NetId,
+    xcall_msg::{ExecuteMsg, Role},
+};
 use setup::test::*;
+use std::str::FromStr;
 
 #[test]
 #[should_panic(expected = "OnlyAdmin")]
@@ -242,3 +246,185 @@ fn validate_address_set_admin_size_more_than_45() {
         )
         .unwrap();
 }
+
+#[test]
+fn set_admin_takes_effect_once_accepted() {
+    let mut mock_deps = deps();
+    let mock_env = mock_env();
+    let mut contract = CwCallService::default();
+
+    contract
+        .set_admin(
+            mock_deps.as_mut().storage,
+            Addr::unchecked(admin_one().to_string()),
+        )
+        .unwrap();
+
+    contract
+        .execute(
+            mock_deps.as_mut(),
+            mock_env.clone(),
+            create_mock_info(&admin_one().to_string(), "umlg", 0),
+            ExecuteMsg::SetAdmin {
+                address: admin_two().to_string(),
+            },
+        )
+        .unwrap();
+    let result = contract.query_admin(mock_deps.as_ref().storage).unwrap();
+    assert_eq!(result, admin_one().to_string());
+
+    let err = contract
+        .execute(
+            mock_deps.as_mut(),
+            mock_env.clone(),
+            create_mock_info(&alice().to_string(), "umlg", 0),
+            ExecuteMsg::AcceptAdmin {},
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "OnlyPendingAdmin");
+
+    contract
+        .execute(
+            mock_deps.as_mut(),
+            mock_env,
+            create_mock_info(&admin_two().to_string(), "umlg", 0),
+            ExecuteMsg::AcceptAdmin {},
+        )
+        .unwrap();
+    let result = contract.query_admin(mock_deps.as_ref().storage).unwrap();
+    assert_eq!(result, admin_two().to_string());
+    assert!(contract
+        .get_pending_admin(mock_deps.as_ref().storage)
+        .is_none());
+}
+
+#[test]
+fn grant_revoke_and_renounce_role() {
+    let mut mock_deps = deps();
+    let mock_env = mock_env();
+    let mut contract = CwCallService::default();
+    let admin_info = create_mock_info(&admin_one().to_string(), "umlg", 0);
+    let manager_info = create_mock_info(&alice().to_string(), "umlg", 0);
+
+    contract
+        .set_admin(mock_deps.as_mut().storage, admin_info.sender.clone())
+        .unwrap();
+
+    let set_connection = ExecuteMsg::SetDefaultConnection {
+        nid: NetId::from_str("archway").unwrap(),
+        address: Addr::unchecked("centralized"),
+    };
+    let err = contract
+        .execute(
+            mock_deps.as_mut(),
+            mock_env.clone(),
+            manager_info.clone(),
+            set_connection.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "MissingRole connection_manager");
+
+    contract
+        .execute(
+            mock_deps.as_mut(),
+            mock_env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::GrantRole {
+                role: Role::ConnectionManager,
+                address: alice().to_string(),
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            mock_deps.as_mut(),
+            mock_env.clone(),
+            manager_info.clone(),
+            set_connection.clone(),
+        )
+        .unwrap();
+    assert_eq!(
+        contract
+            .get_role_members(mock_deps.as_ref().storage, &Role::ConnectionManager)
+            .unwrap(),
+        vec![alice().to_string()]
+    );
+
+    contract
+        .execute(
+            mock_deps.as_mut(),
+            mock_env.clone(),
+            manager_info.clone(),
+            ExecuteMsg::RenounceRole {
+                role: Role::ConnectionManager,
+            },
+        )
+        .unwrap();
+    let err = contract
+        .execute(
+            mock_deps.as_mut(),
+            mock_env.clone(),
+            manager_info.clone(),
+            set_connection,
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "MissingRole connection_manager");
+
+    contract
+        .execute(
+            mock_deps.as_mut(),
+            mock_env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::GrantRole {
+                role: Role::FeeManager,
+                address: alice().to_string(),
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            mock_deps.as_mut(),
+            mock_env.clone(),
+            admin_info,
+            ExecuteMsg::RevokeRole {
+                role: Role::FeeManager,
+                address: alice().to_string(),
+            },
+        )
+        .unwrap();
+    let err = contract
+        .execute(
+            mock_deps.as_mut(),
+            mock_env,
+            manager_info,
+            ExecuteMsg::SetProtocolFee { value: 10 },
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "MissingRole fee_manager");
+}
+
+#[test]
+#[should_panic(expected = "OnlyAdmin")]
+fn grant_role_unauthorized() {
+    let mut mock_deps = deps();
+    let mut contract = CwCallService::default();
+
+    contract
+        .set_admin(
+            mock_deps.as_mut().storage,
+            Addr::unchecked(admin_one().to_string()),
+        )
+        .unwrap();
+
+    contract
+        .execute(
+            mock_deps.as_mut(),
+            mock_env(),
+            create_mock_info(&alice().to_string(), "umlg", 0),
+            ExecuteMsg::GrantRole {
+                role: Role::Pauser,
+                address: alice().to_string(),
+            },
+        )
+        .unwrap();
+}
@@ -69,6 +69,7 @@ fn send_packet_by_non_contract_and_rollback_data_is_not_null() {
             Some(vec![1, 2, 3]),
             vec![],
             vec![],
+            None,
         )
         .unwrap();
 }
@@ -136,6 +137,7 @@ fn send_packet_failure_due_data_len() {
         Some(vec![]),
            vec![],
            vec![],
+           None,
         )
         .unwrap();
 }
@@ -202,6 +204,7 @@ fn send_packet_failure_due_rollback_len() {
             Some("HuykcBsssssTXfpMmbwWx9COZWbuMkecnMTGI54oXFBsSFOypVHuiBT2egh0drcRAS4wQyGxOCGhL8mBWTttEOG88kuvDcF5R5OmhBa1beo46i9IwD56OqhpzCOVxJqF87fhctAymhmMSBWA95gCnNP45If5FfFtRIsiU9fkwqYPRKCpjtwsFcYJSB3fmABDfsiQBT3rCjvWybzrdN3NoS4VHT3sKuzVeOTNSHDGZaztEpRqBBX1NgNMdky63xfCcslBujryZIbT3xFOXQTzhmCqypqCBsfE2IKbRpZ4zjJjRHhK9e2H2EThk0huP7QVKkHJ2UECyj8QahqvqwtK3QOV8PN1lQmaLV8gtKuBEQalQScHopXOCbeSZgrGRE0r447i7ppCLi6PbX3qja1R3UxMQ2mTIqZRwAsqFHazl7hjchqKkLKrbc0YRz3egQdZi55c7BBpvwGLvEeHUFH4qrSbZ6oRHOJfyWaBtTsoZzjAApSL94EFcFjZV7b5ImDt1uvCy8lGULMig8D8XWcdYQWdlMYwvStzzDpqBU2tw1dX9omD7IJNcBnYNQEXtiEGDdhnCDF9z6lxH0JHG9ZepbiMKi1bduhZrUR51gkqNPT3JxziAlN2xuaM9f3TVIqNLI9IjJYAFNIqe4IZ7qfJCSIoDj2Tq0wJrEkXgW8kAheMzvmOVglr1SlSo3uweVaOgfGbwANak39MtplyksgH8GGgSv0k3ghLHeT06HbKt6MCVCi5fcFLXuCa0HZt7Dslg601YqJn36Hw031ObkJf1HFoNf8mdLHjCfDCXaUwWY9owqmYDL39Jh46P80sXa4u1IqKUfrFMmmCpF7MaVvtdMsJelz2zZHZUPSiC38xfUkOdcgRVcLVBv8GSKcqrMGo9QZs2fu9Zi25WuSZ0SzRo61TjBpRXm1MypIDnxTTEMMBA7l9L7TeojRak80SXhKGx1Pj4AKKNGiKYeIhyx3eSL1JzXmW9qABN6ex1MK4v8pdViMszPgWjeAL95hIWZHuQRMQkTW6A8zBIltmBrM7HAVXbgvMEN48MiacvF7uyC4ogptOw2M01RPX5vgrYS0uXiNUe3AkkPM52z73t6zcNtB1ey1p99HlvVi7ESkPfwQ6MWI2M0bJjru9qYll61idDW3H05v7fFtGg8Ic0MyMbzSX115GIMn6wadHubyaOLNCTJzsApcwuVDUb7uYxRkb53ZP4vVKbPqGugcQojjq22rYNTJt0frigyQYpXm8F1B06VcHnUj460kXEXrpep7UkPaRX5qloF5csnqStuutf9lDPSX8Yrfy6ptdS6FLys0gJpJvR1cDc2h1AfKYyRkflHWUShpJlyrxF4bsOR42vu5ZzX1OQZJaTMaiq1K8IlgzEIFzj9NVji7t26iIgtiZnq17twaw97L3U0I2RlVV6xF9oE27uF08ttTQ0D33VnrzeYBfyrHfrjouf44igELGwolxamYgmaT6NqWhLW45juzqmklNt33DoFRYfMImRrnAbh5zR20XLWAgspPDXgdd52b1sclR6DbAa43wQgdHpoPhSnYCGszGrN2vR1kyMRb32wf37BA725rcOBvfhQSFzNtTk1IqYDyPGUPsZTSknUq4oBRTFJhfzDMh6xy6950EyNAsfUd471kIFvg2dpprhbStY92ftm5TAiAorUXRCljzzU5hfJ6NQinsCmDSRcadtlgn1uThvdqi62xcmDlWDvCf5nKrmad1e3SEmyo99TjjoZXMMPtiGbq9YLEp96JP1TTlcPLHuDewAJNDjQN3ZYg0zQM6b1F3cAD5AgP8ZZc8pK1lJph05YzzV4Lindpx99zewUinVS60ipj4hKbQWNCJhlQCWXPURXI7J8RoLC9leZCqOyPMYEF0tosVmtA4yn1Vup7LJP8DhZ5Br5M0oFGPzmlBSztMj9Gpp0bHnqby5q4elF3KTncyAFDv5xtlN4pxFgclB22aCaT2j7BvNTgaLPQEfH1NQY3fdDzpQbAbGzdLjo77RbClagKYH2iCnq0lg885jMiavPL1NMtqOKPFc".to_string().as_bytes().to_vec()),
              vec![],
              vec![],
+             None,
         )
         .unwrap();
 }
@@ -270,6 +273,7 @@ fn send_packet_success_needresponse() {
             Some(vec![1, 2, 3]),
             vec![],
             vec![],
+            None,
         )
         .unwrap();
 
@@ -345,6 +349,7 @@ fn send_packet_fail_insufficient_funds() {
             Some(vec![1, 2, 3]),
             vec![],
             vec![],
+            None,
         )
         .unwrap();
 
@@ -373,12 +378,73 @@ fn test_send_message_on_reply_state() {
     );
 
     let res = contract
-        .send_call(deps.as_mut(), ctx.info, from, envelope)
+        .send_call(deps.as_mut(), ctx.info, ctx.env, from, envelope, None)
         .unwrap();
     assert_eq!(res.attributes[0].value, "xcall-service");
     assert_eq!(res.attributes[1].value, "send_packet");
 }
 
+#[test]
+fn test_send_multiple_replies_on_reply_state() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_reply_state(deps.as_mut().storage, &contract);
+
+    let from = ctx.request_message.unwrap().from().clone();
+    contract
+        .store_extended_messages(deps.as_mut().storage, from.nid(), true)
+        .unwrap();
+    for data in [vec![1], vec![2]] {
+        let envelope = Envelope::new(
+            AnyMessage::CallMessagePersisted(CallMessagePersisted { data }),
+            vec![],
+            vec![],
+        );
+        contract
+            .send_call(
+                deps.as_mut(),
+                ctx.info.clone(),
+                ctx.env.clone(),
+                from.clone(),
+                envelope,
+                None,
+            )
+            .unwrap();
+    }
+
+    let replies = contract.get_call_replies(deps.as_ref().storage);
+    let data: Vec<Vec<u8>> = replies
+        .iter()
+        .map(|reply| reply.data().unwrap().to_vec())
+        .collect();
+    assert_eq!(data, vec![vec![1], vec![2]]);
+}
+
+#[test]
+fn test_is_reply_returns_false_after_first_reply_without_extended_messages() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_reply_state(deps.as_mut().storage, &contract);
+
+    let request = ctx.request_message.unwrap();
+    let protocols = request.protocols().clone();
+    assert!(contract.is_reply(deps.as_ref(), request.from().nid(), &protocols));
+
+    contract
+        .push_call_reply(deps.as_mut().storage, &request)
+        .unwrap();
+    assert!(!contract.is_reply(deps.as_ref(), request.from().nid(), &protocols));
+
+    contract
+        .store_extended_messages(deps.as_mut().storage, request.from().nid(), true)
+        .unwrap();
+    assert!(contract.is_reply(deps.as_ref(), request.from().nid(), &protocols));
+}
+
 #[test]
 fn test_is_reply_returns_false_on_mismatch_network_id() {
     let mut deps = deps();
@@ -2,26 +2,38 @@ mod account;
 mod setup;
 
 use common::utils::keccak256;
-use cw2::{get_contract_version, ContractVersion};
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use cw_xcall::MigrateMsg;
 use setup::{test::*, *};
 use std::str::FromStr;
 
 use cosmwasm_std::{
-    testing::{mock_env, MOCK_CONTRACT_ADDR},
-    to_json_binary, Addr, Event, Reply, SubMsgResponse, SubMsgResult,
+    coin, coins, from_json,
+    testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR},
+    to_json_binary, Addr, BankMsg, CosmosMsg, Decimal, Event, Reply, ReplyOn, SubMsgResponse,
+    SubMsgResult, WasmMsg,
 };
 use cw_xcall::{
     execute, instantiate, migrate,
     msg::{InstantiateMsg, QueryMsg},
     query, reply,
-    state::CwCallService,
-    types::{request::CSMessageRequest, rollback::Rollback},
+    state::{CwCallService, PAYLOAD_LIMIT_CEILING, SEND_CALL_MESSAGE_REPLY_ID},
+    types::{
+        message::{CSMessage, CSMessageType},
+        request::CSMessageRequest,
+        result::{CSMessageResult, CallServiceResponseType},
+        rollback::Rollback,
+    },
 };
 use cw_xcall_lib::{
     message::msg_type::MessageType,
     network_address::{NetId, NetworkAddress},
+    xcall_connection_msg,
     xcall_msg::ExecuteMsg,
+    xcall_query_msg::{
+        DecodedCsMessage, DecodedMessageType, FeeUsage, PayloadLimits, ProxyRequestResponse,
+        ProxyRequestsResponse, RollbackResponse, RollbacksResponse,
+    },
 };
 
 #[test]
@@ -77,7 +89,8 @@ fn test_migrate() {
     const CONTRACT_NAME: &str = "crates.io:cw-xcall";
     const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-    migrate(deps.as_mut(), ctx.env, MigrateMsg {}).unwrap();
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "0.2.1").unwrap();
+    migrate(deps.as_mut(), ctx.env, MigrateMsg::default()).unwrap();
     let expected = ContractVersion {
         contract: CONTRACT_NAME.to_string(),
         version: CONTRACT_VERSION.to_string(),
@@ -163,6 +176,7 @@ fn test_execute_send_call_message() {
         rollback: None,
         sources: Some(vec![]),
         destinations: Some(vec![]),
+        max_fee: None,
     };
 
     mock_connection_fee_query(&mut deps);
@@ -187,6 +201,7 @@ fn test_execute_send_call() {
     let msg = ExecuteMsg::SendCall {
         envelope,
         to: get_dummy_network_address("archway"),
+        max_fee: None,
     };
     let res = execute(deps.as_mut(), ctx.env, ctx.info, msg).unwrap();
     assert_eq!(res.attributes[0].value, "xcall-service");
@@ -368,7 +383,7 @@ fn test_execute_call_reply() {
         .store_proxy_request(deps.as_mut().storage, ctx.request_id, &request)
         .unwrap();
     contract
-        .save_call_reply(deps.as_mut().storage, &ctx.request_message.unwrap())
+        .push_call_reply(deps.as_mut().storage, &ctx.request_message.unwrap())
         .unwrap();
     contract
         .store_execute_request_id(deps.as_mut().storage, ctx.request_id)
@@ -387,3 +402,699 @@ fn test_execute_call_reply() {
         .unwrap();
     assert_eq!(res.attributes[1].value, "execute_callback")
 }
+
+#[test]
+fn test_query_pending_rollbacks() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    for sn in 1..=3 {
+        let (dapp, nid) = if sn == 2 {
+            ("other", "evm")
+        } else {
+            ("dapp", "archway")
+        };
+        let rollback = Rollback::new(
+            Addr::unchecked(dapp),
+            get_dummy_network_address(nid),
+            vec![],
+            vec![1, 2, 3],
+            false,
+        );
+        contract
+            .store_call_request(deps.as_mut().storage, sn, &rollback)
+            .unwrap();
+    }
+
+    let msg = QueryMsg::GetPendingRollbacks {
+        start_after: None,
+        limit: None,
+        dapp: Some("dapp".to_string()),
+        nid: None,
+    };
+    let res: RollbacksResponse =
+        from_json(query(deps.as_ref(), ctx.env.clone(), msg).unwrap()).unwrap();
+    let sns: Vec<u128> = res.rollbacks.iter().map(|r| r.sn).collect();
+    assert_eq!(sns, vec![1, 3]);
+
+    let msg = QueryMsg::GetPendingRollbacks {
+        start_after: Some(1),
+        limit: Some(1),
+        dapp: None,
+        nid: None,
+    };
+    let res: RollbacksResponse =
+        from_json(query(deps.as_ref(), ctx.env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.rollbacks.len(), 1);
+    assert_eq!(res.rollbacks[0].sn, 2);
+
+    let msg = QueryMsg::GetPendingRollbacks {
+        start_after: None,
+        limit: None,
+        dapp: None,
+        nid: Some(NetId::from_str("evm").unwrap()),
+    };
+    let res: RollbacksResponse =
+        from_json(query(deps.as_ref(), ctx.env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.rollbacks.len(), 1);
+    assert_eq!(res.rollbacks[0].from, Addr::unchecked("other"));
+
+    let msg = QueryMsg::GetRollback { sn: 3 };
+    let res: RollbackResponse = from_json(query(deps.as_ref(), ctx.env, msg).unwrap()).unwrap();
+    assert_eq!(res.rollback, vec![1, 2, 3]);
+    assert!(!res.enabled);
+}
+
+#[test]
+fn test_query_proxy_requests() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_execute_call(deps.as_mut().storage, &contract);
+
+    let msg = QueryMsg::GetProxyRequests {
+        start_after: None,
+        limit: None,
+        dapp: Some("dapp".to_string()),
+        nid: Some(NetId::from_str("archway").unwrap()),
+    };
+    let res: ProxyRequestsResponse =
+        from_json(query(deps.as_ref(), ctx.env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.requests.len(), 1);
+    assert_eq!(res.requests[0].req_id, ctx.request_id);
+    assert_eq!(res.requests[0].data_hash, keccak256(&[1, 2, 3]).to_vec());
+
+    let msg = QueryMsg::GetProxyRequests {
+        start_after: None,
+        limit: None,
+        dapp: None,
+        nid: Some(NetId::from_str("evm").unwrap()),
+    };
+    let res: ProxyRequestsResponse =
+        from_json(query(deps.as_ref(), ctx.env.clone(), msg).unwrap()).unwrap();
+    assert!(res.requests.is_empty());
+
+    let msg = QueryMsg::GetProxyRequest {
+        req_id: ctx.request_id,
+    };
+    let res: ProxyRequestResponse = from_json(query(deps.as_ref(), ctx.env, msg).unwrap()).unwrap();
+    assert_eq!(res.msg_type, MessageType::CallMessage);
+    assert_eq!(res.to, Addr::unchecked("dapp"));
+}
+
+#[test]
+fn test_query_decode_cs_message() {
+    let deps = deps();
+    let ctx = TestContext::default();
+
+    let request = get_dummy_req_msg().with_gas_limit(Some(100_000));
+    let msg = QueryMsg::DecodeCsMessage {
+        msg: CSMessage::from(request).as_bytes().into(),
+    };
+    let res: DecodedCsMessage =
+        from_json(query(deps.as_ref(), ctx.env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.message_type, DecodedMessageType::RequestV2);
+    assert!(res.result.is_none());
+    let request = res.request.unwrap();
+    assert_eq!(request.from, get_dummy_network_address("archway"));
+    assert_eq!(request.to, "dapp");
+    assert_eq!(request.sn, 1);
+    assert_eq!(request.msg_type, MessageType::CallMessage);
+    assert_eq!(request.data, keccak256(&[1, 2, 3]).to_vec());
+    assert_eq!(request.gas_limit, Some(100_000));
+
+    let msg = QueryMsg::DecodeCsMessage {
+        msg: CSMessage::from(get_dummy_result_message())
+            .as_bytes()
+            .into(),
+    };
+    let res: DecodedCsMessage = from_json(query(deps.as_ref(), ctx.env, msg).unwrap()).unwrap();
+    assert_eq!(res.message_type, DecodedMessageType::Result);
+    let result = res.result.unwrap();
+    assert_eq!(result.sn, 1);
+    assert_eq!(result.code, 1);
+    assert_eq!(result.reply.unwrap().to, "dapp");
+}
+
+#[test]
+fn test_query_decode_cs_message_reports_failing_part() {
+    let deps = deps();
+    let ctx = TestContext::default();
+
+    let msg = QueryMsg::DecodeCsMessage {
+        msg: vec![1, 2, 3].into(),
+    };
+    let err = query(deps.as_ref(), ctx.env.clone(), msg).unwrap_err();
+    assert!(err.to_string().contains("DecodeFailed message:"));
+
+    let result = CSMessage::new(CSMessageType::CSMessageResult, vec![0xc0]);
+    let msg = QueryMsg::DecodeCsMessage {
+        msg: result.as_bytes().into(),
+    };
+    let err = query(deps.as_ref(), ctx.env.clone(), msg).unwrap_err();
+    assert!(err.to_string().contains("DecodeFailed result:"));
+
+    let result = CSMessageResult::new(
+        1,
+        CallServiceResponseType::CallServiceResponseSuccess,
+        Some(vec![0xc1, 0x01]),
+    );
+    let msg = QueryMsg::DecodeCsMessage {
+        msg: CSMessage::from(result).as_bytes().into(),
+    };
+    let err = query(deps.as_ref(), ctx.env, msg).unwrap_err();
+    assert!(err.to_string().contains("DecodeFailed reply:"));
+}
+
+#[test]
+fn test_query_payload_limits() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let msg = ExecuteMsg::SetPayloadLimits {
+        nid: Some(NetId::from_str("archway").unwrap()),
+        max_data_size: 4096,
+        max_rollback_size: 512,
+    };
+    execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), msg).unwrap();
+
+    let query_limits = |nid: Option<&str>| -> PayloadLimits {
+        let msg = QueryMsg::GetPayloadLimits {
+            nid: nid.map(|n| NetId::from_str(n).unwrap()),
+        };
+        from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+    };
+
+    let limits = query_limits(Some("archway"));
+    assert_eq!(limits.max_data_size, 4096);
+    assert_eq!(limits.max_rollback_size, 512);
+
+    let limits = query_limits(Some("ethereum"));
+    assert_eq!(limits.max_data_size, 2048);
+    assert_eq!(limits.max_rollback_size, 1024);
+    assert_eq!(query_limits(None), limits);
+}
+
+#[test]
+#[should_panic(expected = "MaxDataSizeExceeded")]
+fn test_send_call_fail_on_network_payload_limit() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    mock_connection_fee_query(&mut deps);
+
+    let msg = ExecuteMsg::SetPayloadLimits {
+        nid: Some(NetId::from_str("archway").unwrap()),
+        max_data_size: 2,
+        max_rollback_size: 2,
+    };
+    execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::SendCall {
+        envelope: get_dummy_call_msg_envelop(),
+        to: get_dummy_network_address("archway"),
+        max_fee: None,
+    };
+    execute(deps.as_mut(), ctx.env, ctx.info, msg).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "OnlyAdmin")]
+fn test_set_payload_limits_fail_on_non_admin() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let msg = ExecuteMsg::SetPayloadLimits {
+        nid: None,
+        max_data_size: 4096,
+        max_rollback_size: 4096,
+    };
+    let info = create_mock_info("user", "icx", 0);
+    execute(deps.as_mut(), ctx.env, info, msg).unwrap();
+}
+
+#[test]
+fn test_send_call_paid_in_accepted_fee_denom() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    mock_connection_fee_query(&mut deps);
+
+    let msg = ExecuteMsg::SetFeeDenom {
+        denom: "uusdc".to_string(),
+        rate: Decimal::from_str("2.5").unwrap(),
+    };
+    execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), msg).unwrap();
+
+    let fee: u128 = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetFee {
+                nid: NetId::from_str("archway").unwrap(),
+                rollback: false,
+                sources: None,
+                denom: Some("uusdc".to_string()),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(fee, 25);
+
+    let msg = ExecuteMsg::SendCall {
+        envelope: get_dummy_call_msg_envelop(),
+        to: get_dummy_network_address("archway"),
+        max_fee: None,
+    };
+    let info = create_mock_info("user", "uusdc", 100);
+    let res = execute(deps.as_mut(), ctx.env, info, msg).unwrap();
+
+    match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { funds, .. }) => {
+            assert_eq!(funds, &coins(25, "uusdc"))
+        }
+        _ => panic!("expected connection message"),
+    }
+    match &res.messages[1].msg {
+        CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+            assert_eq!(to_address, "user");
+            assert_eq!(amount, &coins(75, "uusdc"))
+        }
+        _ => panic!("expected refund"),
+    }
+}
+
+#[test]
+#[should_panic(expected = "MultipleFeeDenoms")]
+fn test_send_call_fail_on_multiple_fee_denoms() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    mock_connection_fee_query(&mut deps);
+
+    let msg = ExecuteMsg::SetFeeDenom {
+        denom: "uusdc".to_string(),
+        rate: Decimal::one(),
+    };
+    execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::SendCall {
+        envelope: get_dummy_call_msg_envelop(),
+        to: get_dummy_network_address("archway"),
+        max_fee: None,
+    };
+    let info = mock_info("user", &[coin(100, "icx"), coin(100, "uusdc")]);
+    execute(deps.as_mut(), ctx.env, info, msg).unwrap();
+}
+
+#[test]
+fn test_send_call_splits_protocol_fee_and_refund() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    mock_connection_fee_query(&mut deps);
+
+    let msg = ExecuteMsg::SetProtocolFee { value: 5 };
+    execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::SendCall {
+        envelope: get_dummy_call_msg_envelop(),
+        to: get_dummy_network_address("archway"),
+        max_fee: Some(15),
+    };
+    let info = create_mock_info("user", "icx", 100);
+    let res = execute(deps.as_mut(), ctx.env, info, msg).unwrap();
+
+    assert_eq!(res.messages.len(), 3);
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "admin".to_string(),
+            amount: coins(5, "icx"),
+        })
+    );
+    assert_eq!(
+        res.messages[2].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "user".to_string(),
+            amount: coins(85, "icx"),
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "MaxFeeExceeded")]
+fn test_send_call_fail_on_max_fee_exceeded() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    mock_connection_fee_query(&mut deps);
+
+    let msg = ExecuteMsg::SendCallMessage {
+        to: get_dummy_network_address("archway"),
+        data: vec![1, 2, 3],
+        rollback: None,
+        sources: None,
+        destinations: None,
+        max_fee: Some(9),
+    };
+    execute(deps.as_mut(), ctx.env, ctx.info, msg).unwrap();
+}
+
+#[test]
+fn test_send_call_draws_from_fee_balance() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    mock_connection_fee_query(&mut deps);
+
+    let msg = ExecuteMsg::AddFeeSponsor {
+        sponsor: "sponsor".to_string(),
+    };
+    let dapp_info = create_mock_info("dapp", "icx", 0);
+    execute(deps.as_mut(), ctx.env.clone(), dapp_info, msg).unwrap();
+
+    let msg = ExecuteMsg::DepositFees {
+        dapp: "dapp".to_string(),
+    };
+    let info = create_mock_info("sponsor", "icx", 30);
+    execute(deps.as_mut(), ctx.env.clone(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::SendCall {
+        envelope: get_dummy_call_msg_envelop(),
+        to: get_dummy_network_address("archway"),
+        max_fee: None,
+    };
+    let info = mock_info("dapp", &[coin(4, "icx")]);
+    let res = execute(deps.as_mut(), ctx.env, info, msg).unwrap();
+    assert_eq!(res.messages.len(), 1);
+
+    let balance: u128 = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetFeeBalance {
+                dapp: "dapp".to_string(),
+                denom: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(balance, 24);
+
+    let usage: Vec<FeeUsage> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetFeeUsage {
+                dapp: "dapp".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(usage.len(), 1);
+    assert_eq!(usage[0].sn, 1);
+    assert_eq!(usage[0].amount, 6);
+}
+
+#[test]
+fn test_send_call_draws_from_fee_balance_in_other_denom() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    mock_connection_fee_query(&mut deps);
+
+    let msg = ExecuteMsg::SetFeeDenom {
+        denom: "uusdc".to_string(),
+        rate: Decimal::from_str("2.5").unwrap(),
+    };
+    execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::DepositFees {
+        dapp: "dapp".to_string(),
+    };
+    let info = create_mock_info("dapp", "uusdc", 30);
+    execute(deps.as_mut(), ctx.env.clone(), info, msg).unwrap();
+
+    // the attached funds and the balance in their denom fall short, the fee is drawn in uusdc
+    let msg = ExecuteMsg::SendCall {
+        envelope: get_dummy_call_msg_envelop(),
+        to: get_dummy_network_address("archway"),
+        max_fee: None,
+    };
+    let info = mock_info("dapp", &[coin(4, "icx")]);
+    let res = execute(deps.as_mut(), ctx.env.clone(), info, msg.clone()).unwrap();
+
+    match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { funds, .. }) => {
+            assert_eq!(funds, &coins(25, "uusdc"))
+        }
+        _ => panic!("expected connection message"),
+    }
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "dapp".to_string(),
+            amount: coins(4, "icx"),
+        })
+    );
+    assert_eq!(
+        contract.get_fee_balance(deps.as_ref().storage, &Addr::unchecked("dapp"), "uusdc"),
+        5
+    );
+
+    let info = mock_info("dapp", &[]);
+    let err = execute(deps.as_mut(), ctx.env, info, msg).unwrap_err();
+    assert_eq!(err.to_string(), "InsufficientFunds");
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_deposit_fees_fail_on_unknown_sponsor() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let msg = ExecuteMsg::DepositFees {
+        dapp: "dapp".to_string(),
+    };
+    let info = create_mock_info("sponsor", "icx", 30);
+    execute(deps.as_mut(), ctx.env, info, msg).unwrap();
+}
+
+#[test]
+fn test_bump_fee() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    mock_connection_fee_query(&mut deps);
+
+    let rollback = Rollback::new(
+        Addr::unchecked("dapp"),
+        get_dummy_network_address("archway"),
+        vec![],
+        vec![1, 2, 3],
+        false,
+    );
+    contract
+        .store_call_request(deps.as_mut().storage, 1, &rollback)
+        .unwrap();
+
+    let msg = ExecuteMsg::BumpFee {
+        sn: 1,
+        connection: "centralized".to_string(),
+    };
+    let info = create_mock_info("user", "icx", 50);
+    let res = execute(deps.as_mut(), ctx.env.clone(), info.clone(), msg).unwrap();
+
+    let add_fee = xcall_connection_msg::ExecuteMsg::AddFee { conn_sn: 10 };
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "centralized".to_string(),
+            msg: to_json_binary(&add_fee).unwrap(),
+            funds: coins(50, "icx"),
+        })
+    );
+    assert_eq!(res.events[0].ty, "FeeBumped");
+
+    let msg = ExecuteMsg::BumpFee {
+        sn: 1,
+        connection: "ibc".to_string(),
+    };
+    let res = execute(deps.as_mut(), ctx.env, info, msg);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_bump_fee_for_message_without_response() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    mock_connection_fee_query(&mut deps);
+
+    let msg = ExecuteMsg::SendCall {
+        envelope: get_dummy_call_msg_envelop(),
+        to: get_dummy_network_address("archway"),
+        max_fee: None,
+    };
+    let info = create_mock_info("dapp", "icx", 20);
+    let res = execute(deps.as_mut(), ctx.env.clone(), info, msg).unwrap();
+    assert_eq!(res.messages[0].reply_on, ReplyOn::Success);
+
+    let msg = Reply {
+        id: SEND_CALL_MESSAGE_REPLY_ID,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![Event::new("wasm-Message")
+                .add_attribute("_contract_address", "centralized")
+                .add_attribute("targetNetwork", "archway")
+                .add_attribute("connSn", "7")],
+            data: None,
+        }),
+    };
+    reply(deps.as_mut(), ctx.env.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::BumpFee {
+        sn: 1,
+        connection: "centralized".to_string(),
+    };
+    let info = create_mock_info("user", "icx", 50);
+    let res = execute(deps.as_mut(), ctx.env.clone(), info.clone(), msg).unwrap();
+    let add_fee = xcall_connection_msg::ExecuteMsg::AddFee { conn_sn: 7 };
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "centralized".to_string(),
+            msg: to_json_binary(&add_fee).unwrap(),
+            funds: coins(50, "icx"),
+        })
+    );
+
+    // the message was not sent through this connection
+    let msg = ExecuteMsg::BumpFee {
+        sn: 1,
+        connection: "ibc".to_string(),
+    };
+    let err = execute(deps.as_mut(), ctx.env, info, msg).unwrap_err();
+    assert_eq!(err.to_string(), "CallRequest Not Found For 1");
+}
+
+#[test]
+#[should_panic(expected = "InvalidThreshold")]
+fn test_send_call_rejects_threshold_above_protocols() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    mock_connection_fee_query(&mut deps);
+
+    let protocols = vec!["centralized".to_string(), "ibc".to_string()];
+    let mut envelope = get_dummy_call_msg_envelop().with_threshold(3);
+    envelope.sources = protocols.clone();
+    envelope.destinations = protocols;
+    let msg = ExecuteMsg::SendCall {
+        envelope,
+        to: get_dummy_network_address("archway"),
+        max_fee: None,
+    };
+    execute(deps.as_mut(), ctx.env, ctx.info, msg).unwrap();
+}
+
+#[test]
+fn test_set_payload_limits_fail_on_invalid_limits() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    for (max_data_size, max_rollback_size) in
+        [(0, 1024), (2048, 0), (PAYLOAD_LIMIT_CEILING + 1, 1024)]
+    {
+        let msg = ExecuteMsg::SetPayloadLimits {
+            nid: None,
+            max_data_size,
+            max_rollback_size,
+        };
+        let err = execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), msg).unwrap_err();
+        assert_eq!(err.to_string(), "InvalidPayloadLimits");
+    }
+}
+
+#[test]
+fn test_failure_response_enables_rollback_above_lowered_limit() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let rollback = Rollback::new(
+        Addr::unchecked("xcall"),
+        get_dummy_network_address("archway"),
+        vec![],
+        vec![1, 2, 3],
+        false,
+    );
+    contract
+        .store_call_request(deps.as_mut().storage, 1, &rollback)
+        .unwrap();
+
+    let msg = ExecuteMsg::SetPayloadLimits {
+        nid: Some(NetId::from_str("archway").unwrap()),
+        max_data_size: 2048,
+        max_rollback_size: 1,
+    };
+    execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), msg).unwrap();
+
+    let result = CSMessageResult::new(1, CallServiceResponseType::CallServiceResponseFailure, None);
+    contract
+        .handle_result(
+            deps.as_mut(),
+            ctx.env,
+            create_mock_info("centralized", "icx", 0),
+            &result.as_bytes(),
+        )
+        .unwrap();
+
+    assert!(contract
+        .get_call_request(deps.as_ref().storage, 1)
+        .unwrap()
+        .enabled());
+}
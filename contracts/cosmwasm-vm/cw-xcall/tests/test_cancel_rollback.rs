@@ -0,0 +1,187 @@
+mod setup;
+
+use setup::{test::*, *};
+
+use cosmwasm_std::{from_json, Addr, CosmosMsg, DepsMut, Env, Response, WasmMsg};
+use cw_xcall::{
+    error::ContractError,
+    execute,
+    msg::QueryMsg,
+    query,
+    state::CwCallService,
+    types::{
+        result::{CSMessageResult, CallServiceResponseType},
+        rollback::Rollback,
+    },
+};
+use cw_xcall_lib::{
+    xcall_events::{RollbackCancelledEvent, XcallEvent},
+    xcall_msg::ExecuteMsg,
+    xcall_query_msg::MessageStatus,
+};
+
+const GRACE_PERIOD: u64 = 100;
+
+/// Stores a two-way message sent by "dapp" at the time of `env`, awaiting its response.
+fn store_pending_rollback(deps: DepsMut, env: &Env, sn: u128) {
+    let rollback = Rollback::new(
+        Addr::unchecked("dapp"),
+        get_dummy_network_address("archway"),
+        vec![],
+        vec![1, 2, 3],
+        false,
+    )
+    .with_sent_at(Some(env.block.time));
+    let contract = CwCallService::new();
+    contract
+        .store_call_request(deps.storage, sn, &rollback)
+        .unwrap();
+    contract
+        .store_message_status(deps.storage, sn, MessageStatus::AwaitingResponse)
+        .unwrap();
+}
+
+fn cancel_rollback(
+    deps: DepsMut,
+    env: Env,
+    sender: &str,
+    sn: u128,
+) -> Result<Response, ContractError> {
+    execute(
+        deps,
+        env,
+        create_mock_info(sender, "arch", 0),
+        ExecuteMsg::CancelRollback { sn },
+    )
+}
+
+fn after_grace_period(env: &Env) -> Env {
+    let mut env = env.clone();
+    env.block.time = env.block.time.plus_seconds(GRACE_PERIOD);
+    env
+}
+
+#[test]
+fn test_cancel_rollback_runs_rollback() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let msg = ExecuteMsg::SetRollbackGracePeriod {
+        period: Some(GRACE_PERIOD),
+    };
+    execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), msg).unwrap();
+    let period: Option<u64> = from_json(
+        query(
+            deps.as_ref(),
+            ctx.env.clone(),
+            QueryMsg::GetRollbackGracePeriod {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(period, Some(GRACE_PERIOD));
+
+    store_pending_rollback(deps.as_mut(), &ctx.env, 1);
+    let env = after_grace_period(&ctx.env);
+    let res = cancel_rollback(deps.as_mut(), env.clone(), "dapp", 1).unwrap();
+
+    assert_eq!(res.attributes[1].value, "cancel_rollback");
+    let event = RollbackCancelledEvent::parse(&res.events[0]).unwrap();
+    assert_eq!(event.sn, 1);
+    assert_eq!(event.from, "dapp");
+    match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
+            assert_eq!(contract_addr, "dapp")
+        }
+        msg => panic!("unexpected message {msg:?}"),
+    }
+    assert!(contract.get_call_request(deps.as_ref().storage, 1).is_err());
+    assert_eq!(
+        contract
+            .query_message_status(deps.as_ref().storage, 1)
+            .unwrap(),
+        MessageStatus::Cancelled
+    );
+
+    let result = CSMessageResult::new(1, CallServiceResponseType::CallServiceResponseFailure, None);
+    let err = contract
+        .handle_result(
+            deps.as_mut(),
+            env.clone(),
+            create_mock_info("centralized", "arch", 0),
+            &result.as_bytes(),
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "CallRequestCancelled 1");
+
+    assert!(cancel_rollback(deps.as_mut(), env, "dapp", 1).is_err());
+}
+
+#[test]
+fn test_cancel_rollback_waits_for_grace_period() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    store_pending_rollback(deps.as_mut(), &ctx.env, 1);
+
+    let env = after_grace_period(&ctx.env);
+    let err = cancel_rollback(deps.as_mut(), env, "dapp", 1).unwrap_err();
+    assert_eq!(err.to_string(), "RollbackGracePeriodNotSet");
+
+    contract
+        .store_rollback_grace_period(deps.as_mut().storage, Some(GRACE_PERIOD))
+        .unwrap();
+    let mut env = ctx.env.clone();
+    env.block.time = env.block.time.plus_seconds(GRACE_PERIOD - 1);
+    let err = cancel_rollback(deps.as_mut(), env, "dapp", 1).unwrap_err();
+    assert_eq!(err.to_string(), "RollbackGracePeriodNotElapsed 1");
+    assert_eq!(
+        contract
+            .query_message_status(deps.as_ref().storage, 1)
+            .unwrap(),
+        MessageStatus::AwaitingResponse
+    );
+}
+
+#[test]
+fn test_cancel_rollback_only_sender() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    contract
+        .store_rollback_grace_period(deps.as_mut().storage, Some(GRACE_PERIOD))
+        .unwrap();
+    store_pending_rollback(deps.as_mut(), &ctx.env, 1);
+
+    let env = after_grace_period(&ctx.env);
+    let err = cancel_rollback(deps.as_mut(), env, "other_dapp", 1).unwrap_err();
+    assert_eq!(err.to_string(), "OnlyDapp");
+}
+
+#[test]
+fn test_set_rollback_grace_period_only_admin() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let msg = ExecuteMsg::SetRollbackGracePeriod {
+        period: Some(GRACE_PERIOD),
+    };
+    let err = execute(
+        deps.as_mut(),
+        ctx.env,
+        create_mock_info("dapp", "arch", 0),
+        msg,
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "OnlyAdmin");
+    assert_eq!(
+        contract.get_rollback_grace_period(deps.as_ref().storage),
+        None
+    );
+}
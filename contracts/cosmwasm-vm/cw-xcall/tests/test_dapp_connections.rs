@@ -0,0 +1,106 @@
+mod setup;
+
+use setup::{test::*, *};
+use std::str::FromStr;
+
+use cosmwasm_std::{from_json, CosmosMsg, Response, WasmMsg};
+use cw_xcall::{execute, msg::QueryMsg, query, state::CwCallService};
+use cw_xcall_lib::{
+    message::envelope::Envelope, network_address::NetId, xcall_msg::ExecuteMsg,
+    xcall_query_msg::DappConnections,
+};
+
+fn connections_called(res: &Response) -> Vec<String> {
+    res.messages
+        .iter()
+        .filter_map(|submsg| match &submsg.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => Some(contract_addr.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn send_call(envelope: Envelope) -> ExecuteMsg {
+    ExecuteMsg::SendCall {
+        envelope,
+        to: get_dummy_network_address("archway"),
+        max_fee: None,
+    }
+}
+
+#[test]
+fn test_send_call_uses_dapp_connections() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    mock_connection_fee_query(&mut deps);
+
+    let msg = ExecuteMsg::SetDappConnections {
+        nid: NetId::from_str("archway").unwrap(),
+        sources: vec!["conn_a".to_string(), "conn_b".to_string()],
+        destinations: vec!["dest_a".to_string(), "dest_b".to_string()],
+    };
+    execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), msg).unwrap();
+
+    let query_msg = QueryMsg::GetDappConnections {
+        dapp: ctx.info.sender.to_string(),
+        nid: NetId::from_str("archway").unwrap(),
+    };
+    let stored: Option<DappConnections> =
+        from_json(query(deps.as_ref(), ctx.env.clone(), query_msg).unwrap()).unwrap();
+    assert_eq!(
+        stored.unwrap().destinations,
+        vec!["dest_a".to_string(), "dest_b".to_string()]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        ctx.env.clone(),
+        ctx.info.clone(),
+        send_call(get_dummy_call_msg_envelop()),
+    )
+    .unwrap();
+    assert_eq!(connections_called(&res), vec!["conn_a", "conn_b"]);
+
+    let envelope = Envelope {
+        sources: vec!["centralized".to_string()],
+        destinations: vec!["dest".to_string()],
+        ..get_dummy_call_msg_envelop()
+    };
+    let res = execute(
+        deps.as_mut(),
+        ctx.env.clone(),
+        ctx.info.clone(),
+        send_call(envelope),
+    )
+    .unwrap();
+    assert_eq!(connections_called(&res), vec!["centralized"]);
+
+    let msg = ExecuteMsg::RemoveDappConnections {
+        nid: NetId::from_str("archway").unwrap(),
+    };
+    execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), msg).unwrap();
+    let res = execute(
+        deps.as_mut(),
+        ctx.env,
+        ctx.info,
+        send_call(get_dummy_call_msg_envelop()),
+    )
+    .unwrap();
+    assert_eq!(connections_called(&res), vec!["centralized"]);
+}
+
+#[test]
+fn test_set_dapp_connections_requires_sources() {
+    let mut deps = deps();
+    let ctx = TestContext::default();
+
+    let msg = ExecuteMsg::SetDappConnections {
+        nid: NetId::from_str("archway").unwrap(),
+        sources: vec![],
+        destinations: vec!["dest".to_string()],
+    };
+    let err = execute(deps.as_mut(), ctx.env, ctx.info, msg).unwrap_err();
+    assert_eq!(err.to_string(), "InvalidDappConnections");
+}
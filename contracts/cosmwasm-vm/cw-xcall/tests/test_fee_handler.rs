@@ -35,12 +35,12 @@ fn set_protocol_fee_handler() {
         .set_protocol_feehandler(deps.as_mut(), &info, address.clone())
         .unwrap();
 
-    let result = contract.get_protocol_feehandler(deps.as_ref());
+    let result = contract.get_protocol_feehandler(deps.as_ref()).unwrap();
     assert_eq!(address, result);
 }
 
 #[test]
-#[should_panic(expected = "OnlyAdmin")]
+#[should_panic(expected = "MissingRole")]
 fn test_invalid_input() {
     let mut deps = mock_dependencies();
     let info = mock_info("user", &[Coin::new(1000, "ucosm")]);
@@ -84,7 +84,7 @@ fn get_protocol_fee_handler() {
     contract
         .set_protocol_feehandler(deps.as_mut(), &info, address)
         .unwrap();
-    let result = contract.get_protocol_feehandler(deps.as_ref());
+    let result = contract.get_protocol_feehandler(deps.as_ref()).unwrap();
     let env = mock_env();
 
     assert_eq!("xyz", result);
@@ -188,6 +188,7 @@ fn get_fee() {
             NetId::from_str("icon").unwrap(),
             true,
             vec![],
+            None,
         )
         .unwrap();
     assert_eq!("223", result.to_string());
@@ -199,6 +200,7 @@ fn get_fee() {
                 nid: NetId::from_str("icon").unwrap(),
                 rollback: true,
                 sources: None,
+                denom: None,
             },
         )
         .unwrap();
@@ -2,7 +2,7 @@ mod account;
 mod setup;
 use crate::account::alice;
 
-use common::utils::keccak256;
+use common::{rlp, utils::keccak256};
 use schemars::_serde_json::to_string;
 use setup::test::*;
 use setup::*;
@@ -14,16 +14,22 @@ use cosmwasm_std::{
     to_json_binary, Addr, Coin, CosmosMsg, Reply, SubMsgResponse, SubMsgResult, WasmMsg,
 };
 use cw_xcall::{
-    state::{CwCallService, EXECUTE_CALL_ID},
+    state::{CwCallService, CALL_RESULT_REPLY_ID, EXECUTE_CALLS_REPLY_ID, EXECUTE_CALL_ID},
     types::{
         message::{CSMessage, CSMessageType},
+        pending::DeliveryKind,
         request::CSMessageRequest,
+        result::{CSMessageResult, CallServiceResponseType},
         rollback::Rollback,
     },
 };
 use cw_xcall_lib::{
-    message::msg_type::MessageType,
+    dapp_callback_msg,
+    message::{expiry::MessageExpiry, msg_type::MessageType},
     network_address::{NetId, NetworkAddress},
+    xcall_connection_msg,
+    xcall_events::{CallMessageEvent, ResponseMessageEvent, RollbackMessageEvent, XcallEvent},
+    xcall_query_msg::{InboundPolicy, MessageStatus},
 };
 
 #[test]
@@ -60,7 +66,7 @@ fn test_execute_call_with_wrong_data() {
         .unwrap();
 
     cw_callservice
-        .execute_call(deps.as_mut(), info, request_id, data)
+        .execute_call(deps.as_mut(), mock_env(), info, request_id, data)
         .unwrap();
 }
 
@@ -85,7 +91,7 @@ fn test_execute_call_having_request_id_without_rollback() {
         .unwrap();
 
     let res = cw_callservice
-        .execute_call(deps.as_mut(), info, request_id, data)
+        .execute_call(deps.as_mut(), mock_env(), info, request_id, data)
         .unwrap();
     match &res.messages[0].msg {
         CosmosMsg::Wasm(WasmMsg::Execute {
@@ -374,7 +380,7 @@ fn test_handle_reply() {
     let request = get_dummy_req_msg();
     let rollback = get_dummy_rollback_data();
 
-    let res = contract.handle_reply(deps.as_mut(), rollback, request);
+    let res = contract.handle_reply(deps.as_mut(), "centralized", rollback, request);
     assert!(res.is_ok())
 }
 
@@ -398,7 +404,7 @@ fn test_handle_reply_fail() {
     let rollback = get_dummy_rollback_data();
 
     contract
-        .handle_reply(deps.as_mut(), rollback, request)
+        .handle_reply(deps.as_mut(), "centralized", rollback, request)
         .unwrap();
 }
 
@@ -414,7 +420,7 @@ fn test_handle_request_fail_on_mismatch_protocols_request() {
     let data = ctx.request_message.unwrap().as_bytes();
     let src_net = NetId::from_str("evm").unwrap();
     contract
-        .handle_request(deps.as_mut(), ctx.info, src_net, &data)
+        .handle_request(deps.as_mut(), ctx.env, ctx.info, src_net, &data)
         .unwrap();
 }
 
@@ -430,7 +436,7 @@ fn test_handle_request_fail_on_invalid_source() {
     let nid = NetId::from_str("archway").unwrap();
     let data = ctx.request_message.unwrap().as_bytes();
     contract
-        .handle_request(deps.as_mut(), ctx.info, nid, &data)
+        .handle_request(deps.as_mut(), ctx.env, ctx.info, nid, &data)
         .unwrap();
 }
 
@@ -455,7 +461,13 @@ fn test_handle_request_from_multiple_protocols() {
     for protocol in request.protocols() {
         let info = create_mock_info(protocol, "icx", 100);
         let res = contract
-            .handle_request(deps.as_mut(), info, nid.clone(), &request.as_bytes())
+            .handle_request(
+                deps.as_mut(),
+                ctx.env.clone(),
+                info,
+                nid.clone(),
+                &request.as_bytes(),
+            )
             .unwrap();
         if protocol == "ibc" {
             assert_eq!(res.attributes[0].value, "call_service");
@@ -479,7 +491,7 @@ fn test_handle_call_message_fail_on_invalid_request() {
 
     let nid = NetId::from_str("archway").unwrap();
     contract
-        .handle_message(deps.as_mut(), ctx.info, nid, msg.as_bytes())
+        .handle_message(deps.as_mut(), ctx.env, ctx.info, nid, msg.as_bytes())
         .unwrap();
 }
 
@@ -499,7 +511,7 @@ fn test_handle_result_fail_on_invalid_source() {
 
     let msg = get_dummy_result_message().as_bytes();
     contract
-        .handle_result(deps.as_mut(), ctx.info, &msg)
+        .handle_result(deps.as_mut(), mock_env(), ctx.info, &msg)
         .unwrap();
 }
 
@@ -526,7 +538,9 @@ fn test_handle_result_from_multiple_protocols() {
 
     for protocol in rollback.protocols() {
         let info = create_mock_info(protocol, "arch", 100);
-        let res = contract.handle_result(deps.as_mut(), info, &msg).unwrap();
+        let res = contract
+            .handle_result(deps.as_mut(), mock_env(), info, &msg)
+            .unwrap();
         if protocol == "centralized" {
             assert_eq!(res.attributes.len(), 0);
         } else {
@@ -550,6 +564,1090 @@ fn test_handle_result_on_error_response() {
         .unwrap();
 
     let msg = get_dummy_result_message_failure().as_bytes();
-    let res = contract.handle_result(deps.as_mut(), info, &msg).unwrap();
+    let res = contract
+        .handle_result(deps.as_mut(), mock_env(), info, &msg)
+        .unwrap();
     assert_eq!(res.attributes[1].value, "handle_response")
 }
+
+#[test]
+fn test_handle_result_emits_rollback_message_event() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let info = create_mock_info("centralized", "arch", 100);
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+    contract
+        .store_call_request(deps.as_mut().storage, 1, &get_dummy_rollback_data())
+        .unwrap();
+
+    let msg = get_dummy_result_message_failure().as_bytes();
+    let res = contract
+        .handle_result(deps.as_mut(), mock_env(), info, &msg)
+        .unwrap();
+
+    let response = ResponseMessageEvent::parse(&res.events[0]).unwrap();
+    assert_eq!(response.sn, 1);
+    assert_eq!(response.connection, "centralized");
+    let rollback = RollbackMessageEvent::parse(&res.events[1]).unwrap();
+    assert_eq!(res.events[1].ty, "RollbackMessage");
+    assert_eq!(rollback.from, "xcall");
+}
+
+#[test]
+fn test_handle_result_dispatches_every_reply() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let info = create_mock_info("centralized", "arch", 100);
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+    contract
+        .store_call_request(deps.as_mut().storage, 1, &get_dummy_rollback_data())
+        .unwrap();
+
+    let replies = (1..=3)
+        .map(|sn| {
+            CSMessageRequest::new(
+                get_dummy_network_address("archway"),
+                Addr::unchecked("dapp"),
+                sn,
+                MessageType::CallMessage,
+                vec![sn as u8],
+                vec![],
+            )
+            .as_bytes()
+        })
+        .collect();
+    let msg = CSMessageResult::new(1, CallServiceResponseType::CallServiceResponseSuccess, None)
+        .with_replies(replies)
+        .as_bytes();
+    let res = contract
+        .handle_result(deps.as_mut(), mock_env(), info, &msg)
+        .unwrap();
+
+    let calls: Vec<(u128, u128, Vec<u8>)> = res
+        .events
+        .iter()
+        .filter(|event| event.ty == "CallMessage")
+        .map(|event| {
+            let event = CallMessageEvent::parse(event).unwrap();
+            (event.sn, event.req_id, event.data)
+        })
+        .collect();
+    assert_eq!(
+        calls,
+        vec![
+            (1, ctx.request_id + 1, vec![1]),
+            (2, ctx.request_id + 2, vec![2]),
+            (3, ctx.request_id + 3, vec![3]),
+        ]
+    );
+}
+
+#[test]
+fn test_handle_request_emits_call_message_event() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let info = create_mock_info("centralized", "arch", 100);
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let request = get_dummy_req_msg();
+    let res = contract
+        .handle_request(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            NetId::from_str("archway").unwrap(),
+            &request.as_bytes(),
+        )
+        .unwrap();
+
+    let event = CallMessageEvent::parse(&res.events[0]).unwrap();
+    assert_eq!(
+        event,
+        CallMessageEvent {
+            from: "archway/xcall".to_string(),
+            to: "dapp".to_string(),
+            sn: 1,
+            req_id: 1,
+            data: request.data().unwrap().to_vec(),
+            connection: "centralized".to_string(),
+            protocols: vec![],
+        }
+    );
+}
+
+#[test]
+fn test_handle_forced_rollback() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let request = CSMessageRequest::new(
+        get_dummy_network_address("archway"),
+        Addr::unchecked("dapp"),
+        1,
+        MessageType::CallMessageWithRollback,
+        keccak256(&[1, 2, 3]).to_vec(),
+        vec![],
+    );
+    contract
+        .store_proxy_request(deps.as_mut().storage, ctx.request_id, &request)
+        .unwrap();
+
+    let info = create_mock_info("dapp", "icx", 0);
+    let res = contract
+        .handle_forced_rollback(deps.as_mut(), info, ctx.request_id)
+        .unwrap();
+
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.attributes[1].value, "handle_forced_rollback");
+    assert!(contract
+        .get_proxy_request(deps.as_ref().storage, ctx.request_id)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "OnlyDapp")]
+fn test_handle_forced_rollback_fail_on_non_target_caller() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let request = CSMessageRequest::new(
+        get_dummy_network_address("archway"),
+        Addr::unchecked("dapp"),
+        1,
+        MessageType::CallMessageWithRollback,
+        keccak256(&[1, 2, 3]).to_vec(),
+        vec![],
+    );
+    contract
+        .store_proxy_request(deps.as_mut().storage, ctx.request_id, &request)
+        .unwrap();
+
+    contract
+        .handle_forced_rollback(deps.as_mut(), ctx.info, ctx.request_id)
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "RollbackNotPossible")]
+fn test_handle_forced_rollback_fail_on_one_way_message() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    ctx.init_execute_call(deps.as_mut().storage, &contract);
+
+    let info = create_mock_info("dapp", "icx", 0);
+    contract
+        .handle_forced_rollback(deps.as_mut(), info, ctx.request_id)
+        .unwrap();
+}
+
+#[test]
+fn test_message_status_transitions() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let info = create_mock_info("centralized", "arch", 100);
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+    contract.sn().save(deps.as_mut().storage, &2).unwrap();
+
+    let rollback = Rollback::new(
+        Addr::unchecked("xcall"),
+        get_dummy_network_address("archway"),
+        vec![],
+        vec![1, 2, 3],
+        false,
+    );
+    contract
+        .store_call_request(deps.as_mut().storage, 1, &rollback)
+        .unwrap();
+    contract
+        .store_message_status(deps.as_mut().storage, 1, MessageStatus::AwaitingResponse)
+        .unwrap();
+
+    let msg = get_dummy_result_message_failure().as_bytes();
+    contract
+        .handle_result(deps.as_mut(), mock_env(), info.clone(), &msg)
+        .unwrap();
+    let status = contract.query_message_status(deps.as_ref().storage, 1);
+    assert_eq!(status.unwrap(), MessageStatus::RollbackEnabled);
+
+    contract
+        .execute_rollback(deps.as_mut(), mock_env(), info, 1)
+        .unwrap();
+    let status = contract.query_message_status(deps.as_ref().storage, 1);
+    assert_eq!(status.unwrap(), MessageStatus::RolledBack);
+
+    let status = contract.query_message_status(deps.as_ref().storage, 2);
+    assert_eq!(status.unwrap(), MessageStatus::Sent);
+
+    let status = contract.query_message_status(deps.as_ref().storage, 3);
+    assert!(status.is_err());
+}
+
+#[test]
+fn test_message_status_on_success_response() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let info = create_mock_info("centralized", "arch", 100);
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let rollback = get_dummy_rollback_data();
+    contract
+        .store_call_request(deps.as_mut().storage, 1, &rollback)
+        .unwrap();
+
+    let msg = get_dummy_result_message().as_bytes();
+    contract
+        .handle_result(deps.as_mut(), mock_env(), info, &msg)
+        .unwrap();
+
+    let status = contract.query_message_status(deps.as_ref().storage, 1);
+    assert_eq!(status.unwrap(), MessageStatus::Succeeded);
+}
+
+#[test]
+fn test_handle_request_rejects_expired_request() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let request = CSMessageRequest::new(
+        get_dummy_network_address("archway"),
+        Addr::unchecked("dapp"),
+        1,
+        MessageType::CallMessageWithRollback,
+        vec![1, 2, 3],
+        vec![],
+    )
+    .with_expiry(Some(MessageExpiry::Height(10)))
+    .with_sent_height(Some(5));
+
+    let nid = NetId::from_str("archway").unwrap();
+    contract
+        .store_source_height(deps.as_mut().storage, &nid, 11)
+        .unwrap();
+    let info = create_mock_info("centralized", "icx", 0);
+    let res = contract
+        .handle_request(deps.as_mut(), mock_env(), info, nid, &request.as_bytes())
+        .unwrap();
+
+    assert_eq!(res.attributes[1].value, "reject_expired_request");
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        contract
+            .query_last_request_id(deps.as_ref().storage)
+            .unwrap(),
+        ctx.request_id
+    );
+}
+
+#[test]
+fn test_handle_request_height_expiry_uses_source_height() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let request = CSMessageRequest::new(
+        get_dummy_network_address("archway"),
+        Addr::unchecked("dapp"),
+        1,
+        MessageType::CallMessage,
+        vec![1, 2, 3],
+        vec![],
+    )
+    .with_expiry(Some(MessageExpiry::Height(10)))
+    .with_sent_height(Some(9));
+
+    // the local height says nothing about the height reached by the source chain
+    let mut env = mock_env();
+    env.block.height = 1_000;
+    let nid = NetId::from_str("archway").unwrap();
+    let res = contract
+        .handle_request(
+            deps.as_mut(),
+            env,
+            create_mock_info("centralized", "icx", 0),
+            nid.clone(),
+            &request.as_bytes(),
+        )
+        .unwrap();
+
+    assert_eq!(res.attributes[1].value, "handle_response");
+    assert_eq!(
+        contract.get_source_height(deps.as_ref().storage, &nid),
+        Some(9)
+    );
+}
+
+#[test]
+fn test_execute_call_rejects_expired_request() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let request = CSMessageRequest::new(
+        get_dummy_network_address("archway"),
+        Addr::unchecked("dapp"),
+        1,
+        MessageType::CallMessage,
+        keccak256(&[1, 2, 3]).to_vec(),
+        vec![],
+    )
+    .with_expiry(Some(MessageExpiry::Timestamp(10)));
+    contract
+        .store_proxy_request(deps.as_mut().storage, ctx.request_id, &request)
+        .unwrap();
+
+    let res = contract
+        .execute_call(
+            deps.as_mut(),
+            mock_env(),
+            ctx.info,
+            ctx.request_id,
+            vec![1, 2, 3],
+        )
+        .unwrap();
+
+    assert!(res.messages.is_empty());
+    assert!(contract
+        .get_proxy_request(deps.as_ref().storage, ctx.request_id)
+        .is_err());
+}
+
+#[test]
+fn test_handle_result_notifies_dapp_with_result_callback() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let info = create_mock_info("centralized", "arch", 100);
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let rollback = get_dummy_rollback_data().with_result_callback(true);
+    contract
+        .store_call_request(deps.as_mut().storage, 1, &rollback)
+        .unwrap();
+
+    let msg = get_dummy_result_message().as_bytes();
+    let res = contract
+        .handle_result(deps.as_mut(), mock_env(), info, &msg)
+        .unwrap();
+
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].id, CALL_RESULT_REPLY_ID);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "xcall".to_string(),
+            msg: to_json_binary(&dapp_callback_msg::ExecuteMsg::HandleCallResult {
+                sn: 1,
+                success: true
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+}
+
+#[test]
+fn test_handle_result_notifies_dapp_of_failure() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let info = create_mock_info("centralized", "arch", 100);
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let rollback = get_dummy_rollback_data().with_result_callback(true);
+    contract
+        .store_call_request(deps.as_mut().storage, 1, &rollback)
+        .unwrap();
+
+    let msg = get_dummy_result_message_failure().as_bytes();
+    let res = contract
+        .handle_result(deps.as_mut(), mock_env(), info, &msg)
+        .unwrap();
+
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "xcall".to_string(),
+            msg: to_json_binary(&dapp_callback_msg::ExecuteMsg::HandleCallResult {
+                sn: 1,
+                success: false
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+}
+
+#[test]
+fn test_handle_result_without_result_callback() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let info = create_mock_info("centralized", "arch", 100);
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let rollback = get_dummy_rollback_data();
+    contract
+        .store_call_request(deps.as_mut().storage, 1, &rollback)
+        .unwrap();
+
+    let msg = get_dummy_result_message().as_bytes();
+    let res = contract
+        .handle_result(deps.as_mut(), mock_env(), info, &msg)
+        .unwrap();
+    assert!(res.messages.is_empty());
+}
+
+#[test]
+fn test_failed_result_callback_does_not_revert() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let msg = Reply {
+        id: CALL_RESULT_REPLY_ID,
+        result: SubMsgResult::Err("dapp error".to_string()),
+    };
+
+    let res = contract.reply(deps.as_mut(), mock_env(), msg).unwrap();
+    assert_eq!(res.events[0].ty, "CallResultFailed");
+    assert_eq!(res.events[0].attributes[0].value, "dapp error");
+}
+
+fn store_batch_request(
+    contract: &CwCallService,
+    storage: &mut dyn cosmwasm_std::Storage,
+    request_id: u128,
+    msg_type: MessageType,
+    data: &[u8],
+) {
+    let request = CSMessageRequest::new(
+        NetworkAddress::new("nid", "mockaddress"),
+        Addr::unchecked("dapp"),
+        request_id,
+        msg_type,
+        keccak256(data).to_vec(),
+        vec![],
+    );
+    contract
+        .store_proxy_request(storage, request_id, &request)
+        .unwrap();
+}
+
+#[test]
+fn test_execute_calls_isolates_items() {
+    let mut deps = mock_dependencies();
+    let contract = CwCallService::default();
+    let info = mock_info("relayer", &[]);
+    store_batch_request(
+        &contract,
+        deps.as_mut().storage,
+        1,
+        MessageType::CallMessage,
+        &[1],
+    );
+    store_batch_request(
+        &contract,
+        deps.as_mut().storage,
+        2,
+        MessageType::CallMessage,
+        &[2],
+    );
+
+    let items = vec![(1, vec![1]), (2, vec![2])];
+    let res = contract
+        .execute_calls(deps.as_mut(), mock_env(), info, items)
+        .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(res.messages[0].id, EXECUTE_CALLS_REPLY_ID);
+    assert_eq!(res.messages[1].id, EXECUTE_CALLS_REPLY_ID + 1);
+    assert_eq!(
+        contract
+            .get_execute_request_id(deps.as_ref().storage)
+            .unwrap(),
+        1
+    );
+
+    let reply = Reply {
+        id: EXECUTE_CALLS_REPLY_ID,
+        result: SubMsgResult::Err("error message".into()),
+    };
+    let res = contract.reply(deps.as_mut(), mock_env(), reply).unwrap();
+    assert_eq!(res.events[0].attributes[1].value, "0");
+    assert_eq!(
+        contract
+            .get_execute_request_id(deps.as_ref().storage)
+            .unwrap(),
+        2
+    );
+
+    let reply = Reply {
+        id: EXECUTE_CALLS_REPLY_ID + 1,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    let res = contract.reply(deps.as_mut(), mock_env(), reply).unwrap();
+    assert_eq!(res.events[0].attributes[1].value, "1");
+    assert!(contract
+        .get_execute_request_id(deps.as_ref().storage)
+        .is_err());
+    assert!(contract
+        .get_proxy_request(deps.as_ref().storage, 2)
+        .is_err());
+}
+
+#[test]
+fn test_execute_calls_keeps_failed_persisted_request() {
+    let mut deps = mock_dependencies();
+    let contract = CwCallService::default();
+    let info = mock_info("relayer", &[]);
+    store_batch_request(
+        &contract,
+        deps.as_mut().storage,
+        1,
+        MessageType::CallMessagePersisted,
+        &[1],
+    );
+
+    contract
+        .execute_calls(deps.as_mut(), mock_env(), info, vec![(1, vec![1])])
+        .unwrap();
+    let reply = Reply {
+        id: EXECUTE_CALLS_REPLY_ID,
+        result: SubMsgResult::Err("error message".into()),
+    };
+    contract.reply(deps.as_mut(), mock_env(), reply).unwrap();
+
+    assert!(contract.get_proxy_request(deps.as_ref().storage, 1).is_ok());
+    assert!(contract
+        .get_execute_request_id(deps.as_ref().storage)
+        .is_err());
+}
+
+#[test]
+fn test_execute_calls_skips_executed_request() {
+    let mut deps = mock_dependencies();
+    let contract = CwCallService::default();
+    store_batch_request(
+        &contract,
+        deps.as_mut().storage,
+        1,
+        MessageType::CallMessage,
+        &[1],
+    );
+    store_batch_request(
+        &contract,
+        deps.as_mut().storage,
+        2,
+        MessageType::CallMessage,
+        &[2],
+    );
+
+    contract
+        .execute_calls(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("relayer", &[]),
+            vec![(1, vec![1])],
+        )
+        .unwrap();
+    let reply = Reply {
+        id: EXECUTE_CALLS_REPLY_ID,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    contract.reply(deps.as_mut(), mock_env(), reply).unwrap();
+
+    // the second batch races the first one, its executed item is skipped
+    let res = contract
+        .execute_calls(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("relayer", &[]),
+            vec![(1, vec![1]), (2, vec![2])],
+        )
+        .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].id, EXECUTE_CALLS_REPLY_ID + 1);
+
+    let skipped = res
+        .events
+        .iter()
+        .find(|event| event.ty == "ExecuteCallSkipped")
+        .unwrap();
+    assert_eq!(skipped.attributes[0].value, "1");
+    assert_eq!(skipped.attributes[1].value, "InvalidRequestId 1");
+    assert_eq!(
+        contract
+            .get_execute_request_id(deps.as_ref().storage)
+            .unwrap(),
+        2
+    );
+}
+
+#[test]
+#[should_panic(expected = "DuplicateRequestId")]
+fn test_execute_calls_rejects_duplicate_request_id() {
+    let mut deps = mock_dependencies();
+    let contract = CwCallService::default();
+    let info = mock_info("relayer", &[]);
+    store_batch_request(
+        &contract,
+        deps.as_mut().storage,
+        1,
+        MessageType::CallMessage,
+        &[1],
+    );
+
+    contract
+        .execute_calls(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            vec![(1, vec![1]), (1, vec![1])],
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "CallAlreadyInProgress")]
+fn test_execute_call_rejected_while_batch_in_progress() {
+    let mut deps = mock_dependencies();
+    let contract = CwCallService::default();
+    store_batch_request(
+        &contract,
+        deps.as_mut().storage,
+        1,
+        MessageType::CallMessage,
+        &[1],
+    );
+    store_batch_request(
+        &contract,
+        deps.as_mut().storage,
+        2,
+        MessageType::CallMessage,
+        &[2],
+    );
+
+    contract
+        .execute_calls(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("relayer", &[]),
+            vec![(1, vec![1])],
+        )
+        .unwrap();
+    contract
+        .execute_call(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("dapp", &[]),
+            2,
+            vec![2],
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_execute_call_applies_gas_limit() {
+    let mut deps = mock_dependencies();
+    let contract = CwCallService::default();
+    let data = vec![1, 2, 3];
+    let request = CSMessageRequest::new(
+        NetworkAddress::new("nid", "mockaddress"),
+        Addr::unchecked("dapp"),
+        1,
+        MessageType::CallMessageWithRollback,
+        keccak256(&data).to_vec(),
+        vec![],
+    )
+    .with_gas_limit(Some(150_000));
+    contract
+        .store_proxy_request(deps.as_mut().storage, 1, &request)
+        .unwrap();
+
+    let res = contract
+        .execute_call(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("relayer", &[]),
+            1,
+            data,
+        )
+        .unwrap();
+    assert_eq!(res.messages[0].gas_limit, Some(150_000));
+}
+
+#[test]
+fn test_execute_call_out_of_gas_sends_failure_result() {
+    let mut deps = mock_dependencies();
+    let contract = CwCallService::default();
+    let data = vec![1, 2, 3];
+    let request = CSMessageRequest::new(
+        NetworkAddress::new("nid", "mockaddress"),
+        Addr::unchecked("dapp"),
+        1,
+        MessageType::CallMessageWithRollback,
+        keccak256(&data).to_vec(),
+        vec!["centralized".to_string()],
+    )
+    .with_gas_limit(Some(150_000));
+    contract
+        .store_proxy_request(deps.as_mut().storage, 1, &request)
+        .unwrap();
+
+    contract
+        .execute_call(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("relayer", &[]),
+            1,
+            data,
+        )
+        .unwrap();
+    let reply = Reply {
+        id: EXECUTE_CALL_ID,
+        result: SubMsgResult::Err("codespace: sdk, code: 11: out of gas".into()),
+    };
+    let res = contract.reply(deps.as_mut(), mock_env(), reply).unwrap();
+
+    let result = CSMessageResult::new(1, CallServiceResponseType::CallServiceResponseFailure, None);
+    let message = xcall_connection_msg::ExecuteMsg::SendMessage {
+        to: NetId::from("nid".to_string()),
+        sn: -1,
+        msg: rlp::encode(&CSMessage::from(result)).to_vec(),
+    };
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "centralized".to_string(),
+            msg: to_json_binary(&message).unwrap(),
+            funds: vec![],
+        })
+    );
+    assert!(contract
+        .get_proxy_request(deps.as_ref().storage, 1)
+        .is_err());
+}
+
+#[test]
+fn test_execute_call_with_gas_limit_retries_persisted_request() {
+    let mut deps = mock_dependencies();
+    let contract = CwCallService::default();
+    let data = vec![1, 2, 3];
+    let request = CSMessageRequest::new(
+        NetworkAddress::new("nid", "mockaddress"),
+        Addr::unchecked("dapp"),
+        1,
+        MessageType::CallMessagePersisted,
+        keccak256(&data).to_vec(),
+        vec![],
+    )
+    .with_gas_limit(Some(100_000));
+    contract
+        .store_proxy_request(deps.as_mut().storage, 1, &request)
+        .unwrap();
+
+    // the request stays pending after running out of its gas limit
+    contract
+        .execute_call(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("relayer", &[]),
+            1,
+            data.clone(),
+        )
+        .unwrap();
+    let reply = Reply {
+        id: EXECUTE_CALL_ID,
+        result: SubMsgResult::Err("codespace: sdk, code: 11: out of gas".into()),
+    };
+    assert!(contract.reply(deps.as_mut(), mock_env(), reply).is_err());
+    // the failed reply reverts the transaction, which mock storage does not
+    contract
+        .store_proxy_request(deps.as_mut().storage, 1, &request)
+        .unwrap();
+
+    let err = contract
+        .execute_call_with_gas_limit(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("relayer", &[]),
+            1,
+            data.clone(),
+            100_000,
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "InvalidGasLimit 100000");
+
+    let res = contract
+        .execute_call_with_gas_limit(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("relayer", &[]),
+            1,
+            data,
+            300_000,
+        )
+        .unwrap();
+    assert_eq!(res.messages[0].gas_limit, Some(300_000));
+}
+
+#[test]
+fn test_execute_call_with_gas_limit_rejects_request_without_retry() {
+    let mut deps = mock_dependencies();
+    let contract = CwCallService::default();
+    let data = vec![1, 2, 3];
+    let request = CSMessageRequest::new(
+        NetworkAddress::new("nid", "mockaddress"),
+        Addr::unchecked("dapp"),
+        1,
+        MessageType::CallMessage,
+        keccak256(&data).to_vec(),
+        vec![],
+    )
+    .with_gas_limit(Some(100_000));
+    contract
+        .store_proxy_request(deps.as_mut().storage, 1, &request)
+        .unwrap();
+
+    let err = contract
+        .execute_call_with_gas_limit(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("relayer", &[]),
+            1,
+            data,
+            300_000,
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "InvalidGasLimit 300000");
+}
+
+#[test]
+fn test_handle_request_with_threshold_ignores_late_delivery() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let request = CSMessageRequest::new(
+        get_dummy_network_address("archway"),
+        Addr::unchecked("dapp"),
+        1,
+        MessageType::CallMessage,
+        keccak256(&[1, 2, 3]).to_vec(),
+        vec![
+            "centralized".to_string(),
+            "ibc".to_string(),
+            "layerzero".to_string(),
+        ],
+    )
+    .with_threshold(Some(2));
+    let policy = InboundPolicy {
+        threshold: Some(2),
+        ..Default::default()
+    };
+    contract
+        .store_inbound_policy(deps.as_mut().storage, request.to(), &policy)
+        .unwrap();
+
+    let nid = NetId::from_str("archway").unwrap();
+    let mut accepted = vec![];
+    for protocol in request.protocols() {
+        let info = create_mock_info(protocol, "icx", 100);
+        let res = contract
+            .handle_request(
+                deps.as_mut(),
+                ctx.env.clone(),
+                info,
+                nid.clone(),
+                &request.as_bytes(),
+            )
+            .unwrap();
+        accepted.push(!res.attributes.is_empty());
+    }
+    assert_eq!(accepted, vec![false, true, false]);
+
+    let key = keccak256(&request.as_bytes()).to_vec();
+    assert!(contract
+        .get_pending_delivery(deps.as_ref().storage, DeliveryKind::Request, &key)
+        .is_none());
+    assert!(contract
+        .get_pending_requests_by_hash(deps.as_ref().storage, key)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_handle_request_ignores_claimed_threshold_without_policy() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    // a single connection claims a threshold of one
+    let request = CSMessageRequest::new(
+        get_dummy_network_address("archway"),
+        Addr::unchecked("dapp"),
+        1,
+        MessageType::CallMessage,
+        keccak256(&[1, 2, 3]).to_vec(),
+        vec!["centralized".to_string(), "ibc".to_string()],
+    )
+    .with_threshold(Some(1));
+    let nid = NetId::from_str("archway").unwrap();
+
+    let info = create_mock_info("centralized", "icx", 100);
+    let res = contract
+        .handle_request(
+            deps.as_mut(),
+            ctx.env.clone(),
+            info,
+            nid.clone(),
+            &request.as_bytes(),
+        )
+        .unwrap();
+    assert!(res.events.is_empty());
+
+    // with a policy threshold of one the claimed one is honoured, and only the protocol that
+    // delivered the request is reported
+    let policy = InboundPolicy {
+        threshold: Some(1),
+        ..Default::default()
+    };
+    contract
+        .store_inbound_policy(deps.as_mut().storage, request.to(), &policy)
+        .unwrap();
+    let request = CSMessageRequest::new(
+        get_dummy_network_address("archway"),
+        Addr::unchecked("dapp"),
+        2,
+        MessageType::CallMessage,
+        keccak256(&[1, 2, 3]).to_vec(),
+        request.protocols().clone(),
+    )
+    .with_threshold(Some(1));
+    let info = create_mock_info("ibc", "icx", 100);
+    let res = contract
+        .handle_request(deps.as_mut(), ctx.env, info, nid, &request.as_bytes())
+        .unwrap();
+    let event = CallMessageEvent::parse(&res.events[0]).unwrap();
+    assert_eq!(event.protocols, vec!["ibc".to_string()]);
+    let stored = contract
+        .get_proxy_request(deps.as_ref().storage, event.req_id)
+        .unwrap();
+    assert_eq!(stored.protocols(), &vec!["ibc".to_string()]);
+}
+
+#[test]
+fn test_handle_result_with_threshold_ignores_late_delivery() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let rollback = Rollback::new(
+        Addr::unchecked("xcall"),
+        get_dummy_network_address("archway"),
+        vec![
+            "centralized".to_string(),
+            "ibc".to_string(),
+            "layerzero".to_string(),
+        ],
+        vec![1, 2, 3],
+        false,
+    )
+    .with_threshold(Some(2));
+    contract
+        .store_call_request(deps.as_mut().storage, 1, &rollback)
+        .unwrap();
+
+    let msg = get_dummy_result_message().as_bytes();
+    let mut accepted = vec![];
+    for protocol in rollback.protocols() {
+        let info = create_mock_info(protocol, "arch", 100);
+        let res = contract
+            .handle_result(deps.as_mut(), mock_env(), info, &msg)
+            .unwrap();
+        accepted.push(!res.attributes.is_empty());
+    }
+    assert_eq!(accepted, vec![false, true, false]);
+    assert!(contract
+        .get_pending_delivery(
+            deps.as_ref().storage,
+            DeliveryKind::Response,
+            &keccak256(&msg)
+        )
+        .is_none());
+}
+
+#[test]
+#[should_panic(expected = "ProtocolsMismatch")]
+fn test_late_result_from_unknown_protocol() {
+    let ctx = TestContext::default();
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let rollback = Rollback::new(
+        Addr::unchecked("xcall"),
+        get_dummy_network_address("archway"),
+        vec![
+            "centralized".to_string(),
+            "ibc".to_string(),
+            "layerzero".to_string(),
+        ],
+        vec![1, 2, 3],
+        false,
+    )
+    .with_threshold(Some(1));
+    contract
+        .store_call_request(deps.as_mut().storage, 1, &rollback)
+        .unwrap();
+
+    let msg = get_dummy_result_message().as_bytes();
+    let info = create_mock_info("centralized", "arch", 100);
+    contract
+        .handle_result(deps.as_mut(), mock_env(), info, &msg)
+        .unwrap();
+
+    let info = create_mock_info("unknown", "arch", 100);
+    contract
+        .handle_result(deps.as_mut(), mock_env(), info, &msg)
+        .unwrap();
+}
@@ -0,0 +1,114 @@
+mod setup;
+
+use setup::{test::*, *};
+use std::str::FromStr;
+
+use cw_xcall::{
+    execute,
+    state::CwCallService,
+    types::message::{CSMessage, CSMessageType},
+};
+use cw_xcall_lib::{network_address::NetId, xcall_msg::ExecuteMsg};
+
+const CASES: usize = 500;
+
+/// Deterministic xorshift generator so that a failing case can be reproduced from its seed.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, max: usize) -> usize {
+        (self.next() % max as u64) as usize
+    }
+
+    fn bytes(&mut self, max_len: usize) -> Vec<u8> {
+        let len = self.below(max_len + 1);
+        (0..len).map(|_| self.next() as u8).collect()
+    }
+
+    /// Flips, inserts, removes or truncates bytes of a valid encoding.
+    fn mutate(&mut self, mut bytes: Vec<u8>) -> Vec<u8> {
+        for _ in 0..=self.below(4) {
+            match self.below(4) {
+                0 if !bytes.is_empty() => {
+                    let i = self.below(bytes.len());
+                    bytes[i] ^= 1 << self.below(8);
+                }
+                1 => {
+                    let i = self.below(bytes.len() + 1);
+                    bytes.insert(i, self.next() as u8);
+                }
+                2 if !bytes.is_empty() => {
+                    let i = self.below(bytes.len());
+                    bytes.remove(i);
+                }
+                _ => bytes.truncate(self.below(bytes.len() + 1)),
+            }
+        }
+        bytes
+    }
+}
+
+/// Delivers `msg` from the default connection of `archway` to a contract that has a pending
+/// rollback for sn 1. Any outcome but a panic is accepted.
+fn handle_message(msg: Vec<u8>) {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    contract
+        .store_call_request(deps.as_mut().storage, 1, &get_dummy_rollback_data())
+        .unwrap();
+
+    let info = create_mock_info("centralized", "icx", 0);
+    let msg = ExecuteMsg::HandleMessage {
+        from_nid: NetId::from_str("archway").unwrap(),
+        msg,
+    };
+    let _ = execute(deps.as_mut(), ctx.env, info, msg);
+}
+
+#[test]
+fn test_handle_message_with_arbitrary_bytes() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..CASES {
+        handle_message(rng.bytes(256));
+    }
+}
+
+#[test]
+fn test_handle_message_with_arbitrary_payload() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    let message_types = [
+        CSMessageType::CSMessageRequest,
+        CSMessageType::CSMessageResult,
+        CSMessageType::CSMessageRequestV2,
+    ];
+    for _ in 0..CASES {
+        let message_type = message_types[rng.below(message_types.len())].clone();
+        let msg = CSMessage::new(message_type, rng.bytes(256));
+        handle_message(msg.as_bytes());
+    }
+}
+
+#[test]
+fn test_handle_message_with_mutated_messages() {
+    let mut rng = Rng(0xd1b5_4a32_d192_ed03);
+    let request = get_dummy_req_msg().as_bytes();
+    let result = get_dummy_result_message().as_bytes();
+    for _ in 0..CASES {
+        let msg = match rng.below(4) {
+            0 => CSMessage::new(CSMessageType::CSMessageRequest, rng.mutate(request.clone())),
+            1 => CSMessage::new(CSMessageType::CSMessageResult, rng.mutate(result.clone())),
+            2 => get_dummy_request_message(),
+            _ => CSMessage::from(get_dummy_result_message()),
+        };
+        handle_message(rng.mutate(msg.as_bytes()));
+    }
+}
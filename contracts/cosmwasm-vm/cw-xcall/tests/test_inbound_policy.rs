@@ -0,0 +1,227 @@
+mod setup;
+
+use setup::{test::*, *};
+use std::str::FromStr;
+
+use cosmwasm_std::{from_json, Addr, DepsMut, Env, Response};
+use cw_xcall::{
+    error::ContractError, execute, msg::QueryMsg, query, state::CwCallService,
+    types::request::CSMessageRequest,
+};
+use cw_xcall_lib::{
+    message::msg_type::MessageType,
+    network_address::{NetId, NetworkAddress},
+    xcall_msg::ExecuteMsg,
+    xcall_query_msg::InboundPolicy,
+};
+
+fn deliver_request(
+    deps: DepsMut,
+    env: Env,
+    from: NetworkAddress,
+    msg_type: MessageType,
+    protocols: Vec<String>,
+) -> Result<Response, ContractError> {
+    let request = CSMessageRequest::new(
+        from.clone(),
+        Addr::unchecked("dapp"),
+        1,
+        msg_type,
+        vec![1, 2, 3],
+        protocols,
+    );
+    let contract = CwCallService::new();
+    contract.handle_request(
+        deps,
+        env,
+        create_mock_info("centralized", "icx", 0),
+        from.nid(),
+        &request.as_bytes(),
+    )
+}
+
+fn set_policy(deps: DepsMut, env: Env, policy: InboundPolicy) -> Result<Response, ContractError> {
+    execute(
+        deps,
+        env,
+        create_mock_info("dapp", "arch", 0),
+        ExecuteMsg::SetInboundPolicy { policy },
+    )
+}
+
+#[test]
+fn test_inbound_policy_allows_matching_sources() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let policy = InboundPolicy {
+        sources: vec![NetworkAddress::from_str("ethereum/0xdapp").unwrap()],
+        networks: vec![NetId::from_str("archway").unwrap()],
+        protocol_sets: vec![],
+        threshold: None,
+    };
+    set_policy(deps.as_mut(), ctx.env.clone(), policy.clone()).unwrap();
+
+    let res = deliver_request(
+        deps.as_mut(),
+        ctx.env.clone(),
+        get_dummy_network_address("archway"),
+        MessageType::CallMessage,
+        vec![],
+    )
+    .unwrap();
+    assert_eq!(res.events[0].ty, "CallMessage");
+
+    let msg = QueryMsg::GetInboundPolicy {
+        dapp: "dapp".to_string(),
+    };
+    let stored: Option<InboundPolicy> =
+        from_json(query(deps.as_ref(), ctx.env, msg).unwrap()).unwrap();
+    assert_eq!(stored, Some(policy));
+}
+
+#[test]
+fn test_inbound_policy_rejects_unknown_source() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let policy = InboundPolicy {
+        sources: vec![NetworkAddress::from_str("archway/0xtrusted").unwrap()],
+        ..Default::default()
+    };
+    set_policy(deps.as_mut(), ctx.env.clone(), policy).unwrap();
+
+    let res = deliver_request(
+        deps.as_mut(),
+        ctx.env.clone(),
+        get_dummy_network_address("archway"),
+        MessageType::CallMessage,
+        vec![],
+    )
+    .unwrap();
+    assert_eq!(res.events[0].ty, "CallMessageRejected");
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        contract
+            .query_last_request_id(deps.as_ref().storage)
+            .unwrap(),
+        ctx.request_id
+    );
+
+    let res = deliver_request(
+        deps.as_mut(),
+        ctx.env,
+        get_dummy_network_address("archway"),
+        MessageType::CallMessageWithRollback,
+        vec![],
+    )
+    .unwrap();
+    assert_eq!(res.attributes[1].value, "reject_unauthorized_request");
+    assert_eq!(res.messages.len(), 1);
+}
+
+#[test]
+fn test_inbound_policy_requires_protocol_set() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let policy = InboundPolicy {
+        protocol_sets: vec![vec!["centralized".to_string()]],
+        ..Default::default()
+    };
+    set_policy(deps.as_mut(), ctx.env.clone(), policy).unwrap();
+
+    let from = get_dummy_network_address("archway");
+    let res = deliver_request(
+        deps.as_mut(),
+        ctx.env.clone(),
+        from.clone(),
+        MessageType::CallMessage,
+        vec![],
+    )
+    .unwrap();
+    assert_eq!(res.events[0].ty, "CallMessage");
+
+    let policy = InboundPolicy {
+        protocol_sets: vec![vec!["ibc".to_string()]],
+        ..Default::default()
+    };
+    set_policy(deps.as_mut(), ctx.env.clone(), policy).unwrap();
+    let res = deliver_request(
+        deps.as_mut(),
+        ctx.env.clone(),
+        from,
+        MessageType::CallMessage,
+        vec!["centralized".to_string()],
+    )
+    .unwrap();
+    assert_eq!(res.events[0].ty, "CallMessageRejected");
+
+    execute(
+        deps.as_mut(),
+        ctx.env,
+        create_mock_info("dapp", "arch", 0),
+        ExecuteMsg::RemoveInboundPolicy {},
+    )
+    .unwrap();
+    assert!(contract
+        .get_inbound_policy(deps.as_ref().storage, &Addr::unchecked("dapp"))
+        .is_none());
+}
+
+#[test]
+fn test_inbound_policy_rejects_empty_protocol_set() {
+    let mut deps = deps();
+    let ctx = TestContext::default();
+
+    let policy = InboundPolicy {
+        protocol_sets: vec![vec![]],
+        ..Default::default()
+    };
+    let err = set_policy(deps.as_mut(), ctx.env, policy).unwrap_err();
+    assert_eq!(err.to_string(), "InvalidInboundPolicy");
+}
+
+#[test]
+fn test_inbound_policy_matches_delivered_protocols() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let protocols = vec!["centralized".to_string(), "ibc".to_string()];
+    let policy = InboundPolicy {
+        protocol_sets: vec![protocols.clone()],
+        threshold: Some(1),
+        ..Default::default()
+    };
+    set_policy(deps.as_mut(), ctx.env.clone(), policy).unwrap();
+
+    // the request claims the required set but only one of its connections delivered it
+    let from = get_dummy_network_address("archway");
+    let request = CSMessageRequest::new(
+        from.clone(),
+        Addr::unchecked("dapp"),
+        1,
+        MessageType::CallMessage,
+        vec![1, 2, 3],
+        protocols,
+    )
+    .with_threshold(Some(1));
+    let res = contract
+        .handle_request(
+            deps.as_mut(),
+            ctx.env,
+            create_mock_info("centralized", "icx", 0),
+            from.nid(),
+            &request.as_bytes(),
+        )
+        .unwrap();
+    assert_eq!(res.events[0].ty, "CallMessageRejected");
+}
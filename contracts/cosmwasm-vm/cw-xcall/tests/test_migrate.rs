@@ -0,0 +1,167 @@
+mod setup;
+
+use setup::{test::*, *};
+
+use cosmwasm_std::{Deps, Storage};
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Map;
+use cw_xcall::{
+    execute, migrate, migrations::BACKFILL_BATCH_SIZE, state::CwCallService,
+    types::pending::DeliveryKind, types::rollback::Rollback, MigrateMsg,
+};
+use cw_xcall_lib::{xcall_msg::ExecuteMsg, xcall_query_msg::MessageStatus};
+
+const CONTRACT_NAME: &str = "crates.io:cw-xcall";
+
+/// Writes the state of a 0.2.1 deployment: `count` two-way messages awaiting their response,
+/// stored before rollbacks had a callback flag or threshold, and a request delivered by one of its
+/// two protocols, stored before delivery records existed.
+fn store_v0_2_1_state(storage: &mut dyn Storage, count: u128) {
+    set_contract_version(storage, CONTRACT_NAME, "0.2.1").unwrap();
+    for sn in 1..=count {
+        storage.set(
+            &Map::<u128, Rollback>::new("requests").key(sn),
+            br#"{"from":"xcall","to":"archway/xcall","protocols":["centralized","ibc"],"rollback":[1,2,3],"enabled":false}"#,
+        );
+    }
+    Map::<(Vec<u8>, String), bool>::new("pending_requests")
+        .save(storage, (vec![1, 2], "centralized".to_string()), &true)
+        .unwrap();
+}
+
+#[test]
+fn test_migrate_backfills_old_state() {
+    let mut deps = deps();
+    let ctx = TestContext::default();
+    let contract = CwCallService::new();
+    store_v0_2_1_state(deps.as_mut().storage, 1);
+
+    let msg = MigrateMsg {
+        pending_delivery_ttl: Some(3600),
+    };
+    let res = migrate(deps.as_mut(), ctx.env.clone(), msg).unwrap();
+    let migrations: Vec<&str> = res
+        .attributes
+        .iter()
+        .filter(|attribute| attribute.key == "migration")
+        .map(|attribute| attribute.value.as_str())
+        .collect();
+    assert_eq!(
+        migrations,
+        vec!["backfill_call_requests", "backfill_pending_deliveries"]
+    );
+    assert!(!res.attributes.iter().any(|attr| attr.key == "backfill"));
+
+    let rollback = contract.get_call_request(deps.as_ref().storage, 1).unwrap();
+    assert!(!rollback.enabled());
+    assert_eq!(rollback.sent_at(), Some(ctx.env.block.time));
+    assert_eq!(
+        contract.get_message_status(deps.as_ref().storage, 1),
+        Some(MessageStatus::AwaitingResponse)
+    );
+
+    let pending = contract
+        .get_pending_delivery(deps.as_ref().storage, DeliveryKind::Request, &[1, 2])
+        .unwrap();
+    assert_eq!(pending.protocols, vec!["centralized".to_string()]);
+    assert_eq!(pending.received_at, ctx.env.block.time);
+    assert!(!pending.accepted);
+
+    assert_eq!(
+        contract.get_pending_delivery_ttl(deps.as_ref().storage),
+        Some(3600)
+    );
+    let version = get_contract_version(deps.as_ref().storage).unwrap();
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn test_migrate_backfill_continues_from_cursor() {
+    let mut deps = deps();
+    let ctx = TestContext::default();
+    let contract = CwCallService::new();
+    let count = BACKFILL_BATCH_SIZE as u128 + 5;
+    store_v0_2_1_state(deps.as_mut().storage, count);
+
+    let res = migrate(deps.as_mut(), ctx.env.clone(), MigrateMsg::default()).unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "backfill" && attr.value == "pending"));
+    let sent_at = |deps: Deps, sn| {
+        contract
+            .get_call_request(deps.storage, sn)
+            .unwrap()
+            .sent_at()
+    };
+    assert_eq!(sent_at(deps.as_ref(), count - 5), Some(ctx.env.block.time));
+    assert_eq!(sent_at(deps.as_ref(), count - 4), None);
+    assert!(contract
+        .get_message_status(deps.as_ref().storage, count)
+        .is_none());
+
+    let msg = ExecuteMsg::ContinueBackfill { limit: Some(3) };
+    let info = create_mock_info("anyone", "icx", 0);
+    let res = execute(deps.as_mut(), ctx.env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(res.attributes[2].value, "false");
+    assert_eq!(sent_at(deps.as_ref(), count - 1), None);
+
+    let msg = ExecuteMsg::ContinueBackfill { limit: None };
+    let res = execute(deps.as_mut(), ctx.env.clone(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(res.attributes[2].value, "true");
+    assert_eq!(sent_at(deps.as_ref(), count), Some(ctx.env.block.time));
+    assert_eq!(
+        contract.get_message_status(deps.as_ref().storage, count),
+        Some(MessageStatus::AwaitingResponse)
+    );
+
+    let err = execute(deps.as_mut(), ctx.env, info, msg).unwrap_err();
+    assert_eq!(err.to_string(), "NoBackfillInProgress");
+}
+
+#[test]
+fn test_migrate_same_version_runs_no_migration() {
+    let mut deps = deps();
+    let ctx = TestContext::default();
+    set_contract_version(
+        deps.as_mut().storage,
+        CONTRACT_NAME,
+        env!("CARGO_PKG_VERSION"),
+    )
+    .unwrap();
+
+    let res = migrate(deps.as_mut(), ctx.env, MigrateMsg::default()).unwrap();
+    assert!(!res
+        .attributes
+        .iter()
+        .any(|attribute| attribute.key == "migration"));
+}
+
+#[test]
+fn test_migrate_refuses_downgrade() {
+    let mut deps = deps();
+    let ctx = TestContext::default();
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "9.0.0").unwrap();
+
+    let err = migrate(deps.as_mut(), ctx.env, MigrateMsg::default()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "CannotDowngrade from 9.0.0 to {}",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
+}
+
+#[test]
+fn test_migrate_refuses_other_contract() {
+    let mut deps = deps();
+    let ctx = TestContext::default();
+    set_contract_version(deps.as_mut().storage, "crates.io:cw-mock-dapp", "0.2.1").unwrap();
+
+    let err = migrate(deps.as_mut(), ctx.env, MigrateMsg::default()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "CannotMigrateFromContract crates.io:cw-mock-dapp"
+    );
+}
@@ -0,0 +1,249 @@
+mod setup;
+
+use setup::{test::*, *};
+use std::str::FromStr;
+
+use common::utils::keccak256;
+use cosmwasm_std::{from_json, Addr, Deps, DepsMut, Env, Response};
+use cw_xcall::{
+    error::ContractError,
+    execute,
+    msg::QueryMsg,
+    query,
+    state::{CwCallService, MAX_ORDERED_LOOKAHEAD, MAX_ORDERED_RELEASES},
+    types::request::CSMessageRequest,
+};
+use cw_xcall_lib::{
+    message::msg_type::MessageType, network_address::NetId, xcall_msg::ExecuteMsg,
+    xcall_query_msg::OrderedChannelResponse,
+};
+
+fn deliver_ordered_request(
+    deps: DepsMut,
+    env: Env,
+    sn: u128,
+    ordered_sn: u128,
+) -> Result<Response, ContractError> {
+    let request = CSMessageRequest::new(
+        get_dummy_network_address("archway"),
+        Addr::unchecked("dapp"),
+        sn,
+        MessageType::CallMessage,
+        keccak256(&[1, 2, 3]).to_vec(),
+        vec![],
+    )
+    .with_ordered_sn(Some(ordered_sn));
+    let contract = CwCallService::new();
+    contract.handle_request(
+        deps,
+        env,
+        create_mock_info("centralized", "icx", 0),
+        NetId::from_str("archway").unwrap(),
+        &request.as_bytes(),
+    )
+}
+
+fn query_channel(deps: Deps, env: Env) -> OrderedChannelResponse {
+    let msg = QueryMsg::GetOrderedChannel {
+        from: get_dummy_network_address("archway"),
+        to: "dapp".to_string(),
+        start_after: None,
+        limit: None,
+    };
+    from_json(query(deps, env, msg).unwrap()).unwrap()
+}
+
+fn call_message_sns(res: &Response) -> Vec<String> {
+    res.events
+        .iter()
+        .filter(|event| event.ty == "CallMessage")
+        .flat_map(|event| event.attributes.iter())
+        .filter(|attribute| attribute.key == "sn")
+        .map(|attribute| attribute.value.clone())
+        .collect()
+}
+
+#[test]
+fn test_ordered_requests_are_delivered_in_order() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let res = deliver_ordered_request(deps.as_mut(), ctx.env.clone(), 12, 2).unwrap();
+    assert!(call_message_sns(&res).is_empty());
+    assert_eq!(res.events[0].ty, "CallMessageBuffered");
+    let res = deliver_ordered_request(deps.as_mut(), ctx.env.clone(), 13, 3).unwrap();
+    assert!(call_message_sns(&res).is_empty());
+
+    let channel = query_channel(deps.as_ref(), ctx.env.clone());
+    assert_eq!(channel.next_sn, 1);
+    let buffered: Vec<u128> = channel.buffered.iter().map(|b| b.ordered_sn).collect();
+    assert_eq!(buffered, vec![2, 3]);
+    assert_eq!(channel.buffered[0].sn, 12);
+
+    let res = deliver_ordered_request(deps.as_mut(), ctx.env.clone(), 11, 1).unwrap();
+    assert_eq!(call_message_sns(&res), vec!["11", "12", "13"]);
+
+    let channel = query_channel(deps.as_ref(), ctx.env);
+    assert_eq!(channel.next_sn, 4);
+    assert!(channel.buffered.is_empty());
+}
+
+#[test]
+fn test_ordered_request_delivered_twice_is_rejected() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    deliver_ordered_request(deps.as_mut(), ctx.env.clone(), 11, 1).unwrap();
+    let err = deliver_ordered_request(deps.as_mut(), ctx.env.clone(), 11, 1).unwrap_err();
+    assert_eq!(err.to_string(), "OrderedRequestAlreadyDelivered 1");
+
+    deliver_ordered_request(deps.as_mut(), ctx.env.clone(), 13, 3).unwrap();
+    let err = deliver_ordered_request(deps.as_mut(), ctx.env, 13, 3).unwrap_err();
+    assert_eq!(err.to_string(), "OrderedRequestAlreadyBuffered 3");
+}
+
+#[test]
+fn test_skip_ordered_sequence_releases_buffered_requests() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    deliver_ordered_request(deps.as_mut(), ctx.env.clone(), 12, 2).unwrap();
+
+    let skip = |ordered_sn| ExecuteMsg::SkipOrderedSequence {
+        from: get_dummy_network_address("archway"),
+        to: "dapp".to_string(),
+        ordered_sn,
+    };
+    let err = execute(
+        deps.as_mut(),
+        ctx.env.clone(),
+        create_mock_info("relayer", "icx", 0),
+        skip(1),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "OnlyAdmin");
+
+    let err = execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), skip(2)).unwrap_err();
+    assert_eq!(err.to_string(), "InvalidOrderedSequence expected 1 got 2");
+
+    let res = execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), skip(1)).unwrap();
+    assert_eq!(res.events[0].ty, "OrderedSequenceSkipped");
+    assert_eq!(call_message_sns(&res), vec!["12"]);
+    assert_eq!(query_channel(deps.as_ref(), ctx.env).next_sn, 3);
+}
+
+#[test]
+fn test_release_of_buffered_requests_is_bounded() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    for ordered_sn in 2..=12 {
+        deliver_ordered_request(deps.as_mut(), ctx.env.clone(), 10 + ordered_sn, ordered_sn)
+            .unwrap();
+    }
+    let res = deliver_ordered_request(deps.as_mut(), ctx.env.clone(), 11, 1).unwrap();
+    assert_eq!(call_message_sns(&res).len(), 1 + MAX_ORDERED_RELEASES);
+    assert_eq!(query_channel(deps.as_ref(), ctx.env.clone()).next_sn, 12);
+
+    let msg = ExecuteMsg::ReleaseBuffered {
+        from: get_dummy_network_address("archway"),
+        to: "dapp".to_string(),
+        limit: None,
+    };
+    let info = create_mock_info("anyone", "icx", 0);
+    let res = execute(deps.as_mut(), ctx.env.clone(), info, msg).unwrap();
+    assert_eq!(call_message_sns(&res), vec!["22"]);
+
+    let channel = query_channel(deps.as_ref(), ctx.env);
+    assert_eq!(channel.next_sn, 13);
+    assert!(channel.buffered.is_empty());
+}
+
+#[test]
+fn test_ordered_request_too_far_ahead_is_rejected() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let ordered_sn = 2 + MAX_ORDERED_LOOKAHEAD;
+    let err = deliver_ordered_request(deps.as_mut(), ctx.env.clone(), 11, ordered_sn).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!("OrderedRequestTooFarAhead expected 1 got {ordered_sn}")
+    );
+    deliver_ordered_request(deps.as_mut(), ctx.env, 11, ordered_sn - 1).unwrap();
+}
+
+#[test]
+fn test_send_call_assigns_ordered_sn_per_destination() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    mock_connection_fee_query(&mut deps);
+
+    let ordered_send = ExecuteMsg::SendCall {
+        envelope: get_dummy_call_msg_envelop().with_ordered(),
+        to: get_dummy_network_address("archway"),
+        max_fee: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        ctx.env.clone(),
+        ctx.info.clone(),
+        ordered_send,
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "ExtendedMessagesNotSupported archway");
+
+    let msg = ExecuteMsg::SetExtendedMessages {
+        nid: NetId::from_str("archway").unwrap(),
+        enabled: true,
+    };
+    execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), msg).unwrap();
+    let enabled: bool = from_json(
+        query(
+            deps.as_ref(),
+            ctx.env.clone(),
+            QueryMsg::GetExtendedMessages {
+                nid: NetId::from_str("archway").unwrap(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(enabled);
+
+    for _ in 0..2 {
+        let msg = ExecuteMsg::SendCall {
+            envelope: get_dummy_call_msg_envelop().with_ordered(),
+            to: get_dummy_network_address("archway"),
+            max_fee: None,
+        };
+        execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), msg).unwrap();
+    }
+    let msg = ExecuteMsg::SendCall {
+        envelope: get_dummy_call_msg_envelop(),
+        to: get_dummy_network_address("archway"),
+        max_fee: None,
+    };
+    execute(deps.as_mut(), ctx.env, ctx.info.clone(), msg).unwrap();
+
+    let next = contract
+        .get_next_ordered_sn(
+            deps.as_mut().storage,
+            &ctx.info.sender,
+            &get_dummy_network_address("archway"),
+        )
+        .unwrap();
+    assert_eq!(next, 3);
+}
@@ -0,0 +1,337 @@
+mod setup;
+
+use setup::{test::*, *};
+use std::str::FromStr;
+
+use cosmwasm_std::{from_json, testing::mock_env, Addr, Deps};
+use cw_xcall::{execute, msg::QueryMsg, query, state::CwCallService};
+use cw_xcall_lib::{
+    network_address::NetId, xcall_msg::ExecuteMsg, xcall_query_msg::PauseStateResponse,
+};
+
+fn pause_network(outbound: bool, inbound: bool, execution: bool) -> ExecuteMsg {
+    ExecuteMsg::SetNetworkPause {
+        nid: NetId::from_str("archway").unwrap(),
+        outbound,
+        inbound,
+        execution,
+    }
+}
+
+fn query_pause_state(deps: Deps) -> PauseStateResponse {
+    let msg = QueryMsg::GetPauseState {
+        nid: NetId::from_str("archway").unwrap(),
+    };
+    from_json(query(deps, mock_env(), msg).unwrap()).unwrap()
+}
+
+#[test]
+#[should_panic(expected = "MissingRole")]
+fn test_set_network_pause_fail_on_unauthorized_sender() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let info = create_mock_info("user", "icx", 0);
+    execute(
+        deps.as_mut(),
+        ctx.env,
+        info,
+        pause_network(true, true, true),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_pauser_can_pause_network() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let msg = ExecuteMsg::SetPauser {
+        address: "pauser".to_string(),
+    };
+    execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), msg).unwrap();
+
+    let info = create_mock_info("pauser", "icx", 0);
+    let res = execute(
+        deps.as_mut(),
+        ctx.env,
+        info,
+        pause_network(true, false, true),
+    )
+    .unwrap();
+    assert_eq!(res.events[0].ty, "NetworkPauseUpdated");
+
+    let state = query_pause_state(deps.as_ref());
+    assert!(!state.global);
+    assert!(state.outbound);
+    assert!(!state.inbound);
+    assert!(state.execution);
+}
+
+#[test]
+fn test_set_pauser_revokes_previous_pauser() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    for address in ["pauser", "new_pauser"] {
+        let msg = ExecuteMsg::SetPauser {
+            address: address.to_string(),
+        };
+        execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), msg).unwrap();
+    }
+
+    let info = create_mock_info("pauser", "icx", 0);
+    let err = execute(
+        deps.as_mut(),
+        ctx.env.clone(),
+        info,
+        pause_network(true, false, false),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "MissingRole pauser");
+
+    let info = create_mock_info("new_pauser", "icx", 0);
+    execute(
+        deps.as_mut(),
+        ctx.env,
+        info,
+        pause_network(true, false, false),
+    )
+    .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "NetworkPaused")]
+fn test_send_call_fail_on_paused_network() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    mock_connection_fee_query(&mut deps);
+
+    execute(
+        deps.as_mut(),
+        ctx.env.clone(),
+        ctx.info.clone(),
+        pause_network(true, false, false),
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::SendCall {
+        envelope: get_dummy_call_msg_envelop(),
+        to: get_dummy_network_address("archway"),
+        max_fee: None,
+    };
+    execute(deps.as_mut(), ctx.env, ctx.info, msg).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "ContractPaused")]
+fn test_send_call_fail_on_global_pause() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+    mock_connection_fee_query(&mut deps);
+
+    let msg = ExecuteMsg::SetGlobalPause { paused: true };
+    execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::SendCall {
+        envelope: get_dummy_call_msg_envelop(),
+        to: get_dummy_network_address("archway"),
+        max_fee: None,
+    };
+    execute(deps.as_mut(), ctx.env, ctx.info, msg).unwrap();
+}
+
+#[test]
+fn test_inbound_messages_are_queued_and_resumed() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    execute(
+        deps.as_mut(),
+        ctx.env.clone(),
+        ctx.info.clone(),
+        pause_network(false, true, false),
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::HandleMessage {
+        from_nid: NetId::from_str("archway").unwrap(),
+        msg: get_dummy_request_message().as_bytes(),
+    };
+    let info = create_mock_info("centralized", "icx", 0);
+    let res = execute(deps.as_mut(), ctx.env.clone(), info, msg).unwrap();
+    assert_eq!(res.events[0].ty, "MessageQueued");
+    assert_eq!(query_pause_state(deps.as_ref()).queued_messages, 1);
+    assert_eq!(
+        contract
+            .query_last_request_id(deps.as_ref().storage)
+            .unwrap(),
+        0
+    );
+
+    let resume = ExecuteMsg::ResumeInbound {
+        nid: NetId::from_str("archway").unwrap(),
+        limit: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        ctx.env.clone(),
+        ctx.info.clone(),
+        resume.clone(),
+    );
+    assert!(res.is_err());
+
+    execute(
+        deps.as_mut(),
+        ctx.env.clone(),
+        ctx.info.clone(),
+        pause_network(false, false, false),
+    )
+    .unwrap();
+
+    let info = create_mock_info("anyone", "icx", 0);
+    let res = execute(deps.as_mut(), ctx.env, info, resume).unwrap();
+    assert_eq!(res.events[0].ty, "CallMessage");
+    assert_eq!(query_pause_state(deps.as_ref()).queued_messages, 0);
+    assert!(contract.get_proxy_request(deps.as_ref().storage, 1).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "NetworkPaused")]
+fn test_execute_call_fail_on_paused_execution() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_execute_call(deps.as_mut().storage, &contract);
+
+    execute(
+        deps.as_mut(),
+        ctx.env.clone(),
+        ctx.info.clone(),
+        pause_network(false, false, true),
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::ExecuteCall {
+        request_id: ctx.request_id,
+        data: vec![1, 2, 3],
+    };
+    execute(deps.as_mut(), ctx.env, ctx.info, msg).unwrap();
+}
+
+#[test]
+fn test_paused_inbound_only_queues_messages_from_valid_connections() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    execute(
+        deps.as_mut(),
+        ctx.env.clone(),
+        ctx.info.clone(),
+        pause_network(false, true, false),
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::HandleMessage {
+        from_nid: NetId::from_str("archway").unwrap(),
+        msg: get_dummy_request_message().as_bytes(),
+    };
+    let info = create_mock_info("attacker", "icx", 0);
+    let err = execute(deps.as_mut(), ctx.env.clone(), info, msg).unwrap_err();
+    assert_eq!(err.to_string(), "ProtocolsMismatch");
+
+    let msg = ExecuteMsg::HandleMessage {
+        from_nid: NetId::from_str("archway").unwrap(),
+        msg: vec![1, 2, 3],
+    };
+    let info = create_mock_info("centralized", "icx", 0);
+    assert!(execute(deps.as_mut(), ctx.env, info, msg).is_err());
+    assert_eq!(query_pause_state(deps.as_ref()).queued_messages, 0);
+}
+
+#[test]
+fn test_resume_inbound_keeps_failing_message_queued() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    execute(
+        deps.as_mut(),
+        ctx.env.clone(),
+        ctx.info.clone(),
+        pause_network(false, true, false),
+    )
+    .unwrap();
+    let msg = ExecuteMsg::HandleMessage {
+        from_nid: NetId::from_str("archway").unwrap(),
+        msg: get_dummy_request_message().as_bytes(),
+    };
+    let info = create_mock_info("centralized", "icx", 0);
+    execute(deps.as_mut(), ctx.env.clone(), info, msg).unwrap();
+    execute(
+        deps.as_mut(),
+        ctx.env.clone(),
+        ctx.info.clone(),
+        pause_network(false, false, false),
+    )
+    .unwrap();
+
+    // the connection that queued the message is no longer the default one
+    contract
+        .store_default_connection(
+            deps.as_mut().storage,
+            NetId::from_str("archway").unwrap(),
+            Addr::unchecked("other_connection"),
+        )
+        .unwrap();
+    let resume = ExecuteMsg::ResumeInbound {
+        nid: NetId::from_str("archway").unwrap(),
+        limit: None,
+    };
+    let err = execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), resume).unwrap_err();
+    assert_eq!(err.to_string(), "QueuedMessageFailed 1: ProtocolsMismatch");
+
+    let drop = ExecuteMsg::DropQueuedMessage {
+        nid: NetId::from_str("archway").unwrap(),
+        id: 1,
+    };
+    let info = create_mock_info("anyone", "icx", 0);
+    let err = execute(deps.as_mut(), ctx.env.clone(), info, drop.clone()).unwrap_err();
+    assert_eq!(err.to_string(), "MissingRole pauser");
+
+    let res = execute(
+        deps.as_mut(),
+        ctx.env.clone(),
+        ctx.info.clone(),
+        drop.clone(),
+    )
+    .unwrap();
+    assert_eq!(res.events[0].ty, "QueuedMessageDropped");
+    assert_eq!(query_pause_state(deps.as_ref()).queued_messages, 0);
+
+    let err = execute(deps.as_mut(), ctx.env, ctx.info, drop).unwrap_err();
+    assert_eq!(err.to_string(), "QueuedMessageNotFound 1");
+}
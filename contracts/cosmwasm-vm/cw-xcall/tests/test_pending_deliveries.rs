@@ -0,0 +1,196 @@
+mod setup;
+
+use setup::{test::*, *};
+use std::str::FromStr;
+
+use common::utils::keccak256;
+use cosmwasm_std::{from_json, testing::mock_env, Addr, Deps, DepsMut, Env};
+use cw_xcall::{
+    execute, msg::QueryMsg, query, state::CwCallService, types::request::CSMessageRequest,
+};
+use cw_xcall_lib::{
+    message::msg_type::MessageType,
+    network_address::NetId,
+    xcall_msg::ExecuteMsg,
+    xcall_query_msg::{InboundPolicy, PendingDeliveriesResponse},
+};
+
+fn deliver_request(deps: DepsMut, env: Env, protocol: &str) -> CSMessageRequest {
+    let request = CSMessageRequest::new(
+        get_dummy_network_address("archway"),
+        Addr::unchecked("dapp"),
+        1,
+        MessageType::CallMessage,
+        keccak256(&[1, 2, 3]).to_vec(),
+        vec!["centralized".to_string(), "ibc".to_string()],
+    );
+    let contract = CwCallService::new();
+    contract
+        .handle_request(
+            deps,
+            env,
+            create_mock_info(protocol, "icx", 0),
+            NetId::from_str("archway").unwrap(),
+            &request.as_bytes(),
+        )
+        .unwrap();
+    request
+}
+
+fn query_pending_requests(deps: Deps) -> PendingDeliveriesResponse {
+    let msg = QueryMsg::GetPendingRequests {
+        start_after: None,
+        limit: None,
+    };
+    from_json(query(deps, mock_env(), msg).unwrap()).unwrap()
+}
+
+#[test]
+fn test_query_pending_requests() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let request = deliver_request(deps.as_mut(), ctx.env.clone(), "centralized");
+
+    let res = query_pending_requests(deps.as_ref());
+    assert_eq!(res.deliveries.len(), 1);
+    let pending = &res.deliveries[0];
+    assert_eq!(pending.hash, keccak256(&request.as_bytes()).to_vec());
+    assert_eq!(pending.delivered, vec!["centralized".to_string()]);
+    assert_eq!(pending.missing, vec!["ibc".to_string()]);
+    assert_eq!(pending.received_at, ctx.env.block.time.seconds());
+    assert!(!pending.accepted);
+
+    deliver_request(deps.as_mut(), ctx.env, "ibc");
+    assert!(query_pending_requests(deps.as_ref()).deliveries.is_empty());
+}
+
+#[test]
+fn test_purge_expired_deliveries() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    deliver_request(deps.as_mut(), ctx.env.clone(), "centralized");
+
+    let msg = ExecuteMsg::SetPendingDeliveryTtl { ttl: Some(3600) };
+    execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), msg).unwrap();
+
+    let info = create_mock_info("anyone", "icx", 0);
+    let msg = ExecuteMsg::PurgeExpiredDeliveries { limit: None };
+    let res = execute(deps.as_mut(), ctx.env.clone(), info.clone(), msg.clone()).unwrap();
+    assert!(res.events.is_empty());
+    assert_eq!(query_pending_requests(deps.as_ref()).deliveries.len(), 1);
+
+    let mut env = ctx.env;
+    env.block.time = env.block.time.plus_seconds(3600);
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(res.events[0].ty, "PendingDeliveryPurged");
+    assert_eq!(res.events[0].attributes[0].value, "request");
+    assert_eq!(res.events[0].attributes[2].value, "centralized");
+    assert!(query_pending_requests(deps.as_ref()).deliveries.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "PendingDeliveryTtlNotSet")]
+fn test_purge_expired_deliveries_without_ttl() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let msg = ExecuteMsg::PurgeExpiredDeliveries { limit: None };
+    execute(deps.as_mut(), ctx.env, ctx.info, msg).unwrap();
+}
+
+#[test]
+fn test_admin_purges_pending_delivery() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let request = deliver_request(deps.as_mut(), ctx.env.clone(), "centralized");
+    let hash = keccak256(&request.as_bytes()).to_vec();
+
+    let msg = ExecuteMsg::PurgePendingDelivery { hash: hash.clone() };
+    let info = create_mock_info("user", "icx", 0);
+    let err = execute(deps.as_mut(), ctx.env.clone(), info, msg.clone()).unwrap_err();
+    assert_eq!(err.to_string(), "OnlyAdmin");
+
+    let res = execute(deps.as_mut(), ctx.env, ctx.info, msg).unwrap();
+    assert_eq!(res.events.len(), 1);
+    assert!(query_pending_requests(deps.as_ref()).deliveries.is_empty());
+    assert!(contract
+        .get_pending_requests_by_hash(deps.as_ref().storage, hash)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_purge_keeps_accepted_delivery() {
+    let mut deps = deps();
+    let contract = CwCallService::new();
+
+    let ctx = TestContext::default();
+    ctx.init_context(deps.as_mut().storage, &contract);
+
+    let policy = InboundPolicy {
+        threshold: Some(1),
+        ..Default::default()
+    };
+    contract
+        .store_inbound_policy(deps.as_mut().storage, &Addr::unchecked("dapp"), &policy)
+        .unwrap();
+    let request = CSMessageRequest::new(
+        get_dummy_network_address("archway"),
+        Addr::unchecked("dapp"),
+        1,
+        MessageType::CallMessage,
+        keccak256(&[1, 2, 3]).to_vec(),
+        vec!["centralized".to_string(), "ibc".to_string()],
+    )
+    .with_threshold(Some(1));
+    let handle_request = |deps: DepsMut, env: Env, protocol: &str| {
+        contract
+            .handle_request(
+                deps,
+                env,
+                create_mock_info(protocol, "icx", 0),
+                NetId::from_str("archway").unwrap(),
+                &request.as_bytes(),
+            )
+            .unwrap()
+    };
+    let res = handle_request(deps.as_mut(), ctx.env.clone(), "centralized");
+    assert_eq!(res.events[0].ty, "CallMessage");
+
+    let msg = ExecuteMsg::SetPendingDeliveryTtl { ttl: Some(3600) };
+    execute(deps.as_mut(), ctx.env.clone(), ctx.info.clone(), msg).unwrap();
+    let mut env = ctx.env.clone();
+    env.block.time = env.block.time.plus_seconds(3600);
+
+    let msg = ExecuteMsg::PurgeExpiredDeliveries { limit: None };
+    let info = create_mock_info("anyone", "icx", 0);
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert!(res.events.is_empty());
+
+    let msg = ExecuteMsg::PurgePendingDelivery {
+        hash: keccak256(&request.as_bytes()).to_vec(),
+    };
+    let err = execute(deps.as_mut(), env.clone(), ctx.info, msg).unwrap_err();
+    assert_eq!(err.to_string(), "PendingDeliveryAccepted");
+    assert!(query_pending_requests(deps.as_ref()).deliveries[0].accepted);
+
+    // the late delivery is not accepted again and clears the entry
+    let res = handle_request(deps.as_mut(), env, "ibc");
+    assert!(res.events.is_empty());
+    assert!(query_pending_requests(deps.as_ref()).deliveries.is_empty());
+}
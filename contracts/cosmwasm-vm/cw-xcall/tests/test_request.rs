@@ -182,6 +182,7 @@ fn invalid_network_id() {
 
     let response = contract.handle_message(
         mock_deps.as_mut(),
+        mock_env(),
         mock_info,
         NetId::from_str("nid").unwrap(),
         vec![],